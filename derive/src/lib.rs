@@ -0,0 +1,123 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Custom derive macros for the `gaps` crate.
+//!
+//! Please use these macros via the `gaps` crate's `derive` feature.
+
+#![warn(
+  missing_debug_implementations,
+  missing_docs,
+  rust_2018_idioms,
+  trivial_numeric_casts,
+  unreachable_pub,
+  unused_import_braces,
+  unused_qualifications,
+  unused_results
+)]
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::parse_macro_input;
+use syn::parse_quote;
+use syn::Data;
+use syn::DeriveInput;
+use syn::Error;
+use syn::Fields;
+use syn::Member;
+use syn::Type;
+
+
+/// Find the single field of a newtype, reporting its accessor and type.
+fn newtype_field(input: &DeriveInput) -> Result<(Member, Type), Error> {
+  let fields = match &input.data {
+    Data::Struct(data) => &data.fields,
+    _ => {
+      return Err(Error::new_spanned(
+        input,
+        "can only be derived for structs with a single field",
+      ))
+    },
+  };
+
+  let mut iter = fields.iter();
+  match (iter.next(), iter.next()) {
+    (Some(field), None) => {
+      let member = match (&field.ident, fields) {
+        (Some(ident), Fields::Named(..)) => Member::Named(ident.clone()),
+        _ => Member::Unnamed(0.into()),
+      };
+      Ok((member, field.ty.clone()))
+    },
+    _ => Err(Error::new_spanned(
+      input,
+      "can only be derived for structs with a single field",
+    )),
+  }
+}
+
+/// Generate an implementation of the trait with the provided name,
+/// with methods produced by `methods`.
+fn derive<F>(input: DeriveInput, trait_: &str, methods: F) -> TokenStream2
+where
+  F: FnOnce(&Member) -> TokenStream2,
+{
+  let (member, ty) = match newtype_field(&input) {
+    Ok(field) => field,
+    Err(err) => return err.to_compile_error(),
+  };
+
+  let trait_ = syn::Ident::new(trait_, proc_macro2::Span::call_site());
+  let name = &input.ident;
+  let mut generics = input.generics.clone();
+  generics
+    .make_where_clause()
+    .predicates
+    .push(parse_quote! { #ty: ::gaps::#trait_ });
+  let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+  let methods = methods(&member);
+
+  quote! {
+    impl #impl_generics ::gaps::#trait_ for #name #ty_generics #where_clause {
+      #methods
+    }
+  }
+}
+
+
+/// Derive `gaps::Inc` for a newtype by delegating to its only field.
+#[proc_macro_derive(Inc)]
+pub fn derive_inc(input: TokenStream) -> TokenStream {
+  let input = parse_macro_input!(input as DeriveInput);
+  derive(input, "Inc", |member| {
+    quote! {
+      fn inc(self) -> Self {
+        Self { #member: ::gaps::Inc::inc(self.#member) }
+      }
+
+      fn checked_inc(self) -> ::std::option::Option<Self> {
+        ::gaps::Inc::checked_inc(self.#member).map(|value| Self { #member: value })
+      }
+    }
+  })
+  .into()
+}
+
+/// Derive `gaps::Dec` for a newtype by delegating to its only field.
+#[proc_macro_derive(Dec)]
+pub fn derive_dec(input: TokenStream) -> TokenStream {
+  let input = parse_macro_input!(input as DeriveInput);
+  derive(input, "Dec", |member| {
+    quote! {
+      fn dec(self) -> Self {
+        Self { #member: ::gaps::Dec::dec(self.#member) }
+      }
+
+      fn checked_dec(self) -> ::std::option::Option<Self> {
+        ::gaps::Dec::checked_dec(self.#member).map(|value| Self { #member: value })
+      }
+    }
+  })
+  .into()
+}
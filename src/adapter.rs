@@ -0,0 +1,577 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::iter::FusedIterator;
+use std::ops::Bound;
+use std::ops::Bound::Excluded;
+use std::ops::Bound::Included;
+use std::ops::Bound::Unbounded;
+
+use crate::bounds::end_lt_end;
+use crate::bounds::lower_le_lower;
+use crate::bounds::start_le_end;
+use crate::bounds::start_lt_start;
+use crate::Gap;
+use crate::Inc;
+use crate::Multiple;
+use crate::Step;
+
+
+/// The type of a function finding the last value of a chunk of a gap.
+type AdvanceFn<T> = fn(T, usize, &Bound<T>) -> Option<T>;
+
+
+/// Find the value `n` values past `first`, if it lies before `end`,
+/// incrementing one value at a time.
+fn advance_linear<T>(first: T, n: usize, end: &Bound<T>) -> Option<T>
+where
+  T: Clone + Ord + Inc,
+{
+  let mut last = first;
+  for _ in 0..n {
+    if !end_lt_end(&Included(last.clone()), end) {
+      return None
+    }
+    last = last.checked_inc()?;
+  }
+  Some(last).filter(|last| end_lt_end(&Included(last.clone()), end))
+}
+
+/// Find the value `n` values past `first`, if it lies before `end`,
+/// skipping over all of them at once.
+fn advance_step<T>(first: T, n: usize, end: &Bound<T>) -> Option<T>
+where
+  T: Clone + Ord + Step,
+{
+  first
+    .forward(n)
+    .filter(|last| end_lt_end(&Included(last.clone()), end))
+}
+
+
+/// An iterator adapter splitting the gaps produced by another iterator
+/// into chunks of a maximum length.
+///
+/// Objects of this type are created by [`GapIter::chunks`][crate::GapIter::chunks].
+#[derive(Clone, Debug)]
+pub struct Chunks<I, T> {
+  /// The iterator over gaps that we wrap.
+  iter: I,
+  /// The maximum number of values in a chunk.
+  max_len: usize,
+  /// The remainder of the gap currently being split.
+  gap: Option<(Bound<T>, Bound<T>)>,
+  /// The function used for finding the end of a chunk.
+  advance: AdvanceFn<T>,
+}
+
+impl<I, T> Chunks<I, T>
+where
+  T: Clone + Ord + Inc,
+{
+  pub(crate) fn new(iter: I, max_len: usize) -> Self {
+    assert!(max_len != 0, "chunk length must not be zero");
+
+    Self {
+      iter,
+      max_len,
+      gap: None,
+      advance: advance_linear,
+    }
+  }
+}
+
+impl<I, T> Chunks<I, T>
+where
+  T: Clone + Ord + Step,
+{
+  /// Find the end of each chunk by skipping over all of its values at
+  /// once, instead of incrementing one value at a time.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use maplit::btreeset;
+  /// # use gaps::Gap;
+  /// # use gaps::RangeGappable as _;
+  ///
+  /// let set = btreeset!{0u64};
+  /// let mut chunks = set.gaps(0..).chunks(1 << 62).striding();
+  /// assert_eq!(chunks.next(), Some(Gap::new(Bound::Excluded(0), Bound::Included(1 << 62))));
+  /// assert_eq!(chunks.nth(2), Some(Gap::new(Bound::Excluded(3 << 62), Bound::Unbounded)));
+  /// ```
+  pub fn striding(self) -> Self {
+    Self {
+      advance: advance_step,
+      ..self
+    }
+  }
+}
+
+impl<I, T> Iterator for Chunks<I, T>
+where
+  I: Iterator<Item = Gap<T>>,
+  T: Clone + Ord + Inc,
+{
+  type Item = Gap<T>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let (start, end) = match self.gap.take() {
+      Some(gap) => gap,
+      None => self.iter.next()?.into_bounds(),
+    };
+
+    let first = match &start {
+      Included(start) => Some(start.clone()),
+      Excluded(start) => start.clone().checked_inc(),
+      Unbounded => None,
+    };
+    let first = match first {
+      Some(first) => first,
+      // Without a first value we have no way of splitting the gap.
+      None => return Some(Gap::new(start, end)),
+    };
+
+    match (self.advance)(first, self.max_len - 1, &end) {
+      Some(last) => {
+        self.gap = Some((Excluded(last.clone()), end));
+        Some(Gap::new(start, Included(last)))
+      },
+      None => Some(Gap::new(start, end)),
+    }
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    let (lower, _) = self.iter.size_hint();
+    let lower = lower.saturating_add(self.gap.is_some() as usize);
+    // Any gap may be split into an arbitrary number of chunks.
+    (lower, None)
+  }
+}
+
+impl<I, T> FusedIterator for Chunks<I, T>
+where
+  I: FusedIterator<Item = Gap<T>>,
+  T: Clone + Ord + Inc,
+{
+}
+
+
+/// An iterator adapter snapping the gaps produced by another iterator
+/// to multiples of a step.
+///
+/// Objects of this type are created by [`GapIter::with_step`][crate::GapIter::with_step].
+#[derive(Clone, Debug)]
+pub struct Stepped<I, T> {
+  /// The iterator over gaps that we wrap.
+  iter: I,
+  /// The step that values are expected to be multiples of.
+  step: T,
+  /// A snapped gap that has not been reported yet, because it may need
+  /// to be merged with the following one.
+  pending: Option<(Bound<T>, Bound<T>)>,
+}
+
+impl<I, T> Stepped<I, T> {
+  pub(crate) fn new(iter: I, step: T) -> Self {
+    Self {
+      iter,
+      step,
+      pending: None,
+    }
+  }
+}
+
+impl<I, T> Stepped<I, T>
+where
+  I: Iterator<Item = Gap<T>>,
+  T: Clone + Ord + Multiple,
+{
+  /// Snap the next gap to multiples of our step, skipping gaps that do
+  /// not contain any such multiple.
+  fn next_snapped(&mut self) -> Option<(Bound<T>, Bound<T>)> {
+    loop {
+      let (start, end) = self.iter.next()?.into_bounds();
+      let step = || self.step.clone();
+      let start = match start {
+        Included(start) => start.round_up(step()).map(Included),
+        Excluded(start) => start.next_multiple(step()).map(Included),
+        Unbounded => Some(Unbounded),
+      };
+      let end = match end {
+        Included(end) => end.round_down(step()).map(Included),
+        Excluded(end) => end.prev_multiple(step()).map(Included),
+        Unbounded => Some(Unbounded),
+      };
+
+      match (start, end) {
+        (Some(Included(start)), Some(Included(end))) if start > end => continue,
+        (Some(start), Some(end)) => break Some((start, end)),
+        _ => continue,
+      }
+    }
+  }
+}
+
+impl<I, T> Iterator for Stepped<I, T>
+where
+  I: Iterator<Item = Gap<T>>,
+  T: Clone + Ord + Multiple,
+{
+  type Item = Gap<T>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let (start, mut end) = match self.pending.take() {
+      Some(gap) => gap,
+      None => self.next_snapped()?,
+    };
+
+    // Elements that are not multiples of our step may cause what is a
+    // single gap in terms of steps to be reported as multiple ones.
+    // Merge those back together.
+    while let Included(last) = &end {
+      let next_multiple = last.clone().next_multiple(self.step.clone());
+      match self.next_snapped() {
+        Some((Included(next), next_end)) if next_multiple.as_ref() == Some(&next) => end = next_end,
+        gap => {
+          self.pending = gap;
+          break
+        },
+      }
+    }
+    Some(Gap::new(start, end))
+  }
+}
+
+impl<I, T> FusedIterator for Stepped<I, T>
+where
+  I: FusedIterator<Item = Gap<T>>,
+  T: Clone + Ord + Multiple,
+{
+}
+
+
+/// An iterator adapter aligning the gaps produced by another iterator
+/// to a given alignment.
+///
+/// Objects of this type are created by [`GapIter::align`][crate::GapIter::align].
+#[derive(Clone, Debug)]
+pub struct Aligned<I, T> {
+  /// The iterator over gaps that we wrap.
+  iter: I,
+  /// The alignment to use.
+  align: T,
+}
+
+impl<I, T> Aligned<I, T> {
+  pub(crate) fn new(iter: I, align: T) -> Self {
+    Self { iter, align }
+  }
+}
+
+impl<I, T> Iterator for Aligned<I, T>
+where
+  I: Iterator<Item = Gap<T>>,
+  T: Clone + Ord + Inc + Multiple,
+{
+  type Item = Gap<T>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    loop {
+      let (start, end) = self.iter.next()?.into_bounds();
+      let align = || self.align.clone();
+      let start = match start {
+        Included(start) => start.round_up(align()).map(Included),
+        Excluded(start) => start.next_multiple(align()).map(Included),
+        Unbounded => Some(Unbounded),
+      };
+      let end = match end {
+        Included(end) => match end.clone().next_multiple(align()) {
+          // If the end is the last value before a multiple of the
+          // alignment, the gap extends up to said multiple.
+          Some(next) if Some(&next) == end.clone().checked_inc().as_ref() => {
+            Some(Excluded(next))
+          },
+          _ => end.round_down(align()).map(Excluded),
+        },
+        Excluded(end) => end.round_down(align()).map(Excluded),
+        Unbounded => Some(Unbounded),
+      };
+
+      match (start, end) {
+        (Some(Included(start)), Some(Excluded(end))) if start >= end => continue,
+        (Some(start), Some(end)) => break Some(Gap::new(start, end)),
+        _ => continue,
+      }
+    }
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    let (_, upper) = self.iter.size_hint();
+    (0, upper)
+  }
+}
+
+impl<I, T> FusedIterator for Aligned<I, T>
+where
+  I: FusedIterator<Item = Gap<T>>,
+  T: Clone + Ord + Inc + Multiple,
+{
+}
+
+
+/// An iterator adapter merging consecutive gaps produced by another
+/// iterator if only few values lie between them.
+///
+/// Objects of this type are created by [`GapIter::coalesce`][crate::GapIter::coalesce].
+#[derive(Clone, Debug)]
+pub struct Coalesce<I, T> {
+  /// The iterator over gaps that we wrap.
+  iter: I,
+  /// Gaps separated by fewer than this many values get merged.
+  k: usize,
+  /// The gap following the one most recently reported, if any.
+  pending: Option<Gap<T>>,
+}
+
+impl<I, T> Coalesce<I, T> {
+  pub(crate) fn new(iter: I, k: usize) -> Self {
+    Self {
+      iter,
+      k,
+      pending: None,
+    }
+  }
+}
+
+impl<I, T> Coalesce<I, T>
+where
+  T: Clone + Ord + Inc,
+{
+  /// Check whether fewer than `k` values lie between the end of one gap
+  /// and the start of the following one.
+  fn mergeable(&self, end: &Bound<T>, next_start: &Bound<T>) -> bool {
+    let mut value = match end {
+      Included(end) => match end.clone().checked_inc() {
+        Some(value) => value,
+        None => return false,
+      },
+      Excluded(end) => end.clone(),
+      Unbounded => return false,
+    };
+
+    let mut count = 0;
+    while start_lt_start(&Included(value.clone()), next_start) {
+      count += 1;
+      if count >= self.k {
+        return false
+      }
+      value = match value.checked_inc() {
+        Some(value) => value,
+        None => break,
+      };
+    }
+    true
+  }
+}
+
+impl<I, T> Iterator for Coalesce<I, T>
+where
+  I: Iterator<Item = Gap<T>>,
+  T: Clone + Ord + Inc,
+{
+  type Item = Gap<T>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let (start, mut end) = match self.pending.take() {
+      Some(gap) => gap,
+      None => self.iter.next()?,
+    }
+    .into_bounds();
+
+    loop {
+      match self.iter.next() {
+        Some(gap) if self.mergeable(&end, gap.start()) => end = gap.into_bounds().1,
+        gap => {
+          self.pending = gap;
+          break Some(Gap::new(start, end))
+        },
+      }
+    }
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    let (lower, upper) = self.iter.size_hint();
+    let pending = self.pending.is_some() as usize;
+    let lower = if lower > 0 || pending > 0 { 1 } else { 0 };
+    (lower, upper.and_then(|upper| upper.checked_add(pending)))
+  }
+}
+
+impl<I, T> FusedIterator for Coalesce<I, T>
+where
+  I: FusedIterator<Item = Gap<T>>,
+  T: Clone + Ord + Inc,
+{
+}
+
+
+/// An iterator adapter yielding the individual values inside the gaps
+/// produced by another iterator.
+///
+/// Objects of this type are created by [`GapIter::values`][crate::GapIter::values].
+#[derive(Clone, Debug)]
+pub struct Values<I, T> {
+  /// The iterator over gaps that we wrap.
+  iter: I,
+  /// The next value to yield along with the end of the gap it is part
+  /// of.
+  next: Option<(T, Bound<T>)>,
+}
+
+impl<I, T> Values<I, T> {
+  pub(crate) fn new(iter: I) -> Self {
+    Self { iter, next: None }
+  }
+}
+
+impl<I, T> Iterator for Values<I, T>
+where
+  I: Iterator<Item = Gap<T>>,
+  T: Clone + Ord + Inc,
+{
+  type Item = T;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    loop {
+      match self.next.take() {
+        Some((value, end)) => {
+          let (within, last) = match &end {
+            Included(end) => (value <= *end, value == *end),
+            Excluded(end) => (value < *end, false),
+            Unbounded => (true, false),
+          };
+          if within {
+            // Only advance if we have not reached the end of the gap
+            // or the maximum value yet.
+            if !last {
+              self.next = value.clone().checked_inc().map(|next| (next, end));
+            }
+            break Some(value)
+          }
+        },
+        None => {
+          let (start, end) = self.iter.next()?.into_bounds();
+          let start = match start {
+            Included(start) => start,
+            Excluded(start) => match start.checked_inc() {
+              Some(start) => start,
+              None => continue,
+            },
+            // We have no way of knowing the first value of a gap with
+            // an unbounded start.
+            Unbounded => continue,
+          };
+          self.next = Some((start, end));
+        },
+      }
+    }
+  }
+}
+
+impl<I, T> FusedIterator for Values<I, T>
+where
+  I: FusedIterator<Item = Gap<T>>,
+  T: Clone + Ord + Inc,
+{
+}
+
+
+/// An iterator adapter restricting the gaps produced by another
+/// iterator to a range.
+///
+/// Objects of this type are created by [`GapIter::clip`][crate::GapIter::clip].
+#[derive(Clone, Debug)]
+pub struct Clip<I, T> {
+  /// The iterator over gaps that we wrap.
+  iter: I,
+  /// The start of the range to restrict gaps to.
+  start: Bound<T>,
+  /// The end of the range to restrict gaps to.
+  end: Bound<T>,
+  /// Whether we have seen a gap starting past `end` or exhausted the
+  /// wrapped iterator.
+  done: bool,
+}
+
+impl<I, T> Clip<I, T> {
+  pub(crate) fn new(iter: I, start: Bound<T>, end: Bound<T>) -> Self {
+    Self {
+      iter,
+      start,
+      end,
+      done: false,
+    }
+  }
+}
+
+impl<I, T> Iterator for Clip<I, T>
+where
+  I: Iterator<Item = Gap<T>>,
+  T: Clone + Ord + Inc,
+{
+  type Item = Gap<T>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    if self.done {
+      return None
+    }
+
+    loop {
+      let (start, end) = match self.iter.next() {
+        Some(gap) => gap.into_bounds(),
+        None => {
+          self.done = true;
+          break None
+        },
+      };
+
+      // Gaps are reported in ascending order, so once one starts past
+      // the end of our range no later one can overlap with it.
+      if !start_le_end(&start, &self.end) {
+        self.done = true;
+        break None
+      }
+
+      let start = if lower_le_lower(&start, &self.start) {
+        self.start.clone()
+      } else {
+        start
+      };
+      let end = if end_lt_end(&end, &self.end) {
+        end
+      } else {
+        self.end.clone()
+      };
+
+      if start_le_end(&start, &end) {
+        break Some(Gap::new(start, end))
+      }
+    }
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    if self.done {
+      return (0, Some(0))
+    }
+
+    let (_, upper) = self.iter.size_hint();
+    (0, upper)
+  }
+}
+
+impl<I, T> FusedIterator for Clip<I, T>
+where
+  I: Iterator<Item = Gap<T>>,
+  T: Clone + Ord + Inc,
+{
+}
@@ -0,0 +1,343 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Low-level gap detection over bitmaps stored as slices of words.
+//!
+//! Bit `i` of a bitmap is bit `i % W` (counting from the least
+//! significant one) of word `i / W`, with `W` being the number of bits
+//! per word. Set bits denote present values, while runs of cleared bits
+//! constitute gaps. Scanning happens a word at a time, making it
+//! suitable as the basis of bitmap-backed allocators.
+//!
+//! Other bitmap representations can be scanned by implementing the
+//! [`Bitmap`] trait. With the `fixedbitset` and `bitvec` features
+//! enabled, it is implemented for `FixedBitSet` and `BitSlice`,
+//! respectively.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+use std::iter::FusedIterator;
+use std::ops::Bound::Excluded;
+use std::ops::Bound::Included;
+use std::ops::Bound::Unbounded;
+use std::ops::RangeBounds;
+
+#[cfg(feature = "bitvec")]
+use bitvec::order::BitOrder;
+#[cfg(feature = "bitvec")]
+use bitvec::slice::BitSlice;
+#[cfg(feature = "bitvec")]
+use bitvec::store::BitStore;
+#[cfg(feature = "fixedbitset")]
+use fixedbitset::FixedBitSet;
+
+use crate::Gap;
+
+
+/// A bitmap that can be scanned for runs of set or cleared bits.
+pub trait Bitmap {
+  /// Retrieve the number of bits in the bitmap.
+  fn bit_len(&self) -> usize;
+
+  /// Find the index of the first bit in `start..end` whose value is
+  /// `set`, or `end` if there is none.
+  ///
+  /// `start` is less than `end`, which in turn does not exceed the
+  /// bitmap's length.
+  fn find_bit(&self, start: usize, end: usize, set: bool) -> usize;
+}
+
+macro_rules! bitmap {
+  ( $t:ty ) => {
+    impl Bitmap for [$t] {
+      fn bit_len(&self) -> usize {
+        self.len() * <$t>::BITS as usize
+      }
+
+      fn find_bit(&self, start: usize, end: usize, set: bool) -> usize {
+        const BITS: usize = <$t>::BITS as usize;
+
+        let mut pos = start;
+        while pos < end {
+          let word = self[pos / BITS];
+          let word = if set { word } else { !word };
+          let offset = pos % BITS;
+          let word = word >> offset;
+          if word != 0 {
+            let pos = pos + word.trailing_zeros() as usize;
+            return pos.min(end)
+          }
+          pos += BITS - offset;
+        }
+        end
+      }
+    }
+  };
+}
+
+bitmap!(u8);
+bitmap!(u16);
+bitmap!(u32);
+bitmap!(u64);
+bitmap!(usize);
+
+/// Bits are scanned a block at a time. Bits beyond the set's length are
+/// not considered.
+#[cfg(feature = "fixedbitset")]
+impl Bitmap for FixedBitSet {
+  fn bit_len(&self) -> usize {
+    self.len()
+  }
+
+  fn find_bit(&self, start: usize, end: usize, set: bool) -> usize {
+    self.as_slice().find_bit(start, end, set)
+  }
+}
+
+/// Bits are scanned using `bitvec`'s search functions, which operate on
+/// whole elements of the underlying storage. Bit `i` is the `i`-th bit
+/// of the slice, regardless of its `BitOrder`.
+#[cfg(feature = "bitvec")]
+impl<T, O> Bitmap for BitSlice<T, O>
+where
+  T: BitStore,
+  O: BitOrder,
+{
+  fn bit_len(&self) -> usize {
+    self.len()
+  }
+
+  fn find_bit(&self, start: usize, end: usize, set: bool) -> usize {
+    let bits = &self[start..end];
+    let pos = if set {
+      bits.first_one()
+    } else {
+      bits.first_zero()
+    };
+    pos.map(|pos| start + pos).unwrap_or(end)
+  }
+}
+
+
+/// An iterator over the runs of cleared bits in a bitmap.
+///
+/// Objects of this type are created by [`gaps`] and [`zero_runs`].
+pub struct ZeroRuns<'w, B = [u64]>
+where
+  B: ?Sized,
+{
+  /// The bitmap.
+  bitmap: &'w B,
+  /// The index of the next bit to inspect.
+  pos: usize,
+  /// The index one past the last bit to inspect.
+  end: usize,
+}
+
+impl<B> Clone for ZeroRuns<'_, B>
+where
+  B: ?Sized,
+{
+  fn clone(&self) -> Self {
+    Self {
+      bitmap: self.bitmap,
+      pos: self.pos,
+      end: self.end,
+    }
+  }
+}
+
+impl<B> Debug for ZeroRuns<'_, B>
+where
+  B: Debug + ?Sized,
+{
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    f.debug_struct("ZeroRuns")
+      .field("bitmap", &self.bitmap)
+      .field("pos", &self.pos)
+      .field("end", &self.end)
+      .finish()
+  }
+}
+
+impl<B> Iterator for ZeroRuns<'_, B>
+where
+  B: Bitmap + ?Sized,
+{
+  type Item = Gap<usize>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    if self.pos >= self.end {
+      return None
+    }
+
+    self.pos = self.bitmap.find_bit(self.pos, self.end, false);
+    if self.pos >= self.end {
+      return None
+    }
+
+    let start = self.pos;
+    self.pos = self.bitmap.find_bit(self.pos, self.end, true);
+    Some(Gap::from(start..self.pos))
+  }
+}
+
+impl<B> FusedIterator for ZeroRuns<'_, B> where B: Bitmap + ?Sized {}
+
+
+/// Create an iterator over the runs of cleared bits in the given range
+/// of bit indices of a bitmap.
+///
+/// The range is clamped to the bits covered by `words`. Gaps are
+/// reported as index ranges with an included start and an excluded
+/// end.
+///
+/// ```rust
+/// use std::ops::Bound;
+/// # use gaps::Gap;
+///
+/// let words = [0b1111_0011, u64::MAX << 4];
+/// let mut gaps = gaps::bits::gaps(&words, 1..68);
+/// assert_eq!(gaps.next(), Some(Gap::new(Bound::Included(2), Bound::Excluded(4))));
+/// assert_eq!(gaps.next(), Some(Gap::new(Bound::Included(8), Bound::Excluded(68))));
+/// assert_eq!(gaps.next(), None);
+/// ```
+pub fn gaps<R>(words: &[u64], range: R) -> ZeroRuns<'_>
+where
+  R: RangeBounds<usize>,
+{
+  zero_runs(words, range)
+}
+
+
+/// Create an iterator over the runs of cleared bits in the given range
+/// of bit indices of an arbitrary [`Bitmap`].
+///
+/// The range is clamped to the bits covered by `bitmap`. Gaps are
+/// reported just like by [`gaps`].
+///
+/// ```rust
+/// use std::ops::Bound;
+/// # use gaps::Gap;
+///
+/// let words = [0b0000_1111u8, 0b1111_0000];
+/// let mut gaps = gaps::bits::zero_runs(&words[..], ..);
+/// assert_eq!(gaps.next(), Some(Gap::new(Bound::Included(4), Bound::Excluded(12))));
+/// assert_eq!(gaps.next(), None);
+/// ```
+pub fn zero_runs<B, R>(bitmap: &B, range: R) -> ZeroRuns<'_, B>
+where
+  B: Bitmap + ?Sized,
+  R: RangeBounds<usize>,
+{
+  let len = bitmap.bit_len();
+  let start = match range.start_bound() {
+    Included(start) => *start,
+    Excluded(start) => start.saturating_add(1),
+    Unbounded => 0,
+  };
+  let end = match range.end_bound() {
+    Included(end) => end.saturating_add(1),
+    Excluded(end) => *end,
+    Unbounded => len,
+  };
+
+  ZeroRuns {
+    bitmap,
+    pos: start,
+    end: end.min(len),
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+
+  /// Check that runs spanning multiple words are reported as one.
+  #[test]
+  fn runs_across_words() {
+    let words = [u64::MAX >> 1, 0, 0b10];
+    let gaps = gaps(&words, ..).collect::<Vec<_>>();
+    let expected = vec![Gap::from(63..129), Gap::from(130..192)];
+    assert_eq!(gaps, expected);
+  }
+
+  /// Check that the range of interest is honored and clamped.
+  #[test]
+  fn range_clamping() {
+    let words = [0b1000_0001];
+    assert_eq!(
+      gaps(&words, 3..5).collect::<Vec<_>>(),
+      vec![Gap::from(3..5)]
+    );
+    assert_eq!(gaps(&words, 7..=7).next(), None);
+    assert_eq!(
+      gaps(&words, 60..100).collect::<Vec<_>>(),
+      vec![Gap::from(60..64)]
+    );
+    assert_eq!(gaps(&words, 70..100).next(), None);
+    assert_eq!(gaps(&[], ..).next(), None);
+  }
+
+  /// Check that words of different widths are scanned consistently.
+  #[test]
+  fn word_widths() {
+    let words = [0xff00_0000_0000_00f0u64, 0];
+    let expected = vec![Gap::from(0..4), Gap::from(8..56), Gap::from(64..128)];
+    assert_eq!(gaps(&words, ..).collect::<Vec<_>>(), expected);
+
+    let bytes = words
+      .iter()
+      .flat_map(|word| word.to_le_bytes())
+      .collect::<Vec<_>>();
+    assert_eq!(zero_runs(&bytes[..], ..).collect::<Vec<_>>(), expected);
+
+    let halves = words
+      .iter()
+      .flat_map(|word| [*word as u32, (*word >> 32) as u32])
+      .collect::<Vec<_>>();
+    assert_eq!(zero_runs(&halves[..], ..).collect::<Vec<_>>(), expected);
+  }
+
+  /// Check that runs are found in a `FixedBitSet`, without considering
+  /// bits beyond its length.
+  #[cfg(feature = "fixedbitset")]
+  #[test]
+  fn fixed_bit_set_runs() {
+    let mut set = FixedBitSet::with_capacity(200);
+    set.insert_range(2..70);
+    set.insert(150);
+
+    let gaps = zero_runs(&set, ..).collect::<Vec<_>>();
+    let expected = vec![Gap::from(0..2), Gap::from(70..150), Gap::from(151..200)];
+    assert_eq!(gaps, expected);
+    assert_eq!(
+      zero_runs(&set, 100..=150).collect::<Vec<_>>(),
+      vec![Gap::from(100..150)]
+    );
+  }
+
+  /// Check that runs are found in `BitSlice` objects of different
+  /// orders and at arbitrary offsets.
+  #[cfg(feature = "bitvec")]
+  #[test]
+  fn bit_slice_runs() {
+    use bitvec::bits;
+    use bitvec::order::Lsb0;
+    use bitvec::order::Msb0;
+
+    let bits = bits![u16, Lsb0; 1, 1, 0, 0, 0, 1, 0, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1];
+    let expected = vec![Gap::from(2..5), Gap::from(6..7), Gap::from(9..19)];
+    assert_eq!(zero_runs(bits, ..).collect::<Vec<_>>(), expected);
+
+    let bits = bits![u8, Msb0; 1, 1, 0, 0, 0, 1, 0, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1];
+    assert_eq!(zero_runs(bits, ..).collect::<Vec<_>>(), expected);
+    assert_eq!(
+      zero_runs(&bits[3..], ..6).collect::<Vec<_>>(),
+      vec![Gap::from(0..2), Gap::from(3..4)]
+    );
+  }
+}
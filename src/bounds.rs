@@ -1,52 +1,82 @@
 // Copyright (C) 2020 Daniel Mueller <deso@posteo.net>
 // SPDX-License-Identifier: GPL-3.0-or-later
 
+use std::cmp::Ordering;
 use std::ops::Bound;
 use std::ops::Bound::Excluded;
 use std::ops::Bound::Included;
 use std::ops::Bound::Unbounded;
 use std::ops::RangeBounds;
 
+use crate::Distance;
+use crate::Domain;
 use crate::Inc;
 
 
 /// Check whether a "start" bound is less than another one.
+///
+/// An unbounded start is treated as lying after all values, as it
+/// results from flipping an unbounded "end" bound. That is the opposite
+/// of [`range::cmp::start_lt_start`][crate::range::cmp::start_lt_start],
+/// which places it before all values.
 pub(crate) fn start_lt_start<T>(b1: &Bound<T>, b2: &Bound<T>) -> bool
 where
-  T: Copy + Ord + Inc,
+  T: Clone + Ord + Inc,
 {
   match (b1, b2) {
     (Unbounded, _) => false,
     (_, Unbounded) => true,
     (Included(b1), Included(b2)) => *b1 < *b2,
     (Included(b1), Excluded(b2)) => *b1 <= *b2,
-    (Excluded(b1), Included(b2)) => b1.inc() < *b2,
+    // Comparing before incrementing ensures that we never increment
+    // past the maximum value.
+    (Excluded(b1), Included(b2)) => *b1 < *b2 && b1.clone().inc() < *b2,
     (Excluded(b1), Excluded(b2)) => *b1 < *b2,
   }
 }
 
 /// Check whether a "start" bound is less than or equal to another one.
+///
+/// An unbounded start is treated as lying after all values, as it
+/// results from flipping an unbounded "end" bound. That is the opposite
+/// of [`range::cmp::start_le_start`][crate::range::cmp::start_le_start],
+/// which places it before all values.
 pub(crate) fn start_le_start<T>(b1: &Bound<T>, b2: &Bound<T>) -> bool
 where
-  T: Copy + Ord + Inc,
+  T: Clone + Ord + Inc,
 {
   match (b1, b2) {
     (_, Unbounded) => true,
     (Unbounded, _) => false,
     (Included(b1), Included(b2)) => *b1 <= *b2,
-    (Included(b1), Excluded(b2)) => *b1 <= b2.inc(),
-    (Excluded(b1), Included(b2)) => b1.inc() <= *b2,
+    (Included(b1), Excluded(b2)) => *b1 <= *b2 || *b1 == b2.clone().inc(),
+    (Excluded(b1), Included(b2)) => *b1 < *b2,
     (Excluded(b1), Excluded(b2)) => *b1 <= *b2,
   }
 }
 
+/// Check whether a "start" bound is less than or equal to another one,
+/// treating an unbounded start as lying before all values.
+pub(crate) fn lower_le_lower<T>(b1: &Bound<T>, b2: &Bound<T>) -> bool
+where
+  T: Clone + Ord + Inc,
+{
+  match (b1, b2) {
+    (Unbounded, _) => true,
+    (_, Unbounded) => false,
+    _ => start_le_start(b1, b2),
+  }
+}
+
 /// Check whether a "start" bound is less than or equal to an "end"
-/// bound.
-pub(crate) fn start_le_end<T>(b1: &Bound<T>, b2: &Bound<T>) -> bool
+/// bound, i.e., whether the range formed by the two is not empty.
+pub fn start_le_end<T>(b1: &Bound<T>, b2: &Bound<T>) -> bool
 where
-  T: Copy + Ord + Inc,
+  T: Clone + Ord + Inc,
 {
   match (b1, b2) {
+    // A range starting after the maximum value is empty.
+    (Excluded(b1), Unbounded) => b1.clone().checked_inc().is_some(),
     (_, Unbounded) => true,
     (Unbounded, _) => true,
     (Included(b1), Included(b2)) => *b1 <= *b2,
@@ -57,51 +87,173 @@ where
       // comparison operations. `(1..2)`, for example, should not result
       // in `true` being reported. We need to increment the start value
       // by one to get the proper check.
-      b1.inc() < *b2
+      *b1 < *b2 && b1.clone().inc() < *b2
     },
   }
 }
 
 /// Check whether an "end" bound is less than another one.
-pub(crate) fn end_lt_end<T>(b1: &Bound<T>, b2: &Bound<T>) -> bool
+///
+/// An end including the maximum value of `T` is considered equal to
+/// an unbounded end.
+pub fn end_lt_end<T>(b1: &Bound<T>, b2: &Bound<T>) -> bool
 where
-  T: Copy + Ord + Inc,
+  T: Clone + Ord + Inc,
 {
   match (b1, b2) {
     (Unbounded, _) => false,
+    // An end including the maximum value is equivalent to an unbounded
+    // one.
+    (Included(b1), Unbounded) => b1.clone().checked_inc().is_some(),
     (_, Unbounded) => true,
     (Included(b1), Included(b2)) => *b1 < *b2,
-    (Included(b1), Excluded(b2)) => b1.inc() < *b2,
+    (Included(b1), Excluded(b2)) => *b1 < *b2 && b1.clone().inc() < *b2,
     (Excluded(b1), Included(b2)) => *b1 <= *b2,
     (Excluded(b1), Excluded(b2)) => *b1 < *b2,
   }
 }
 
+/// Check whether two ranges have at least one value in common.
+pub(crate) fn overlap<T>(r1: (&Bound<T>, &Bound<T>), r2: (&Bound<T>, &Bound<T>)) -> bool
+where
+  T: Clone + Ord + Inc,
+{
+  let start = if lower_le_lower(r1.0, r2.0) {
+    r2.0
+  } else {
+    r1.0
+  };
+  let end = if end_lt_end(r1.1, r2.1) { r1.1 } else { r2.1 };
+  start_le_end(start, end)
+}
+
+/// Check whether a range is fully contained in another one.
+pub(crate) fn contains<T>(outer: (&Bound<T>, &Bound<T>), inner: (&Bound<T>, &Bound<T>)) -> bool
+where
+  T: Clone + Ord + Inc,
+{
+  lower_le_lower(outer.0, inner.0) && !end_lt_end(outer.1, inner.1)
+}
+
+/// Order two "start" bounds structurally, i.e., without taking into
+/// account that distinct bounds may denote the same first value.
+pub(crate) fn cmp_start<T>(b1: &Bound<T>, b2: &Bound<T>) -> Ordering
+where
+  T: Ord,
+{
+  match (b1, b2) {
+    (Unbounded, Unbounded) => Ordering::Equal,
+    (Unbounded, _) => Ordering::Less,
+    (_, Unbounded) => Ordering::Greater,
+    (Included(b1), Included(b2)) | (Excluded(b1), Excluded(b2)) => b1.cmp(b2),
+    (Included(b1), Excluded(b2)) => b1.cmp(b2).then(Ordering::Less),
+    (Excluded(b1), Included(b2)) => b1.cmp(b2).then(Ordering::Greater),
+  }
+}
 
-/// Extract the bounds from a range, copying the inner values.
+/// Order two "end" bounds structurally, i.e., without taking into
+/// account that distinct bounds may denote the same last value.
+pub(crate) fn cmp_end<T>(b1: &Bound<T>, b2: &Bound<T>) -> Ordering
+where
+  T: Ord,
+{
+  match (b1, b2) {
+    (Unbounded, Unbounded) => Ordering::Equal,
+    (Unbounded, _) => Ordering::Greater,
+    (_, Unbounded) => Ordering::Less,
+    (Included(b1), Included(b2)) | (Excluded(b1), Excluded(b2)) => b1.cmp(b2),
+    (Included(b1), Excluded(b2)) => b1.cmp(b2).then(Ordering::Greater),
+    (Excluded(b1), Included(b2)) => b1.cmp(b2).then(Ordering::Less),
+  }
+}
+
+/// Convert an "end" bound into the "start" bound of the range directly
+/// following it, or vice versa.
+pub(crate) fn flip<T>(bound: Bound<T>) -> Bound<T> {
+  match bound {
+    Included(x) => Excluded(x),
+    Excluded(x) => Included(x),
+    Unbounded => Unbounded,
+  }
+}
+
+
+/// Determine the number of values in a range.
+///
+/// `None` is returned if the range is unbounded or the number of values
+/// can not be represented as `usize`.
+pub(crate) fn len<T>(start: &Bound<T>, end: &Bound<T>) -> Option<usize>
+where
+  T: Clone + Ord + Distance,
+{
+  let first = match start {
+    Included(start) => start.clone(),
+    Excluded(start) => match start.clone().checked_inc() {
+      Some(first) => first,
+      None => return Some(0),
+    },
+    Unbounded => return None,
+  };
+
+  match end {
+    Included(end) if *end < first => Some(0),
+    Included(end) => first.distance(end.clone())?.checked_add(1),
+    Excluded(end) if *end < first => Some(0),
+    Excluded(end) => first.distance(end.clone()),
+    Unbounded => None,
+  }
+}
+
+
+/// Extract the bounds from a range, cloning the inner values.
 // TODO: This function should use `Bound::cloned` once it is stable.
 pub fn bounds<R, T>(range: &R) -> (Bound<T>, Bound<T>)
 where
-  R: RangeBounds<T>,
-  T: Copy,
+  R: RangeBounds<T> + ?Sized,
+  T: Clone,
 {
   let start = range.start_bound();
   let end = range.end_bound();
 
   match (start, end) {
-    (Included(start), Included(end)) => (Included(*start), Included(*end)),
-    (Included(start), Excluded(end)) => (Included(*start), Excluded(*end)),
-    (Included(start), Unbounded) => (Included(*start), Unbounded),
-    (Excluded(start), Included(end)) => (Excluded(*start), Included(*end)),
-    (Excluded(start), Excluded(end)) => (Excluded(*start), Excluded(*end)),
-    (Excluded(start), Unbounded) => (Excluded(*start), Unbounded),
-    (Unbounded, Included(end)) => (Unbounded, Included(*end)),
-    (Unbounded, Excluded(end)) => (Unbounded, Excluded(*end)),
+    (Included(start), Included(end)) => (Included(start.clone()), Included(end.clone())),
+    (Included(start), Excluded(end)) => (Included(start.clone()), Excluded(end.clone())),
+    (Included(start), Unbounded) => (Included(start.clone()), Unbounded),
+    (Excluded(start), Included(end)) => (Excluded(start.clone()), Included(end.clone())),
+    (Excluded(start), Excluded(end)) => (Excluded(start.clone()), Excluded(end.clone())),
+    (Excluded(start), Unbounded) => (Excluded(start.clone()), Unbounded),
+    (Unbounded, Included(end)) => (Unbounded, Included(end.clone())),
+    (Unbounded, Excluded(end)) => (Unbounded, Excluded(end.clone())),
     (Unbounded, Unbounded) => (Unbounded, Unbounded),
   }
 }
 
 
+/// Resolve `Unbounded` bounds of a range into the respective extreme
+/// value of the type's [`Domain`].
+///
+/// ```rust
+/// use std::ops::Bound;
+/// # use gaps::range::resolve;
+///
+/// let gap = resolve((Bound::Excluded(7u8), Bound::Unbounded));
+/// assert_eq!(gap, (Bound::Excluded(7), Bound::Included(u8::MAX)));
+/// ```
+pub fn resolve<T>((start, end): (Bound<T>, Bound<T>)) -> (Bound<T>, Bound<T>)
+where
+  T: Domain,
+{
+  let start = match start {
+    Unbounded => Included(T::MIN),
+    start => start,
+  };
+  let end = match end {
+    Unbounded => Included(T::MAX),
+    end => end,
+  };
+  (start, end)
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -182,12 +334,27 @@ mod tests {
     assert!(!start_le_end(&Excluded(3), &Excluded(4)));
 
     assert!(start_le_end(&Excluded(3), &Unbounded));
+    assert!(!start_le_end(&Excluded(u8::MAX), &Unbounded));
 
     assert!(start_le_end(&Unbounded, &Included(5)));
     assert!(start_le_end(&Unbounded, &Excluded(5)));
     assert!(start_le_end::<u8>(&Unbounded, &Unbounded));
   }
 
+  #[test]
+  fn range_length() {
+    assert_eq!(len(&Included(1), &Included(1)), Some(1));
+    assert_eq!(len(&Included(1), &Excluded(1)), Some(0));
+    assert_eq!(len(&Excluded(1), &Included(1)), Some(0));
+    assert_eq!(len(&Excluded(1), &Excluded(2)), Some(0));
+    assert_eq!(len(&Excluded(1), &Excluded(5)), Some(3));
+    assert_eq!(len(&Included(1), &Included(5)), Some(5));
+    assert_eq!(len(&Included(5), &Included(1)), Some(0));
+    assert_eq!(len(&Included(i8::MIN), &Included(i8::MAX)), Some(256));
+    assert_eq!(len(&Included(0), &Unbounded), None);
+    assert_eq!(len(&Unbounded, &Included(0)), None);
+  }
+
   #[test]
   fn end_less_than_end() {
     assert!(end_lt_end(&Included(0), &Included(2)));
@@ -199,6 +366,7 @@ mod tests {
     assert!(!end_lt_end(&Included(2), &Excluded(3)));
 
     assert!(end_lt_end(&Included(4), &Unbounded));
+    assert!(!end_lt_end(&Included(u8::MAX), &Unbounded));
 
     assert!(end_lt_end(&Excluded(1), &Included(2)));
     assert!(end_lt_end(&Excluded(2), &Included(2)));
@@ -214,4 +382,28 @@ mod tests {
     assert!(!end_lt_end(&Unbounded, &Excluded(2)));
     assert!(!end_lt_end::<u8>(&Unbounded, &Unbounded));
   }
+
+  /// Check that "start" bounds are ordered correctly.
+  #[test]
+  fn start_ordering() {
+    assert_eq!(cmp_start::<u8>(&Unbounded, &Unbounded), Ordering::Equal);
+    assert_eq!(cmp_start(&Unbounded, &Included(0)), Ordering::Less);
+    assert_eq!(cmp_start(&Included(1), &Included(2)), Ordering::Less);
+    assert_eq!(cmp_start(&Included(2), &Excluded(2)), Ordering::Less);
+    assert_eq!(cmp_start(&Excluded(1), &Included(2)), Ordering::Less);
+    assert_eq!(cmp_start(&Excluded(2), &Included(2)), Ordering::Greater);
+    assert_eq!(cmp_start(&Excluded(2), &Excluded(2)), Ordering::Equal);
+  }
+
+  /// Check that "end" bounds are ordered correctly.
+  #[test]
+  fn end_ordering() {
+    assert_eq!(cmp_end::<u8>(&Unbounded, &Unbounded), Ordering::Equal);
+    assert_eq!(cmp_end(&Unbounded, &Included(0)), Ordering::Greater);
+    assert_eq!(cmp_end(&Included(1), &Included(2)), Ordering::Less);
+    assert_eq!(cmp_end(&Included(2), &Excluded(2)), Ordering::Greater);
+    assert_eq!(cmp_end(&Excluded(2), &Included(1)), Ordering::Greater);
+    assert_eq!(cmp_end(&Excluded(2), &Included(2)), Ordering::Less);
+    assert_eq!(cmp_end(&Excluded(2), &Excluded(2)), Ordering::Equal);
+  }
 }
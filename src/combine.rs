@@ -0,0 +1,268 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::iter::once;
+use std::iter::Fuse;
+use std::iter::FusedIterator;
+use std::iter::Once;
+use std::ops::Bound;
+use std::ops::Bound::Unbounded;
+use std::ops::RangeBounds;
+
+use crate::bounds::bounds;
+use crate::bounds::end_lt_end;
+use crate::bounds::flip;
+use crate::bounds::lower_le_lower;
+use crate::bounds::start_le_end;
+use crate::Gap;
+use crate::Inc;
+
+
+/// The engine behind the various set operations on gap iterators.
+///
+/// Both wrapped iterators are swept in lock step, splitting the value
+/// domain into segments in which membership in either of them does not
+/// change. Consecutive segments for which `op` reports `true` are
+/// combined and reported.
+#[derive(Clone, Debug)]
+struct Merge<A, B, T> {
+  /// The first iterator over gaps.
+  a: Fuse<A>,
+  /// The second iterator over gaps.
+  b: Fuse<B>,
+  /// The current gap of the first iterator.
+  next_a: Option<(Bound<T>, Bound<T>)>,
+  /// The current gap of the second iterator.
+  next_b: Option<(Bound<T>, Bound<T>)>,
+  /// The start of the part of the domain not yet swept, if any.
+  pos: Option<Bound<T>>,
+  /// The operation deciding whether a segment should be reported, based
+  /// on whether it is part of a gap of the first and the second
+  /// iterator, respectively.
+  op: fn(bool, bool) -> bool,
+}
+
+impl<A, B, T> Merge<A, B, T>
+where
+  A: Iterator<Item = Gap<T>>,
+  B: Iterator<Item = Gap<T>>,
+{
+  fn new(a: A, b: B, op: fn(bool, bool) -> bool) -> Self {
+    Self {
+      a: a.fuse(),
+      b: b.fuse(),
+      next_a: None,
+      next_b: None,
+      pos: Some(Unbounded),
+      op,
+    }
+  }
+}
+
+impl<A, B, T> Merge<A, B, T>
+where
+  A: Iterator<Item = Gap<T>>,
+  B: Iterator<Item = Gap<T>>,
+  T: Clone + Ord + Inc,
+{
+  /// Retrieve the first gap of an iterator that does not end before
+  /// `pos`.
+  fn current<I>(
+    iter: &mut I,
+    next: &mut Option<(Bound<T>, Bound<T>)>,
+    pos: &Bound<T>,
+  ) -> Option<(Bound<T>, Bound<T>)>
+  where
+    I: Iterator<Item = Gap<T>>,
+  {
+    loop {
+      match next {
+        Some((_, end)) if start_le_end(pos, end) => break next.clone(),
+        _ => {
+          *next = iter.next().map(Gap::into_bounds);
+          if next.is_none() {
+            break None
+          }
+        },
+      }
+    }
+  }
+
+  /// Sweep the next segment, returning it along with whether it should
+  /// be reported.
+  #[allow(clippy::type_complexity)]
+  fn next_segment(&mut self) -> Option<((Bound<T>, Bound<T>), bool)> {
+    let pos = self.pos.clone()?;
+    let a = Self::current(&mut self.a, &mut self.next_a, &pos);
+    let b = Self::current(&mut self.b, &mut self.next_b, &pos);
+
+    if a.is_none() && b.is_none() {
+      self.pos = None;
+      return None
+    }
+
+    let mut end = Unbounded;
+    let mut member = [false; 2];
+    for (i, gap) in [a, b].iter().enumerate() {
+      if let Some((gap_start, gap_end)) = gap {
+        let inside = lower_le_lower(gap_start, &pos);
+        // The segment ends where the gap ends or, if we are not inside
+        // it yet, right before it starts.
+        let gap_end = if inside {
+          gap_end.clone()
+        } else {
+          flip(gap_start.clone())
+        };
+        if end_lt_end(&gap_end, &end) {
+          end = gap_end;
+        }
+        member[i] = inside;
+      }
+    }
+
+    self.pos = match &end {
+      Unbounded => None,
+      end => Some(flip(end.clone())),
+    };
+    Some(((pos, end), (self.op)(member[0], member[1])))
+  }
+}
+
+impl<A, B, T> Iterator for Merge<A, B, T>
+where
+  A: Iterator<Item = Gap<T>>,
+  B: Iterator<Item = Gap<T>>,
+  T: Clone + Ord + Inc,
+{
+  type Item = Gap<T>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let (start, mut end) = loop {
+      match self.next_segment()? {
+        (segment, true) => break segment,
+        (_, false) => continue,
+      }
+    };
+
+    // Segments are contiguous, so any directly following one that is to
+    // be reported as well just extends the range.
+    while let Some(((_, next_end), report)) = self.next_segment() {
+      if !report {
+        break
+      }
+      end = next_end;
+    }
+    Some(Gap::new(start, end))
+  }
+}
+
+impl<A, B, T> FusedIterator for Merge<A, B, T>
+where
+  A: Iterator<Item = Gap<T>>,
+  B: Iterator<Item = Gap<T>>,
+  T: Clone + Ord + Inc,
+{
+}
+
+
+macro_rules! combine {
+  ( $(#[$docs:meta])* $name:ident, $op:expr ) => {
+    $(#[$docs])*
+    #[derive(Clone, Debug)]
+    pub struct $name<A, B, T>(Merge<A, B, T>);
+
+    impl<A, B, T> $name<A, B, T>
+    where
+      A: Iterator<Item = Gap<T>>,
+      B: Iterator<Item = Gap<T>>,
+    {
+      pub(crate) fn new(a: A, b: B) -> Self {
+        Self(Merge::new(a, b, $op))
+      }
+    }
+
+    impl<A, B, T> Iterator for $name<A, B, T>
+    where
+      A: Iterator<Item = Gap<T>>,
+      B: Iterator<Item = Gap<T>>,
+      T: Clone + Ord + Inc,
+    {
+      type Item = Gap<T>;
+
+      fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+      }
+    }
+
+    impl<A, B, T> FusedIterator for $name<A, B, T>
+    where
+      A: Iterator<Item = Gap<T>>,
+      B: Iterator<Item = Gap<T>>,
+      T: Clone + Ord + Inc,
+    {
+    }
+  };
+}
+
+combine!(
+  /// An iterator adapter yielding the ranges that are gaps in both of
+  /// two iterators over gaps.
+  ///
+  /// Objects of this type are created by [`GapIter::intersect`][crate::GapIter::intersect].
+  Intersection,
+  |a, b| a && b
+);
+combine!(
+  /// An iterator adapter yielding the ranges that are gaps in either of
+  /// two iterators over gaps.
+  ///
+  /// Objects of this type are created by [`GapIter::union`][crate::GapIter::union].
+  Union,
+  |a, b| a || b
+);
+combine!(
+  /// An iterator adapter yielding the ranges that are gaps in one
+  /// iterator over gaps but not in another one.
+  ///
+  /// Objects of this type are created by [`GapIter::difference`][crate::GapIter::difference].
+  Difference,
+  |a, b| a && !b
+);
+combine!(
+  /// An iterator adapter yielding the ranges that are gaps in exactly
+  /// one of two iterators over gaps.
+  ///
+  /// Objects of this type are created by [`GapIter::symmetric_difference`][crate::GapIter::symmetric_difference].
+  SymmetricDifference,
+  |a, b| a != b
+);
+
+
+/// Invert gaps within a domain, yielding the ranges covered by values.
+///
+/// `gaps` may be any iterator over gaps in ascending order, such as a
+/// [`GapIter`][crate::GapIter].
+///
+/// ```rust
+/// use std::ops::Bound;
+/// # use gaps::complement;
+/// # use gaps::Gap;
+///
+/// let gaps = vec![
+///   Gap::new(Bound::Included(2), Bound::Excluded(4)),
+///   Gap::new(Bound::Excluded(6), Bound::Unbounded),
+/// ];
+/// let mut present = complement(gaps, 0..=10);
+/// assert_eq!(present.next(), Some(Gap::new(Bound::Included(0), Bound::Excluded(2))));
+/// assert_eq!(present.next(), Some(Gap::new(Bound::Included(4), Bound::Included(6))));
+/// assert_eq!(present.next(), None);
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn complement<I, R, T>(gaps: I, domain: R) -> Difference<Once<Gap<T>>, I::IntoIter, T>
+where
+  I: IntoIterator<Item = Gap<T>>,
+  R: RangeBounds<T>,
+  T: Clone,
+{
+  Difference::new(once(Gap::from(bounds(&domain))), gaps.into_iter())
+}
@@ -0,0 +1,52 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+
+/// A trait for types with a finite domain, i.e., types that have a
+/// smallest and a largest value.
+///
+/// Knowledge of the domain allows for resolving `Unbounded` bounds
+/// into concrete values, for example by means of
+/// [`range::resolve`][crate::range::resolve].
+pub trait Domain: Sized {
+  /// The smallest value of the type.
+  const MIN: Self;
+  /// The largest value of the type.
+  const MAX: Self;
+}
+
+macro_rules! domain {
+  ( $t:ty ) => {
+    impl Domain for $t {
+      const MIN: Self = <$t>::MIN;
+      const MAX: Self = <$t>::MAX;
+    }
+  };
+}
+
+domain!(u8);
+domain!(i8);
+domain!(u16);
+domain!(i16);
+domain!(u32);
+domain!(i32);
+domain!(u64);
+domain!(i64);
+domain!(u128);
+domain!(i128);
+domain!(usize);
+domain!(isize);
+
+impl<const N: usize> Domain for [u8; N] {
+  const MIN: Self = [u8::MIN; N];
+  const MAX: Self = [u8::MAX; N];
+}
+
+impl<A, B> Domain for (A, B)
+where
+  A: Domain,
+  B: Domain,
+{
+  const MIN: Self = (A::MIN, B::MIN);
+  const MAX: Self = (A::MAX, B::MAX);
+}
@@ -0,0 +1,97 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::error::Error;
+use std::fmt::Debug;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+
+
+/// An error as reported by fallible gap iteration and conversion.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum GapError<T> {
+  /// The sequence of values is not ascending.
+  Unsorted {
+    /// The previously encountered value.
+    prev: T,
+    /// The value following `prev`, but being less than it.
+    next: T,
+  },
+  /// A gap could not be converted into a range, because it is
+  /// unbounded.
+  Unbounded,
+  /// A gap could not be converted into a range, because its bounds
+  /// are not representable by the range type.
+  Unrepresentable,
+}
+
+impl<T> Display for GapError<T>
+where
+  T: Debug,
+{
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    match self {
+      Self::Unsorted { prev, next } => write!(
+        f,
+        "sequence is not ascending: {:?} is followed by {:?}",
+        prev, next
+      ),
+      Self::Unbounded => f.write_str("gap is unbounded"),
+      Self::Unrepresentable => f.write_str("gap is not representable as range"),
+    }
+  }
+}
+
+impl<T> Error for GapError<T> where T: Debug {}
+
+
+/// An error as reported when parsing a range list.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseError<E> {
+  /// An item of the list is empty.
+  EmptyItem,
+  /// A value could not be parsed.
+  Value(E),
+}
+
+impl<E> Display for ParseError<E>
+where
+  E: Display,
+{
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    match self {
+      Self::EmptyItem => f.write_str("range list contains an empty item"),
+      Self::Value(err) => write!(f, "failed to parse value: {}", err),
+    }
+  }
+}
+
+impl<E> Error for ParseError<E> where E: Debug + Display {}
+
+
+/// An error as reported when decoding a binary representation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DecodeError {
+  /// The input ended prematurely.
+  Truncated,
+  /// A decoded value is out of range for the target type.
+  Overflow,
+  /// The input contains data following the encoded object.
+  TrailingData,
+}
+
+impl Display for DecodeError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    match self {
+      Self::Truncated => f.write_str("input is truncated"),
+      Self::Overflow => f.write_str("decoded value is out of range"),
+      Self::TrailingData => f.write_str("input contains trailing data"),
+    }
+  }
+}
+
+impl Error for DecodeError {}
@@ -0,0 +1,191 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::cell::Cell;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+use std::iter::FusedIterator;
+use std::ops::RangeBounds;
+use std::rc::Rc;
+
+use crate::Gap;
+use crate::GapIter;
+use crate::Gappable as _;
+use crate::Inc;
+
+
+/// An iterator yielding the values of `Ok` items of the wrapped
+/// iterator, stopping at the first `Err`.
+struct Oks<I, E> {
+  /// The iterator over results that we wrap.
+  iter: I,
+  /// The error encountered, if any.
+  error: Rc<Cell<Option<E>>>,
+}
+
+impl<I, T, E> Iterator for Oks<I, E>
+where
+  I: Iterator<Item = Result<T, E>>,
+{
+  type Item = T;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    match self.iter.next()? {
+      Ok(value) => Some(value),
+      Err(err) => {
+        self.error.set(Some(err));
+        None
+      },
+    }
+  }
+}
+
+
+/// An iterator over the gaps in a sequence represented by an iterator
+/// over results, reporting the first error encountered.
+///
+/// Objects of this type are created by
+/// [`FallibleGappable::fallible_gaps`].
+pub struct FallibleGapIter<I, T, E> {
+  /// The iterator over the gaps between the successfully produced
+  /// values.
+  gaps: GapIter<Oks<I, E>, T>,
+  /// The error reported by the wrapped iterator, if any.
+  error: Rc<Cell<Option<E>>>,
+  /// Whether iteration has finished.
+  done: bool,
+}
+
+impl<I, T, E> Debug for FallibleGapIter<I, T, E> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    f.debug_struct("FallibleGapIter")
+      .field("done", &self.done)
+      .finish()
+  }
+}
+
+impl<I, T, E> Iterator for FallibleGapIter<I, T, E>
+where
+  I: Iterator<Item = Result<T, E>>,
+  T: Clone + Ord + Inc,
+{
+  type Item = Result<Gap<T>, E>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    if self.done {
+      return None
+    }
+
+    let gap = self.gaps.next();
+    // A gap produced after the wrapped iterator reported an error is
+    // bogus, as the error cut the sequence short.
+    if let Some(err) = self.error.take() {
+      self.done = true;
+      return Some(Err(err))
+    }
+
+    if gap.is_none() {
+      self.done = true;
+    }
+    gap.map(Ok)
+  }
+}
+
+impl<I, T, E> FusedIterator for FallibleGapIter<I, T, E>
+where
+  I: Iterator<Item = Result<T, E>>,
+  T: Clone + Ord + Inc,
+{
+}
+
+
+/// An extension trait that provides range based access to the "gaps"
+/// between ordered elements yielded by a fallible iterator.
+///
+/// ```rust
+/// use std::ops::Bound;
+/// # use gaps::FallibleGappable as _;
+/// # use gaps::Gap;
+///
+/// let lines = "1\n2\n5\nx\n7";
+/// let mut gaps = lines.lines().map(str::parse::<u32>).fallible_gaps(0..10);
+/// assert_eq!(gaps.next(), Some(Ok(Gap::new(Bound::Included(0), Bound::Excluded(1)))));
+/// assert_eq!(gaps.next(), Some(Ok(Gap::new(Bound::Excluded(2), Bound::Excluded(5)))));
+/// assert!(matches!(gaps.next(), Some(Err(..))));
+/// assert_eq!(gaps.next(), None);
+/// ```
+pub trait FallibleGappable<I, T, E> {
+  /// Create a new [`FallibleGapIter`] that yields ranges identifying
+  /// the gaps in a certain range of a collection, stopping at the first
+  /// error reported by the iterator.
+  ///
+  /// # Notes
+  /// - the successfully produced values are assumed to be in ascending
+  ///   order
+  fn fallible_gaps<R>(self, range: R) -> FallibleGapIter<I, T, E>
+  where
+    R: RangeBounds<T>;
+}
+
+impl<I, T, E> FallibleGappable<I, T, E> for I
+where
+  I: Iterator<Item = Result<T, E>>,
+  T: Clone + Ord + Inc,
+{
+  fn fallible_gaps<R>(self, range: R) -> FallibleGapIter<I, T, E>
+  where
+    R: RangeBounds<T>,
+  {
+    let error = Rc::new(Cell::new(None));
+    let oks = Oks {
+      iter: self,
+      error: Rc::clone(&error),
+    };
+
+    FallibleGapIter {
+      gaps: oks.gaps(range),
+      error,
+      done: false,
+    }
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use std::ops::Bound::Excluded;
+  use std::ops::Bound::Included;
+  use std::ops::Bound::Unbounded;
+
+
+  /// Check that gaps are reported correctly for a sequence without
+  /// errors.
+  #[test]
+  fn no_errors() {
+    let values = vec![Ok::<_, ()>(1), Ok(3)];
+    let gaps = values.into_iter().fallible_gaps(..).collect::<Vec<_>>();
+    let expected = vec![
+      Ok(Gap::new(Unbounded, Excluded(1))),
+      Ok(Gap::new(Excluded(1), Excluded(3))),
+      Ok(Gap::new(Excluded(3), Unbounded)),
+    ];
+    assert_eq!(gaps, expected);
+  }
+
+  /// Check that the first error is reported and ends iteration.
+  #[test]
+  fn error_reporting() {
+    let values = vec![Ok(1), Ok(2), Err("first"), Ok(6), Err("second")];
+    let gaps = values.into_iter().fallible_gaps(1..).collect::<Vec<_>>();
+    let expected = vec![Err("first")];
+    assert_eq!(gaps, expected);
+
+    let values = vec![Ok(3), Err(())];
+    let gaps = values.into_iter().fallible_gaps(0..=5).collect::<Vec<_>>();
+    let expected = vec![Ok(Gap::new(Included(0), Excluded(3))), Err(())];
+    assert_eq!(gaps, expected);
+  }
+}
@@ -0,0 +1,93 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::ops::Bound::Excluded;
+use std::ops::Bound::Included;
+use std::ops::RangeInclusive;
+
+use crate::Gap;
+use crate::Inc;
+
+
+/// A sorted collection of values together with the gaps it is known
+/// to have in a range.
+///
+/// Fixtures are meant for property testing code consuming gaps. They
+/// are randomly generated by the strategies of the supported property
+/// testing frameworks and their gaps are determined independently of
+/// the gap iteration logic of this crate. They are reported in the
+/// exact form [`Gappable::gaps`][crate::Gappable::gaps] produces them
+/// for `range`.
+///
+/// ```rust
+/// # use gaps::GapFixture;
+/// # use gaps::Gappable as _;
+/// fn check(fixture: GapFixture<u16>) {
+///   let gaps = fixture.values.iter().copied().gaps(fixture.range).collect::<Vec<_>>();
+///   assert_eq!(gaps, fixture.gaps);
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct GapFixture<T> {
+  /// The present values, in strictly ascending order.
+  pub values: Vec<T>,
+  /// The range the values lie in.
+  pub range: RangeInclusive<T>,
+  /// The gaps among `values` in `range`.
+  pub gaps: Vec<Gap<T>>,
+}
+
+impl<T> GapFixture<T>
+where
+  T: Clone + Ord + Inc,
+{
+  /// Create a fixture for the range starting at `start`, with one
+  /// element of `mask` per value in the range, indicating whether the
+  /// value is present.
+  ///
+  /// The range ends early should the maximum value of `T` be reached.
+  /// `mask` must not be empty.
+  pub(crate) fn from_mask<I>(start: T, mask: I) -> Self
+  where
+    I: IntoIterator<Item = bool>,
+  {
+    let mut values = Vec::<T>::new();
+    let mut gaps = Vec::new();
+    let mut gap_start = None;
+    let mut next = Some(start.clone());
+    let mut end = start.clone();
+
+    for present in mask {
+      let value = match next {
+        Some(value) => value,
+        None => break,
+      };
+
+      if present {
+        if let Some(gap_start) = gap_start.take() {
+          gaps.push(Gap::new(gap_start, Excluded(value.clone())));
+        }
+        values.push(value.clone());
+      } else if gap_start.is_none() {
+        gap_start = Some(match values.last() {
+          Some(last) => Excluded(last.clone()),
+          None => Included(value.clone()),
+        });
+      }
+
+      next = value.clone().checked_inc();
+      end = value;
+    }
+
+    if let Some(gap_start) = gap_start {
+      gaps.push(Gap::new(gap_start, Included(end.clone())));
+    }
+
+    Self {
+      values,
+      range: start..=end,
+      gaps,
+    }
+  }
+}
@@ -0,0 +1,203 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::cmp::Ordering;
+use std::hash::Hash;
+use std::hash::Hasher;
+
+use crate::Inc;
+
+
+/// A floating point value quantized to a multiple of a bucket size.
+///
+/// Floating point values are neither totally ordered nor do they have
+/// a natural notion of a "next" value. `Quantized` maps a value to the
+/// bucket it falls into (rounding to the nearest multiple of the
+/// bucket size) and compares, hashes, and increments based on the
+/// bucket alone. As a result, readings suffering from small
+/// inaccuracies end up in the same bucket and gaps are detected
+/// deterministically. All values taking part in gap detection are
+/// expected to share the same bucket size.
+///
+/// ```rust
+/// use std::ops::Bound;
+/// # use gaps::Gappable as _;
+/// # use gaps::Quantized;
+///
+/// let at = |secs| Quantized::new(secs, 0.5f64);
+/// let readings = vec![at(0.0), at(0.49), at(1.02), at(2.5)];
+/// let missing = readings
+///   .into_iter()
+///   .missing(at(0.0)..=at(2.5))
+///   .map(|q| q.value())
+///   .collect::<Vec<_>>();
+/// assert_eq!(missing, vec![1.5, 2.0]);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Quantized<F> {
+  /// The index of the bucket the value falls into.
+  bucket: i64,
+  /// The size of each bucket.
+  size: F,
+}
+
+impl<F> Quantized<F> {
+  /// Retrieve the index of the bucket the value falls into.
+  pub fn bucket(&self) -> i64 {
+    self.bucket
+  }
+}
+
+impl<F> PartialEq for Quantized<F> {
+  fn eq(&self, other: &Self) -> bool {
+    self.bucket == other.bucket
+  }
+}
+
+impl<F> Eq for Quantized<F> {}
+
+impl<F> PartialOrd for Quantized<F> {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl<F> Ord for Quantized<F> {
+  fn cmp(&self, other: &Self) -> Ordering {
+    self.bucket.cmp(&other.bucket)
+  }
+}
+
+impl<F> Hash for Quantized<F> {
+  fn hash<H>(&self, state: &mut H)
+  where
+    H: Hasher,
+  {
+    self.bucket.hash(state)
+  }
+}
+
+impl<F> Inc for Quantized<F> {
+  fn inc(self) -> Self {
+    self
+      .checked_inc()
+      .expect("attempt to increment past maximum value")
+  }
+
+  fn checked_inc(self) -> Option<Self> {
+    Some(Self {
+      bucket: self.bucket.checked_add(1)?,
+      size: self.size,
+    })
+  }
+}
+
+impl<F> Quantized<F>
+where
+  F: Float,
+{
+  /// Create a new `Quantized` object for the bucket closest to the
+  /// provided value.
+  ///
+  /// # Panics
+  /// This method panics if `size` is not a positive finite number or if
+  /// `value` is not finite.
+  pub fn new(value: F, size: F) -> Self {
+    assert!(
+      size.is_finite() && size > F::ZERO,
+      "bucket size must be positive and finite"
+    );
+    assert!(value.is_finite(), "value must be finite");
+
+    Self {
+      bucket: value.bucket(size),
+      size,
+    }
+  }
+
+  /// Retrieve the value representing the bucket, i.e., the bucket index
+  /// multiplied by the bucket size.
+  pub fn value(&self) -> F {
+    F::from_bucket(self.bucket, self.size)
+  }
+
+  /// Retrieve the size of each bucket.
+  pub fn size(&self) -> F {
+    self.size
+  }
+}
+
+
+/// A (sealed) trait for the floating point types supported by
+/// [`Quantized`].
+pub trait Float: Copy + PartialOrd {
+  /// The value zero.
+  #[doc(hidden)]
+  const ZERO: Self;
+
+  /// Check whether the value is neither infinite nor NaN.
+  #[doc(hidden)]
+  fn is_finite(self) -> bool;
+
+  /// Determine the index of the bucket closest to `self`.
+  #[doc(hidden)]
+  fn bucket(self, size: Self) -> i64;
+
+  /// Determine the value representing the bucket with the given index.
+  #[doc(hidden)]
+  fn from_bucket(bucket: i64, size: Self) -> Self;
+}
+
+macro_rules! float {
+  ( $t:ty ) => {
+    impl Float for $t {
+      const ZERO: Self = 0.0;
+
+      fn is_finite(self) -> bool {
+        <$t>::is_finite(self)
+      }
+
+      fn bucket(self, size: Self) -> i64 {
+        (self / size).round() as i64
+      }
+
+      fn from_bucket(bucket: i64, size: Self) -> Self {
+        bucket as $t * size
+      }
+    }
+  };
+}
+
+float!(f32);
+float!(f64);
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+
+  /// Check that values are mapped to the closest bucket.
+  #[test]
+  fn quantization() {
+    assert_eq!(Quantized::new(0.24f64, 0.5), Quantized::new(0.0, 0.5));
+    assert_eq!(Quantized::new(0.26f64, 0.5).bucket(), 1);
+    assert_eq!(Quantized::new(-1.1f32, 0.5).bucket(), -2);
+    assert!(Quantized::new(1.0f64, 0.5) < Quantized::new(1.5, 0.5));
+  }
+
+  /// Check that `Quantized` values advance by one bucket.
+  #[test]
+  fn increment() {
+    let value = Quantized::new(2.0f64, 0.25).inc();
+    assert_eq!(value.bucket(), 9);
+    assert_eq!(value.value(), 2.25);
+  }
+
+  /// Check that an invalid bucket size is rejected.
+  #[test]
+  #[should_panic(expected = "bucket size must be positive and finite")]
+  fn invalid_size() {
+    let _x = Quantized::new(1.0f32, 0.0);
+  }
+}
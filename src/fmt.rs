@@ -0,0 +1,230 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Formatting and parsing of gaps in compact range list syntax.
+//!
+//! The syntax is a comma separated list of items, each of which is
+//! either a single value (`8`), an inclusive range (`1-5`), or a range
+//! with an open start (`-5`) or end (`10-`). It is used, among others,
+//! for printer page selections and cgroup cpusets.
+//!
+//! Because `-` is used as the range separator, negative values can not
+//! be expressed.
+
+use std::fmt::Display;
+use std::fmt::Write as _;
+use std::iter::FromIterator;
+use std::ops::Bound;
+use std::ops::Bound::Excluded;
+use std::ops::Bound::Included;
+use std::ops::Bound::Unbounded;
+use std::str::FromStr;
+
+use crate::Dec;
+use crate::Gap;
+use crate::Inc;
+use crate::ParseError;
+
+
+/// Format a sequence of gaps in range list syntax.
+///
+/// Empty gaps are skipped.
+///
+/// ```rust
+/// use std::ops::Bound;
+/// # use gaps::Gap;
+///
+/// let gaps = vec![
+///   Gap::new(Bound::Included(1), Bound::Included(5)),
+///   Gap::new(Bound::Excluded(7), Bound::Excluded(9)),
+///   Gap::new(Bound::Included(10), Bound::Unbounded),
+/// ];
+/// assert_eq!(gaps::fmt::format(gaps), "1-5,8,10-");
+/// ```
+pub fn format<I, T>(gaps: I) -> String
+where
+  I: IntoIterator<Item = Gap<T>>,
+  T: Clone + Ord + Inc + Dec + Display,
+{
+  let mut string = String::new();
+  for gap in gaps {
+    if gap.is_empty() {
+      continue
+    }
+
+    let (start, end) = gap.into_bounds();
+    let end = match end {
+      Included(end) => Some(end),
+      Excluded(end) => match end.checked_dec() {
+        Some(end) => Some(end),
+        // A gap ending before the minimum value of the type is empty,
+        // too.
+        None => continue,
+      },
+      Unbounded => None,
+    };
+
+    if !string.is_empty() {
+      string.push(',');
+    }
+
+    // Writing to a `String` can not fail.
+    let _ = match (first(start), end) {
+      (Some(start), Some(end)) if start == end => write!(string, "{}", start),
+      (Some(start), Some(end)) => write!(string, "{}-{}", start, end),
+      (Some(start), None) => write!(string, "{}-", start),
+      (None, Some(end)) => write!(string, "-{}", end),
+      (None, None) => write!(string, "-"),
+    };
+  }
+  string
+}
+
+/// Retrieve the first value of a non-empty gap, if it is bounded.
+fn first<T>(start: Bound<T>) -> Option<T>
+where
+  T: Inc,
+{
+  match start {
+    Included(start) => Some(start),
+    Excluded(start) => Some(start.inc()),
+    Unbounded => None,
+  }
+}
+
+
+/// Parse a string in range list syntax into a collection of gaps.
+///
+/// All bounded ends of the resulting gaps are inclusive. Items are
+/// reported in the order in which they appear in the input and are
+/// neither sorted nor merged.
+///
+/// ```rust
+/// use std::ops::Bound;
+/// # use gaps::Gap;
+/// # use gaps::GapList;
+///
+/// let gaps = gaps::fmt::parse::<Vec<_>, u32>("1-5, 8,10-").unwrap();
+/// assert_eq!(
+///   gaps,
+///   vec![
+///     Gap::new(Bound::Included(1), Bound::Included(5)),
+///     Gap::new(Bound::Included(8), Bound::Included(8)),
+///     Gap::new(Bound::Included(10), Bound::Unbounded),
+///   ]
+/// );
+///
+/// let list = gaps::fmt::parse::<GapList<_>, u32>("-3").unwrap();
+/// assert_eq!(list.get(0), Some(&Gap::new(Bound::Unbounded, Bound::Included(3))));
+/// ```
+pub fn parse<C, T>(s: &str) -> Result<C, ParseError<T::Err>>
+where
+  C: FromIterator<Gap<T>>,
+  T: FromStr,
+{
+  let s = s.trim();
+  if s.is_empty() {
+    return Ok(C::from_iter(None))
+  }
+
+  s.split(',').map(parse_item).collect()
+}
+
+/// Parse a single item of a range list.
+fn parse_item<T>(item: &str) -> Result<Gap<T>, ParseError<T::Err>>
+where
+  T: FromStr,
+{
+  let item = item.trim();
+  if item.is_empty() {
+    return Err(ParseError::EmptyItem)
+  }
+
+  let bound = |value: &str| {
+    let value = value.trim();
+    if value.is_empty() {
+      Ok(Unbounded)
+    } else {
+      value.parse().map(Included).map_err(ParseError::Value)
+    }
+  };
+
+  if let Some((start, end)) = item.split_once('-') {
+    Ok(Gap::new(bound(start)?, bound(end)?))
+  } else {
+    let start = bound(item)?;
+    let end = bound(item)?;
+    Ok(Gap::new(start, end))
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use std::collections::BTreeSet;
+  use std::num::ParseIntError;
+
+  use crate::GapList;
+  use crate::RangeGappable as _;
+
+
+  /// Check that gaps are formatted correctly.
+  #[test]
+  fn formatting() {
+    assert_eq!(format(Vec::<Gap<u8>>::new()), "");
+
+    let gaps = vec![
+      Gap::new(Unbounded, Excluded(2u8)),
+      Gap::new(Excluded(2), Excluded(3)),
+      Gap::new(Excluded(3), Included(4)),
+      Gap::new(Excluded(u8::MAX), Unbounded),
+    ];
+    assert_eq!(format(gaps), "-1,4");
+
+    let gaps = vec![
+      Gap::new(Unbounded, Excluded(0u8)),
+      Gap::new(Excluded(0), Excluded(3)),
+    ];
+    assert_eq!(format(gaps), "1-2");
+
+    let set = BTreeSet::from([0u8, 3]);
+    assert_eq!(format(set.gaps(..)), "1-2,4-");
+
+    let gaps = vec![Gap::<u8>::new(Unbounded, Unbounded)];
+    assert_eq!(format(gaps), "-");
+  }
+
+  /// Check that range lists are parsed correctly.
+  #[test]
+  fn parsing() {
+    let gaps = parse::<Vec<_>, u8>("").unwrap();
+    assert_eq!(gaps, Vec::new());
+
+    let gaps = parse::<Vec<_>, u8>(" 3 , 5 - 7,- ").unwrap();
+    let expected = vec![
+      Gap::new(Included(3), Included(3)),
+      Gap::new(Included(5), Included(7)),
+      Gap::new(Unbounded, Unbounded),
+    ];
+    assert_eq!(gaps, expected);
+
+    let result = parse::<Vec<_>, u8>("1,,3");
+    assert_eq!(result, Err(ParseError::EmptyItem));
+
+    let result = parse::<Vec<_>, u8>("1-x");
+    assert!(matches!(
+      result,
+      Err(ParseError::Value(ParseIntError { .. }))
+    ));
+  }
+
+  /// Check that formatted gaps can be parsed back.
+  #[test]
+  fn round_trip() {
+    let string = "0,2-5,7-8,10-";
+    let list = parse::<GapList<u16>, _>(string).unwrap();
+    assert_eq!(format(list), string);
+  }
+}
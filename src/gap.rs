@@ -0,0 +1,542 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+use std::iter::once;
+use std::iter::Once;
+use std::ops::Bound;
+use std::ops::Bound::Excluded;
+use std::ops::Bound::Included;
+use std::ops::Bound::Unbounded;
+use std::ops::Range;
+use std::ops::RangeBounds;
+use std::ops::RangeFrom;
+use std::ops::RangeInclusive;
+use std::ops::RangeTo;
+use std::ops::RangeToInclusive;
+
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+use crate::bounds::cmp_end;
+use crate::bounds::cmp_start;
+use crate::bounds::len;
+use crate::bounds::start_le_end;
+use crate::Dec;
+use crate::Distance;
+use crate::GapError;
+use crate::Inc;
+use crate::Values;
+
+
+/// A gap, i.e., a range of values missing from a sequence.
+///
+/// ```rust
+/// use std::ops::Bound;
+/// # use gaps::Gap;
+///
+/// let gap = Gap::new(Bound::Excluded(1), Bound::Included(4));
+/// assert_eq!(gap.start(), &Bound::Excluded(1));
+/// assert_eq!(gap.end(), &Bound::Included(4));
+/// assert_eq!(gap.into_bounds(), (Bound::Excluded(1), Bound::Included(4)));
+/// ```
+///
+/// Gaps are ordered by their start and then by their end bound. The
+/// comparison is structural, meaning that, say, `(Excluded(1),
+/// Included(4))` and `(Included(2), Included(4))` are considered
+/// distinct, even though they cover the same values.
+///
+/// With the `serde` feature enabled, a gap is serialized as a struct
+/// with `start` and `end` fields, each using `serde`'s representation
+/// of [`Bound`]: an externally tagged enum with the variants
+/// `Included`, `Excluded`, and `Unbounded`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Gap<T> {
+  /// The start of the gap.
+  start: Bound<T>,
+  /// The end of the gap.
+  end: Bound<T>,
+}
+
+impl<T> Gap<T> {
+  /// Create a new `Gap` from its start and end bounds.
+  pub fn new(start: Bound<T>, end: Bound<T>) -> Self {
+    Self { start, end }
+  }
+
+  /// Retrieve the start of the gap.
+  pub fn start(&self) -> &Bound<T> {
+    &self.start
+  }
+
+  /// Retrieve the end of the gap.
+  pub fn end(&self) -> &Bound<T> {
+    &self.end
+  }
+
+  /// Destructure the gap into its start and end bounds.
+  pub fn into_bounds(self) -> (Bound<T>, Bound<T>) {
+    (self.start, self.end)
+  }
+}
+
+impl<T> Gap<T>
+where
+  T: Clone + Ord + Inc,
+{
+  /// Check whether the gap contains no values at all.
+  ///
+  /// Gaps produced by this crate are never empty, but gaps created
+  /// manually may be.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use gaps::Gap;
+  ///
+  /// assert!(!Gap::new(Bound::Excluded(1), Bound::Excluded(3)).is_empty());
+  /// assert!(Gap::new(Bound::Excluded(1), Bound::Excluded(2)).is_empty());
+  /// assert!(Gap::new(Bound::Excluded(u8::MAX), Bound::Unbounded).is_empty());
+  /// ```
+  pub fn is_empty(&self) -> bool {
+    !start_le_end(&self.start, &self.end)
+  }
+
+  /// Check whether `value` lies inside the gap.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use gaps::Gap;
+  ///
+  /// let gap = Gap::new(Bound::Excluded(1), Bound::Included(4));
+  /// assert!(!gap.contains(&1));
+  /// assert!(gap.contains(&2));
+  /// assert!(gap.contains(&4));
+  /// assert!(!gap.contains(&5));
+  /// ```
+  pub fn contains(&self, value: &T) -> bool {
+    let value = Included(value.clone());
+    start_le_end(&self.start, &value) && start_le_end(&value, &self.end)
+  }
+
+  /// Retrieve the number of values inside the gap.
+  ///
+  /// `None` is returned if the gap is unbounded or the number of values
+  /// can not be represented as `usize`. The bounds of gaps over types
+  /// with a finite [`Domain`][crate::Domain] can be resolved using
+  /// [`range::resolve`][crate::range::resolve].
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use gaps::Gap;
+  ///
+  /// assert_eq!(Gap::new(Bound::Excluded(1), Bound::Included(4)).len(), Some(3));
+  /// assert_eq!(Gap::new(Bound::Excluded(1), Bound::Excluded(2)).len(), Some(0));
+  /// assert_eq!(Gap::new(Bound::Included(1), Bound::Unbounded).len(), None);
+  /// ```
+  pub fn len(&self) -> Option<usize>
+  where
+    T: Clone + Distance,
+  {
+    len(&self.start, &self.end)
+  }
+
+  /// Create an iterator over the individual values inside the gap.
+  ///
+  /// A gap with an unbounded start yields no values, as there is no
+  /// way of knowing its first value. A gap with an unbounded end
+  /// yields values up to the maximum value of the type, if any.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use gaps::Gap;
+  ///
+  /// let gap = Gap::new(Bound::Excluded(1), Bound::Excluded(5));
+  /// assert_eq!(gap.iter().collect::<Vec<_>>(), vec![2, 3, 4]);
+  /// assert_eq!(gap.iter().take(2).collect::<Vec<_>>(), vec![2, 3]);
+  /// ```
+  pub fn iter(&self) -> Values<Once<Self>, T>
+  where
+    T: Clone,
+  {
+    Values::new(once(self.clone()))
+  }
+}
+
+impl<T> Gap<T>
+where
+  T: Inc,
+{
+  /// Retrieve the first value inside the gap.
+  fn first(start: Bound<T>) -> Result<T, GapError<T>> {
+    match start {
+      Included(start) => Ok(start),
+      Excluded(start) => start.checked_inc().ok_or(GapError::Unrepresentable),
+      Unbounded => Err(GapError::Unbounded),
+    }
+  }
+}
+
+impl<T> PartialOrd for Gap<T>
+where
+  T: Ord,
+{
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl<T> Ord for Gap<T>
+where
+  T: Ord,
+{
+  fn cmp(&self, other: &Self) -> Ordering {
+    cmp_start(&self.start, &other.start).then_with(|| cmp_end(&self.end, &other.end))
+  }
+}
+
+/// Format a gap using interval notation.
+///
+/// ```rust
+/// use std::ops::Bound;
+/// # use gaps::Gap;
+///
+/// let gap = Gap::new(Bound::Included(3), Bound::Excluded(7));
+/// assert_eq!(gap.to_string(), "[3, 7)");
+///
+/// let gap = Gap::new(Bound::Excluded(4), Bound::Unbounded);
+/// assert_eq!(gap.to_string(), "(4, ∞)");
+/// ```
+impl<T> Display for Gap<T>
+where
+  T: Display,
+{
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    match &self.start {
+      Included(start) => write!(f, "[{}, ", start)?,
+      Excluded(start) => write!(f, "({}, ", start)?,
+      Unbounded => f.write_str("(-∞, ")?,
+    }
+
+    match &self.end {
+      Included(end) => write!(f, "{}]", end),
+      Excluded(end) => write!(f, "{})", end),
+      Unbounded => f.write_str("∞)"),
+    }
+  }
+}
+
+
+/// A helper for formatting a sequence of gaps as a comma separated
+/// list in interval notation.
+///
+/// ```rust
+/// use std::ops::Bound;
+/// # use gaps::DisplayList;
+/// # use gaps::Gap;
+///
+/// let gaps = [
+///   Gap::new(Bound::Included(0), Bound::Excluded(1)),
+///   Gap::new(Bound::Included(3), Bound::Included(5)),
+///   Gap::new(Bound::Included(9), Bound::Unbounded),
+/// ];
+/// assert_eq!(
+///   DisplayList::new(&gaps).to_string(),
+///   "[0, 1), [3, 5], [9, ∞)"
+/// );
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct DisplayList<'g, T> {
+  /// The gaps to format.
+  gaps: &'g [Gap<T>],
+}
+
+impl<'g, T> DisplayList<'g, T> {
+  /// Create a new `DisplayList` formatting the provided gaps.
+  pub fn new(gaps: &'g [Gap<T>]) -> Self {
+    Self { gaps }
+  }
+}
+
+impl<T> Display for DisplayList<'_, T>
+where
+  T: Display,
+{
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    for (i, gap) in self.gaps.iter().enumerate() {
+      if i > 0 {
+        f.write_str(", ")?;
+      }
+      write!(f, "{}", gap)?;
+    }
+    Ok(())
+  }
+}
+
+
+impl<T> RangeBounds<T> for Gap<T> {
+  fn start_bound(&self) -> Bound<&T> {
+    self.start.as_ref()
+  }
+
+  fn end_bound(&self) -> Bound<&T> {
+    self.end.as_ref()
+  }
+}
+
+impl<T> From<(Bound<T>, Bound<T>)> for Gap<T> {
+  fn from((start, end): (Bound<T>, Bound<T>)) -> Self {
+    Self::new(start, end)
+  }
+}
+
+impl<T> From<Gap<T>> for (Bound<T>, Bound<T>) {
+  fn from(gap: Gap<T>) -> Self {
+    gap.into_bounds()
+  }
+}
+
+macro_rules! from_range {
+  ( $range:ident, |$r:ident| $start:expr, $end:expr ) => {
+    impl<T> From<$range<T>> for Gap<T> {
+      fn from($r: $range<T>) -> Self {
+        Self::new($start, $end)
+      }
+    }
+  };
+}
+
+from_range!(Range, |range| Included(range.start), Excluded(range.end));
+from_range!(RangeFrom, |range| Included(range.start), Unbounded);
+from_range!(RangeTo, |range| Unbounded, Excluded(range.end));
+from_range!(RangeToInclusive, |range| Unbounded, Included(range.end));
+
+impl<T> From<RangeInclusive<T>> for Gap<T> {
+  fn from(range: RangeInclusive<T>) -> Self {
+    let (start, end) = range.into_inner();
+    Self::new(Included(start), Included(end))
+  }
+}
+
+/// Convert a gap into a half-open range.
+///
+/// ```rust
+/// use std::convert::TryFrom as _;
+/// use std::ops::Bound;
+/// use std::ops::Range;
+/// # use gaps::Gap;
+///
+/// let data = [0, 1, 2, 3, 4, 5];
+/// let gap = Gap::new(Bound::Excluded(1), Bound::Included(3));
+/// assert_eq!(&data[Range::try_from(gap).unwrap()], &[2, 3]);
+/// ```
+impl<T> TryFrom<Gap<T>> for Range<T>
+where
+  T: Inc,
+{
+  type Error = GapError<T>;
+
+  fn try_from(gap: Gap<T>) -> Result<Self, Self::Error> {
+    let (start, end) = gap.into_bounds();
+    let end = match end {
+      Included(end) => end.checked_inc().ok_or(GapError::Unrepresentable)?,
+      Excluded(end) => end,
+      Unbounded => return Err(GapError::Unbounded),
+    };
+    Ok(Gap::first(start)?..end)
+  }
+}
+
+/// Convert a gap into an inclusive range.
+///
+/// ```rust
+/// use std::convert::TryFrom as _;
+/// use std::ops::Bound;
+/// use std::ops::RangeInclusive;
+/// # use gaps::Gap;
+///
+/// let gap = Gap::new(Bound::Excluded(1), Bound::Excluded(5));
+/// assert_eq!(RangeInclusive::try_from(gap), Ok(2..=4));
+/// ```
+impl<T> TryFrom<Gap<T>> for RangeInclusive<T>
+where
+  T: Clone + Ord + Inc + Dec,
+{
+  type Error = GapError<T>;
+
+  fn try_from(gap: Gap<T>) -> Result<Self, Self::Error> {
+    // An empty gap ending with an excluded value may not have a last
+    // value that we could express.
+    if gap.is_empty() {
+      return Err(GapError::Unrepresentable)
+    }
+
+    let (start, end) = gap.into_bounds();
+    let end = match end {
+      Included(end) => end,
+      Excluded(end) => end.checked_dec().ok_or(GapError::Unrepresentable)?,
+      Unbounded => return Err(GapError::Unbounded),
+    };
+    Ok(Gap::first(start)?..=end)
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+
+  /// Check that the number of values in a gap is reported correctly.
+  #[test]
+  fn length() {
+    assert_eq!(Gap::new(Included(1), Included(1)).len(), Some(1));
+    assert_eq!(Gap::new(Included(1), Excluded(1)).len(), Some(0));
+    assert_eq!(Gap::new(Excluded(1), Excluded(3)).len(), Some(1));
+    assert_eq!(Gap::new(Included(-2i8), Included(2)).len(), Some(5));
+    assert_eq!(Gap::new(Included(0u8), Included(u8::MAX)).len(), Some(256));
+    assert_eq!(
+      Gap::new(Excluded(u8::MAX), Included(u8::MAX)).len(),
+      Some(0)
+    );
+    assert_eq!(Gap::new(Unbounded, Included(3)).len(), None);
+    assert_eq!(Gap::<u64>::new(Unbounded, Unbounded).len(), None);
+  }
+
+  /// Check that empty gaps are detected as such.
+  #[test]
+  fn emptiness() {
+    assert!(!Gap::new(Included(1), Included(1)).is_empty());
+    assert!(Gap::new(Included(1), Excluded(1)).is_empty());
+    assert!(Gap::new(Included(2), Included(1)).is_empty());
+    assert!(!Gap::new(Unbounded, Excluded(i32::MIN)).is_empty());
+    assert!(!Gap::new(Excluded(7), Unbounded).is_empty());
+    assert!(Gap::new(Excluded(u16::MAX), Unbounded).is_empty());
+  }
+
+  /// Check that we can iterate over the values inside a gap.
+  #[test]
+  fn iteration() {
+    let values = |gap: Gap<u8>| gap.iter().collect::<Vec<_>>();
+    assert_eq!(values(Gap::new(Included(1), Included(3))), vec![1, 2, 3]);
+    assert_eq!(values(Gap::new(Included(1), Excluded(1))), vec![]);
+    assert_eq!(values(Gap::new(Excluded(253), Unbounded)), vec![254, 255]);
+    assert_eq!(values(Gap::new(Excluded(u8::MAX), Unbounded)), vec![]);
+    assert_eq!(values(Gap::new(Unbounded, Included(3))), vec![]);
+  }
+
+  /// Check that gaps are ordered by start and then end.
+  #[test]
+  fn ordering() {
+    let mut gaps = vec![
+      Gap::new(Included(3), Unbounded),
+      Gap::new(Included(3), Excluded(5)),
+      Gap::new(Excluded(1), Included(2)),
+      Gap::new(Unbounded, Excluded(1)),
+      Gap::new(Included(3), Included(5)),
+      Gap::new(Excluded(1), Included(2)),
+    ];
+    gaps.sort();
+    gaps.dedup();
+
+    let expected = vec![
+      Gap::new(Unbounded, Excluded(1)),
+      Gap::new(Excluded(1), Included(2)),
+      Gap::new(Included(3), Excluded(5)),
+      Gap::new(Included(3), Included(5)),
+      Gap::new(Included(3), Unbounded),
+    ];
+    assert_eq!(gaps, expected);
+  }
+
+  /// Check that gaps are formatted using interval notation.
+  #[test]
+  fn formatting() {
+    let gap = Gap::new(Unbounded, Included(-3));
+    assert_eq!(gap.to_string(), "(-∞, -3]");
+
+    let gap = Gap::<u8>::new(Unbounded, Unbounded);
+    assert_eq!(gap.to_string(), "(-∞, ∞)");
+
+    let gaps = [];
+    assert_eq!(DisplayList::<u8>::new(&gaps).to_string(), "");
+
+    let gaps = [
+      Gap::new(Excluded(1), Excluded(3)),
+      Gap::new(Excluded(4), Included(5)),
+    ];
+    assert_eq!(DisplayList::new(&gaps).to_string(), "(1, 3), (4, 5]");
+  }
+
+  /// Check that gaps can be converted into standard ranges.
+  #[test]
+  fn range_conversion() {
+    let gap = Gap::new(Excluded(1u8), Included(3));
+    assert_eq!(Range::try_from(gap), Ok(2..4));
+    assert_eq!(RangeInclusive::try_from(gap), Ok(2..=3));
+
+    let gap = Gap::new(Included(1u8), Included(u8::MAX));
+    assert_eq!(Range::try_from(gap), Err(GapError::Unrepresentable));
+    assert_eq!(RangeInclusive::try_from(gap), Ok(1..=u8::MAX));
+
+    let gap = Gap::new(Excluded(u8::MAX), Excluded(u8::MAX));
+    assert_eq!(Range::try_from(gap), Err(GapError::Unrepresentable));
+    assert_eq!(
+      RangeInclusive::try_from(gap),
+      Err(GapError::Unrepresentable)
+    );
+
+    let gap = Gap::new(Unbounded, Excluded(4u8));
+    assert_eq!(Range::try_from(gap), Err(GapError::Unbounded));
+    assert_eq!(RangeInclusive::try_from(gap), Err(GapError::Unbounded));
+
+    let gap = Gap::new(Unbounded, Excluded(0u8));
+    assert_eq!(
+      RangeInclusive::try_from(gap),
+      Err(GapError::Unrepresentable)
+    );
+
+    let gap = Gap::new(Included(4u8), Unbounded);
+    assert_eq!(Range::try_from(gap), Err(GapError::Unbounded));
+    assert_eq!(RangeInclusive::try_from(gap), Err(GapError::Unbounded));
+  }
+
+  /// Check that standard ranges can be converted into gaps.
+  #[test]
+  fn gap_conversion() {
+    assert_eq!(Gap::from(1..4), Gap::new(Included(1), Excluded(4)));
+    assert_eq!(Gap::from(1..=4), Gap::new(Included(1), Included(4)));
+    assert_eq!(Gap::from(1..), Gap::new(Included(1), Unbounded));
+    assert_eq!(Gap::from(..4), Gap::new(Unbounded, Excluded(4)));
+    assert_eq!(Gap::from(..=4), Gap::new(Unbounded, Included(4)));
+
+    let gap = Gap::new(Excluded(1), Included(4));
+    assert_eq!(gap.start_bound(), Excluded(&1));
+    assert_eq!(gap.end_bound(), Included(&4));
+  }
+
+  /// Check that values are correctly determined to lie inside a gap.
+  #[test]
+  fn containment() {
+    let gap = Gap::new(Unbounded, Excluded(3));
+    assert!(gap.contains(&i64::MIN));
+    assert!(gap.contains(&2));
+    assert!(!gap.contains(&3));
+
+    let gap = Gap::new(Excluded(3u8), Unbounded);
+    assert!(!gap.contains(&3));
+    assert!(gap.contains(&4));
+    assert!(gap.contains(&u8::MAX));
+
+    let gap = Gap::new(Included(5), Included(5));
+    assert!(!gap.contains(&4));
+    assert!(gap.contains(&5));
+    assert!(!gap.contains(&6));
+  }
+}
@@ -1,27 +1,186 @@
 // Copyright (C) 2020-2022 Daniel Mueller <deso@posteo.net>
 // SPDX-License-Identifier: GPL-3.0-or-later
 
-use std::collections::btree_map::Range as BTreeMapRange;
-use std::collections::btree_set::Range as BTreeSetRange;
 use std::collections::BTreeMap;
 use std::collections::BTreeSet;
-use std::iter::Copied;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::iter::Cloned;
+use std::iter::FusedIterator;
 use std::iter::Map;
+use std::iter::Rev;
+use std::mem::replace;
 use std::ops::Bound;
 use std::ops::Bound::Excluded;
 use std::ops::Bound::Included;
 use std::ops::Bound::Unbounded;
+use std::ops::ControlFlow;
 use std::ops::RangeBounds;
+use std::vec;
 
 use crate::bounds::bounds;
 use crate::bounds::end_lt_end;
+use crate::bounds::flip;
+use crate::bounds::len;
 use crate::bounds::start_le_end;
 use crate::bounds::start_le_start;
 use crate::bounds::start_lt_start;
+use crate::query::count;
+use crate::query::coverage;
+use crate::query::find;
+use crate::query::k_largest;
+use crate::query::largest;
+use crate::query::stats;
+use crate::Aligned;
+use crate::Chunks;
+use crate::Clip;
+use crate::Coalesce;
+use crate::Difference;
+use crate::Distance;
+use crate::Domain;
+use crate::Fit;
+use crate::Gap;
+use crate::GapError;
+use crate::GapStats;
+use crate::GroupedGapIter;
 use crate::Inc;
+use crate::Intersection;
+use crate::LenientGapIter;
+use crate::Multiple;
+use crate::RangeIter;
+use crate::Ranged;
+use crate::RevGapIter;
+use crate::RunIter;
+use crate::SegmentIter;
+use crate::Step;
+use crate::Stepped;
+use crate::SymmetricDifference;
+use crate::Union;
+use crate::Values;
+
+
+/// The type of a function repositioning an iterator to cover a range.
+type SeekFn<I, T> = fn(&mut I, Bound<T>, Bound<T>);
+
+
+/// Check that `this` is not less than the previously seen element, if
+/// any, and remember it as the last element.
+fn check_order<T>(last: &mut Option<T>, this: &T) -> Result<(), GapError<T>>
+where
+  T: Clone + Ord,
+{
+  match last.replace(this.clone()) {
+    Some(prev) if *this < prev => Err(GapError::Unsorted {
+      prev,
+      next: this.clone(),
+    }),
+    _ => Ok(()),
+  }
+}
+
+/// Check whether there is any gap in a range of a collection based
+/// solely on the number of elements it contains and its first and last
+/// element.
+///
+/// `None` is returned if that is not sufficient for deciding.
+fn decide_by_count<T>(
+  count: usize,
+  first_last: Option<(&T, &T)>,
+  start: Bound<T>,
+  end: Bound<T>,
+) -> Option<bool>
+where
+  T: Clone + Ord + Distance,
+{
+  let span = len(&start, &end)?;
+  if count < span {
+    // There are fewer elements than values in the range, so some of
+    // them have to be missing.
+    return Some(true)
+  }
+
+  let (first, last) = first_last?;
+  let range = (start, end);
+  if range.contains(first) && range.contains(last) {
+    // All elements lie in the range and the collection contains no
+    // duplicates, so there can only be as many as there are values.
+    Some(false)
+  } else {
+    None
+  }
+}
+
+/// Retrieve the first value inside a gap, if its start is bounded.
+fn first_value<T>(gap: Gap<T>) -> Option<T>
+where
+  T: Inc,
+{
+  match gap.into_bounds().0 {
+    Included(start) => Some(start),
+    Excluded(start) => start.checked_inc(),
+    Unbounded => None,
+  }
+}
+
+/// Process an element produced by the wrapped iterator, adjusting the
+/// `start` of the remaining range accordingly.
+///
+/// The function returns the gap terminated by the element, if any, and
+/// whether iteration is done.
+#[allow(clippy::type_complexity)]
+pub(crate) fn step<T>(start: &mut Bound<T>, end: &Bound<T>, this: T) -> (Option<(Bound<T>, Bound<T>)>, bool)
+where
+  T: Clone + Ord + Inc,
+{
+  let this = Included(this);
+  if *start != Unbounded && start_le_start(&this, start) {
+    // As long as our current element is still less than or even equal
+    // to the actual start of the range that we consider, we just
+    // continue.
+    if !start_lt_start(&this, start) {
+      // But if it is equal to the start bound then we adjust the start
+      // bound to exclude this element.
+      *start = flip(this);
+    }
+    return (None, false)
+  }
+
+  let gap_end = flip(this);
+  if !end_lt_end(&gap_end, end) {
+    // Once we see an element being produced that is at or past our
+    // overarching range's end, we are done.
+    let gap_start = replace(start, gap_end);
+    let gap = if start_le_end(&gap_start, end) {
+      Some((gap_start, end.clone()))
+    } else {
+      None
+    };
+    return (gap, true)
+  }
+
+  // We could still end up with a range that is empty (or even
+  // descending). Don't report those. Only clone the element if it ends
+  // a gap that we report.
+  let gap = if start_le_end(start, &gap_end) {
+    Some((replace(start, gap_end.clone()), gap_end))
+  } else {
+    *start = gap_end;
+    None
+  };
+  // If our start has caught up with our end, we are done.
+  let done = !start_le_end(start, end);
+  (gap, done)
+}
 
 
 /// An iterator over the gaps in a sequence represented by an iterator.
+///
+/// # Notes
+/// - skipping over gaps (e.g., by means of [`Iterator::nth`]) requires
+///   inspection of all elements in between and is linear in their
+///   number, even for collections supporting efficient range queries;
+///   if the value to continue at is known, [`GapIter::advance_to`] is
+///   the more efficient alternative
 #[derive(Clone, Debug)]
 pub struct GapIter<I, T> {
   /// The iterator that we wrap.
@@ -32,14 +191,21 @@ pub struct GapIter<I, T> {
   start: Bound<T>,
   /// The end of the range to iterate over.
   end: Bound<T>,
-  #[cfg(debug_assertions)]
+  /// The last element produced by the wrapped iterator, if any. It is
+  /// only tracked in debug builds and when reporting non-ascending
+  /// sequences as errors.
   last: Option<T>,
+  /// A function for repositioning the wrapped iterator to cover a
+  /// different range, if supported.
+  seek: Option<SeekFn<I, T>>,
+  /// The next gap, if it has been peeked at already.
+  peeked: Option<Gap<T>>,
 }
 
 impl<I, T> GapIter<I, T>
 where
   I: Iterator<Item = T>,
-  T: Copy + Ord + Inc,
+  T: Clone + Ord + Inc,
 {
   /// Create a new `GapIter` wrapping the provided iterator and yielding
   /// ranges identifying the gaps between the elements, if any.
@@ -48,120 +214,1140 @@ where
   /// - the provided iterator is assumed to yield elements in ascending
   ///   order
   pub fn new(iter: I, start: Bound<T>, end: Bound<T>) -> Self {
+    Self::from_parts(Some(iter), start, end)
+  }
+
+  /// Create a new `GapIter` from the parts produced by
+  /// [`GapIter::into_parts`].
+  pub(crate) fn from_parts(iter: Option<I>, start: Bound<T>, end: Bound<T>) -> Self {
     Self {
-      iter: Some(iter),
+      iter,
       start,
       end,
-      #[cfg(debug_assertions)]
       last: None,
+      seek: None,
+      peeked: None,
+    }
+  }
+
+  /// Create a new `GapIter` wrapping an iterator that can be
+  /// repositioned using the provided `seek` function.
+  pub(crate) fn with_seek(iter: I, start: Bound<T>, end: Bound<T>, seek: SeekFn<I, T>) -> Self {
+    Self {
+      seek: Some(seek),
+      ..Self::new(iter, start, end)
+    }
+  }
+
+  /// Advance the iterator to skip all gaps (or parts thereof) before
+  /// `value`.
+  ///
+  /// For iterators created by [`RangeGappable::gaps`] the wrapped
+  /// iterator is repositioned directly, instead of it having to skip
+  /// over all elements less than `value` one by one.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use maplit::btreeset;
+  /// # use gaps::Gap;
+  /// # use gaps::RangeGappable as _;
+  ///
+  /// let set = btreeset!{1, 3, 4, 8};
+  /// let mut gaps = set.gaps(0..=10);
+  /// assert_eq!(gaps.next(), Some(Gap::new(Bound::Included(0), Bound::Excluded(1))));
+  /// gaps.advance_to(6);
+  /// assert_eq!(gaps.next(), Some(Gap::new(Bound::Included(6), Bound::Excluded(8))));
+  /// assert_eq!(gaps.next(), Some(Gap::new(Bound::Excluded(8), Bound::Included(10))));
+  /// assert_eq!(gaps.next(), None);
+  /// ```
+  pub fn advance_to(&mut self, value: T) {
+    let start = Included(value);
+    if let Some(peeked) = self.peeked.take() {
+      if !start_lt_start(peeked.start(), &start) {
+        self.peeked = Some(peeked);
+        return
+      }
+      if start_le_end(&start, peeked.end()) {
+        // The value lies within the peeked gap. Shrink it, but leave
+        // everything past it untouched.
+        self.peeked = Some(Gap::new(start, peeked.into_bounds().1));
+        return
+      }
+    }
+
+    if let Some(iter) = self.iter.as_mut() {
+      if self.start == Unbounded || start_lt_start(&self.start, &start) {
+        self.start = start;
+
+        if !start_le_end(&self.start, &self.end) {
+          self.iter = None;
+        } else if let Some(seek) = self.seek {
+          seek(iter, self.start.clone(), self.end.clone())
+        }
+      }
+    }
+  }
+
+  /// Retrieve the part of the range of interest that has not yet been
+  /// examined, or `None` if iteration has finished.
+  ///
+  /// The returned range can be used to resume iteration at a later
+  /// point, by creating a new [`GapIter`] for it.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use maplit::btreeset;
+  /// # use gaps::Gap;
+  /// # use gaps::RangeGappable as _;
+  ///
+  /// let set = btreeset!{1, 3, 4};
+  /// let mut gaps = set.gaps(0..=6);
+  /// assert_eq!(gaps.next(), Some(Gap::new(Bound::Included(0), Bound::Excluded(1))));
+  ///
+  /// let remainder = gaps.remainder().unwrap();
+  /// assert_eq!(remainder, (Bound::Excluded(1), Bound::Included(6)));
+  /// assert!(set.gaps(remainder).eq(gaps));
+  /// ```
+  pub fn remainder(&self) -> Option<(Bound<T>, Bound<T>)> {
+    match &self.peeked {
+      Some(gap) => Some((gap.start().clone(), self.end.clone())),
+      None => self
+        .iter
+        .as_ref()
+        .map(|_| (self.start.clone(), self.end.clone())),
+    }
+  }
+
+  /// Peek at the next gap without advancing the iterator.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use maplit::btreeset;
+  /// # use gaps::Gap;
+  /// # use gaps::RangeGappable as _;
+  ///
+  /// let set = btreeset!{1, 3};
+  /// let mut gaps = set.gaps(1..=6);
+  /// assert_eq!(gaps.peek_gap(), Some(&Gap::new(Bound::Excluded(1), Bound::Excluded(3))));
+  /// assert_eq!(gaps.next(), Some(Gap::new(Bound::Excluded(1), Bound::Excluded(3))));
+  /// assert_eq!(gaps.peek_gap(), Some(&Gap::new(Bound::Excluded(3), Bound::Included(6))));
+  /// assert_eq!(gaps.next(), Some(Gap::new(Bound::Excluded(3), Bound::Included(6))));
+  /// assert_eq!(gaps.peek_gap(), None);
+  /// ```
+  pub fn peek_gap(&mut self) -> Option<&Gap<T>> {
+    if self.peeked.is_none() {
+      self.peeked = self.next();
+    }
+    self.peeked.as_ref()
+  }
+
+  /// Split the gaps produced by this iterator into chunks of at most
+  /// `max_len` values each.
+  ///
+  /// Gaps with an unbounded start cannot be split and are yielded as
+  /// they are.
+  ///
+  /// # Panics
+  /// This method panics if `max_len` is zero.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use maplit::btreeset;
+  /// # use gaps::Gap;
+  /// # use gaps::RangeGappable as _;
+  ///
+  /// let set = btreeset!{1, 9};
+  /// let mut chunks = set.gaps(0..=9).chunks(3);
+  /// assert_eq!(chunks.next(), Some(Gap::new(Bound::Included(0), Bound::Excluded(1))));
+  /// assert_eq!(chunks.next(), Some(Gap::new(Bound::Excluded(1), Bound::Included(4))));
+  /// assert_eq!(chunks.next(), Some(Gap::new(Bound::Excluded(4), Bound::Included(7))));
+  /// assert_eq!(chunks.next(), Some(Gap::new(Bound::Excluded(7), Bound::Excluded(9))));
+  /// assert_eq!(chunks.next(), None);
+  /// ```
+  pub fn chunks(self, max_len: usize) -> Chunks<Self, T> {
+    Chunks::new(self, max_len)
+  }
+
+  /// Snap the gaps produced by this iterator to multiples of `step`,
+  /// for sequences in which values only occur at such multiples.
+  ///
+  /// A gap is only reported if it contains at least one multiple of
+  /// `step`, and its bounds are set to the first and last such
+  /// multiple, respectively.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use maplit::btreeset;
+  /// # use gaps::Gap;
+  /// # use gaps::RangeGappable as _;
+  ///
+  /// let set = btreeset!{10, 20, 50};
+  /// let mut gaps = set.gaps(0..60).with_step(10);
+  /// assert_eq!(gaps.next(), Some(Gap::new(Bound::Included(0), Bound::Included(0))));
+  /// assert_eq!(gaps.next(), Some(Gap::new(Bound::Included(30), Bound::Included(40))));
+  /// assert_eq!(gaps.next(), None);
+  /// ```
+  pub fn with_step(self, step: T) -> Stepped<Self, T>
+  where
+    T: Multiple,
+  {
+    Stepped::new(self, step)
+  }
+
+  /// Align the gaps produced by this iterator to multiples of `align`,
+  /// rounding starts up and ends down.
+  ///
+  /// Aligned gaps are reported as half-open ranges. Gaps that vanish
+  /// as a result of the alignment are skipped.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use maplit::btreeset;
+  /// # use gaps::Gap;
+  /// # use gaps::RangeGappable as _;
+  ///
+  /// let set = btreeset!{5, 1000, 8199};
+  /// let mut gaps = set.gaps(0..10000).align(4096);
+  /// assert_eq!(gaps.next(), Some(Gap::new(Bound::Included(4096), Bound::Excluded(8192))));
+  /// assert_eq!(gaps.next(), None);
+  /// ```
+  pub fn align(self, align: T) -> Aligned<Self, T>
+  where
+    T: Multiple,
+  {
+    Aligned::new(self, align)
+  }
+
+  /// Merge consecutive gaps produced by this iterator if fewer than `k`
+  /// values lie between them.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use maplit::btreeset;
+  /// # use gaps::Gap;
+  /// # use gaps::RangeGappable as _;
+  ///
+  /// let set = btreeset!{3, 10, 11, 20, 21, 22};
+  /// let mut gaps = set.gaps(0..30).coalesce(3);
+  /// assert_eq!(gaps.next(), Some(Gap::new(Bound::Included(0), Bound::Excluded(20))));
+  /// assert_eq!(gaps.next(), Some(Gap::new(Bound::Excluded(22), Bound::Excluded(30))));
+  /// assert_eq!(gaps.next(), None);
+  /// ```
+  pub fn coalesce(self, k: usize) -> Coalesce<Self, T> {
+    Coalesce::new(self, k)
+  }
+
+  /// Create an iterator over the individual values inside the gaps
+  /// produced by this iterator.
+  ///
+  /// Gaps with an unbounded start are skipped, as their values cannot
+  /// be enumerated.
+  ///
+  /// ```rust
+  /// # use maplit::btreeset;
+  /// # use gaps::RangeGappable as _;
+  ///
+  /// let set = btreeset!{1, 3, 4};
+  /// let values = set.gaps(0..8).values().collect::<Vec<_>>();
+  /// assert_eq!(values, vec![0, 2, 5, 6, 7]);
+  /// ```
+  pub fn values(self) -> Values<Self, T> {
+    Values::new(self)
+  }
+
+  /// Restrict the gaps produced by this iterator to the provided
+  /// range, dropping those that do not overlap with it.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use maplit::btreeset;
+  /// # use gaps::Gap;
+  /// # use gaps::RangeGappable as _;
+  ///
+  /// let set = btreeset!{3, 8, 12};
+  /// let mut gaps = set.gaps(0..20).clip(5..=10);
+  /// assert_eq!(gaps.next(), Some(Gap::new(Bound::Included(5), Bound::Excluded(8))));
+  /// assert_eq!(gaps.next(), Some(Gap::new(Bound::Excluded(8), Bound::Included(10))));
+  /// assert_eq!(gaps.next(), None);
+  /// ```
+  pub fn clip<R>(self, range: R) -> Clip<Self, T>
+  where
+    R: RangeBounds<T>,
+  {
+    let (start, end) = bounds(&range);
+    Clip::new(self, start, end)
+  }
+
+  /// Create an iterator yielding only the ranges that are gaps in both
+  /// this iterator and `other`.
+  ///
+  /// `other` may be any iterator over gaps in ascending order, such as
+  /// another [`GapIter`]. Reported ranges are maximal, i.e., no two of
+  /// them are adjacent to each other.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use maplit::btreeset;
+  /// # use gaps::Gap;
+  /// # use gaps::RangeGappable as _;
+  ///
+  /// let replica1 = btreeset!{1, 2, 6};
+  /// let replica2 = btreeset!{2, 3, 4};
+  /// let mut gaps = replica1.gaps(0..8).intersect(replica2.gaps(0..8));
+  /// assert_eq!(gaps.next(), Some(Gap::new(Bound::Included(0), Bound::Excluded(1))));
+  /// assert_eq!(gaps.next(), Some(Gap::new(Bound::Excluded(4), Bound::Excluded(6))));
+  /// assert_eq!(gaps.next(), Some(Gap::new(Bound::Excluded(6), Bound::Excluded(8))));
+  /// assert_eq!(gaps.next(), None);
+  /// ```
+  pub fn intersect<J>(self, other: J) -> Intersection<Self, J::IntoIter, T>
+  where
+    J: IntoIterator<Item = Gap<T>>,
+  {
+    Intersection::new(self, other.into_iter())
+  }
+
+  /// Create an iterator yielding the ranges that are gaps in this
+  /// iterator, `other`, or both.
+  ///
+  /// `other` may be any iterator over gaps in ascending order, such as
+  /// another [`GapIter`]. Overlapping and adjacent gaps are merged, so
+  /// that reported ranges are maximal.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use maplit::btreeset;
+  /// # use gaps::Gap;
+  /// # use gaps::RangeGappable as _;
+  ///
+  /// let replica1 = btreeset!{1, 2, 6};
+  /// let replica2 = btreeset!{2, 3, 4};
+  /// let mut gaps = replica1.gaps(0..8).union(replica2.gaps(0..8));
+  /// assert_eq!(gaps.next(), Some(Gap::new(Bound::Included(0), Bound::Excluded(2))));
+  /// assert_eq!(gaps.next(), Some(Gap::new(Bound::Excluded(2), Bound::Excluded(8))));
+  /// assert_eq!(gaps.next(), None);
+  /// ```
+  pub fn union<J>(self, other: J) -> Union<Self, J::IntoIter, T>
+  where
+    J: IntoIterator<Item = Gap<T>>,
+  {
+    Union::new(self, other.into_iter())
+  }
+
+  /// Create an iterator yielding the ranges that are gaps in this
+  /// iterator but not in `other`, i.e., that are covered by the source
+  /// of `other`.
+  ///
+  /// `other` may be any iterator over gaps in ascending order, such as
+  /// another [`GapIter`].
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use maplit::btreeset;
+  /// # use gaps::Gap;
+  /// # use gaps::RangeGappable as _;
+  ///
+  /// let before = btreeset!{1, 2, 6};
+  /// let after = btreeset!{1, 2, 3, 4, 6};
+  /// let mut filled = before.gaps(0..8).difference(after.gaps(0..8));
+  /// assert_eq!(filled.next(), Some(Gap::new(Bound::Excluded(2), Bound::Included(4))));
+  /// assert_eq!(filled.next(), None);
+  /// ```
+  pub fn difference<J>(self, other: J) -> Difference<Self, J::IntoIter, T>
+  where
+    J: IntoIterator<Item = Gap<T>>,
+  {
+    Difference::new(self, other.into_iter())
+  }
+
+  /// Create an iterator yielding the ranges that are gaps in either
+  /// this iterator or `other`, but not in both.
+  ///
+  /// `other` may be any iterator over gaps in ascending order, such as
+  /// another [`GapIter`]. Adjacent ranges are merged, so that reported
+  /// ranges are maximal.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use maplit::btreeset;
+  /// # use gaps::Gap;
+  /// # use gaps::RangeGappable as _;
+  ///
+  /// let replica1 = btreeset!{1, 2, 6};
+  /// let replica2 = btreeset!{2, 3, 4};
+  /// let mut gaps = replica1.gaps(0..8).symmetric_difference(replica2.gaps(0..8));
+  /// assert_eq!(gaps.next(), Some(Gap::new(Bound::Included(1), Bound::Excluded(2))));
+  /// assert_eq!(gaps.next(), Some(Gap::new(Bound::Excluded(2), Bound::Included(4))));
+  /// assert_eq!(gaps.next(), Some(Gap::new(Bound::Included(6), Bound::Included(6))));
+  /// assert_eq!(gaps.next(), None);
+  /// ```
+  pub fn symmetric_difference<J>(self, other: J) -> SymmetricDifference<Self, J::IntoIter, T>
+  where
+    J: IntoIterator<Item = Gap<T>>,
+  {
+    SymmetricDifference::new(self, other.into_iter())
+  }
+
+  /// Destructure the iterator into the wrapped iterator (if iteration
+  /// has not yet finished) and the remaining range.
+  pub(crate) fn into_parts(self) -> (Option<I>, Bound<T>, Bound<T>) {
+    (self.iter, self.start, self.end)
+  }
+
+  /// Advance the iterator, producing the next gap.
+  ///
+  /// If `strict` is `true`, a non-ascending sequence of elements will
+  /// be reported as an error and iteration stops. Otherwise it is
+  /// merely flagged by a debug assertion.
+  #[allow(clippy::type_complexity)]
+  fn next_checked(&mut self, strict: bool) -> Option<Result<Gap<T>, GapError<T>>> {
+    loop {
+      match self.iter.as_mut() {
+        Some(iter) => {
+          if let Some(this) = iter.next() {
+            // Keeping track of the last element requires cloning each
+            // one, so we only do so when needed.
+            if strict || cfg!(debug_assertions) {
+              if let Err(err) = check_order(&mut self.last, &this) {
+                if strict {
+                  self.iter = None;
+                  break Some(Err(err))
+                }
+                debug_assert!(false, "sequence is not ascending");
+              }
+            }
+
+            let (gap, done) = step(&mut self.start, &self.end, this);
+            if done {
+              self.iter = None;
+            }
+            if let Some((start, end)) = gap {
+              break Some(Ok(Gap::new(start, end)))
+            }
+          } else {
+            // The iterator is out of items and we are done.
+            self.iter = None;
+            if start_le_end(&self.start, &self.end) {
+              break Some(Ok(Gap::new(self.start.clone(), self.end.clone())))
+            }
+          }
+        },
+        None => break None,
+      }
+    }
+  }
+}
+
+impl<I, T> Iterator for GapIter<I, T>
+where
+  I: Iterator<Item = T>,
+  T: Clone + Ord + Inc,
+{
+  type Item = Gap<T>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    if let Some(gap) = self.peeked.take() {
+      return Some(gap)
+    }
+
+    self.next_checked(false).map(|result| match result {
+      Ok(gap) => gap,
+      Err(..) => unreachable!(),
+    })
+  }
+
+  fn fold<B, F>(self, init: B, mut f: F) -> B
+  where
+    F: FnMut(B, Self::Item) -> B,
+  {
+    let Self {
+      iter,
+      mut start,
+      end,
+      mut last,
+      peeked,
+      ..
+    } = self;
+
+    let init = match peeked {
+      Some(gap) => f(init, gap),
+      None => init,
+    };
+
+    let flow = match iter {
+      Some(mut iter) => iter.try_fold(init, |acc, this| {
+        if cfg!(debug_assertions) && check_order(&mut last, &this).is_err() {
+          debug_assert!(false, "sequence is not ascending");
+        }
+
+        let (gap, done) = step(&mut start, &end, this);
+        let acc = match gap {
+          Some((start, end)) => f(acc, Gap::new(start, end)),
+          None => acc,
+        };
+
+        if done {
+          ControlFlow::Break(acc)
+        } else {
+          ControlFlow::Continue(acc)
+        }
+      }),
+      None => return init,
+    };
+
+    match flow {
+      // The iterator is out of items and we may have a trailing gap.
+      ControlFlow::Continue(acc) if start_le_end(&start, &end) => f(acc, Gap::new(start, end)),
+      ControlFlow::Continue(acc) | ControlFlow::Break(acc) => acc,
     }
   }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    let peeked = self.peeked.is_some() as usize;
+    let (lower, upper) = match self.iter.as_ref() {
+      Some(iter) => match iter.size_hint() {
+        // If the wrapped iterator is known to be empty then we will
+        // produce at most the one gap covering the remaining range.
+        (_, Some(0)) => {
+          let count = start_le_end(&self.start, &self.end) as usize;
+          (count, Some(count))
+        },
+        // Each element produced by the wrapped iterator can at most
+        // terminate one gap, with one more gap possibly trailing the
+        // last element. Elements may very well be adjacent to each
+        // other or lie outside of the range of interest, though, so we
+        // can't provide a lower bound.
+        (_, upper) => (0, upper.and_then(|upper| upper.checked_add(1))),
+      },
+      None => (0, Some(0)),
+    };
+
+    (
+      lower + peeked,
+      upper.and_then(|upper| upper.checked_add(peeked)),
+    )
+  }
+}
+
+impl<I, T> FusedIterator for GapIter<I, T>
+where
+  I: Iterator<Item = T>,
+  T: Clone + Ord + Inc,
+{
+}
+
+
+/// An iterator over the gaps in a sequence represented by an iterator,
+/// reporting a sequence that is not in ascending order as an error.
+///
+/// Objects of this type are created by [`Gappable::try_gaps`].
+#[derive(Clone, Debug)]
+pub struct TryGapIter<I, T>(GapIter<I, T>);
+
+impl<I, T> Iterator for TryGapIter<I, T>
+where
+  I: Iterator<Item = T>,
+  T: Clone + Ord + Inc,
+{
+  type Item = Result<Gap<T>, GapError<T>>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.0.next_checked(true)
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.0.size_hint()
+  }
+}
+
+impl<I, T> FusedIterator for TryGapIter<I, T>
+where
+  I: Iterator<Item = T>,
+  T: Clone + Ord + Inc,
+{
+}
+
+
+/// An extension trait that provides range based access to the "gaps"
+/// between ordered elements yielded by an iterator.
+///
+/// E.g., given an ordered set {1, 3, 4}, the gaps in the range
+/// `0..=6` would be: `[0..1, 2..3, 5..=6]`.
+///
+/// ```rust
+/// use std::ops::Bound;
+/// # use gaps::Gap;
+/// # use gaps::Gappable as _;
+///
+/// let vec = vec![1, 3, 4];
+/// let mut gaps = vec.iter().copied().gaps(0..=6);
+/// assert_eq!(gaps.next(), Some(Gap::new(Bound::Included(0), Bound::Excluded(1))));
+/// assert_eq!(gaps.next(), Some(Gap::new(Bound::Excluded(1), Bound::Excluded(3))));
+/// assert_eq!(gaps.next(), Some(Gap::new(Bound::Excluded(4), Bound::Included(6))));
+/// assert_eq!(gaps.next(), None);
+/// ```
+pub trait Gappable<I, T> {
+  /// Create a new [`GapIter`] that yields ranges identifying the gaps
+  /// in a certain range of a collection.
+  fn gaps<R>(self, range: R) -> GapIter<I, T>
+  where
+    R: RangeBounds<T>;
+
+  /// Create a new [`TryGapIter`] that yields ranges identifying the
+  /// gaps in a certain range of a collection, reporting an error if
+  /// elements are not in ascending order.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use gaps::Gap;
+  /// # use gaps::GapError;
+  /// # use gaps::Gappable as _;
+  ///
+  /// let vec = vec![1, 4, 3];
+  /// let mut gaps = vec.iter().copied().try_gaps(0..=6);
+  /// assert_eq!(gaps.next(), Some(Ok(Gap::new(Bound::Included(0), Bound::Excluded(1)))));
+  /// assert_eq!(gaps.next(), Some(Ok(Gap::new(Bound::Excluded(1), Bound::Excluded(4)))));
+  /// assert_eq!(gaps.next(), Some(Err(GapError::Unsorted { prev: 4, next: 3 })));
+  /// assert_eq!(gaps.next(), None);
+  /// ```
+  fn try_gaps<R>(self, range: R) -> TryGapIter<I, T>
+  where
+    R: RangeBounds<T>;
+
+  /// Create a new [`GapIter`] that yields ranges identifying the gaps
+  /// in the full [`Domain`] of the element type.
+  ///
+  /// In contrast to using an unbounded range, all reported gaps have
+  /// concrete bounds.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use gaps::Gap;
+  /// # use gaps::Gappable as _;
+  ///
+  /// let vec = vec![0u8, 1, 200];
+  /// let mut gaps = vec.iter().copied().gaps_full();
+  /// assert_eq!(gaps.next(), Some(Gap::new(Bound::Excluded(1), Bound::Excluded(200))));
+  /// assert_eq!(gaps.next(), Some(Gap::new(Bound::Excluded(200), Bound::Included(u8::MAX))));
+  /// assert_eq!(gaps.next(), None);
+  /// ```
+  fn gaps_full(self) -> GapIter<I, T>
+  where
+    Self: Sized,
+    I: Iterator<Item = T>,
+    T: Clone + Ord + Inc + Domain,
+  {
+    self.gaps(T::MIN..=T::MAX)
+  }
+
+  /// Create a new [`GapIter`] that yields ranges identifying the gaps
+  /// in a certain range of a collection whose elements are not
+  /// necessarily ordered.
+  ///
+  /// # Notes
+  /// - all elements are collected, sorted, and deduplicated upfront,
+  ///   resulting in `O(n log n)` time and `O(n)` memory cost
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use gaps::Gap;
+  /// # use gaps::Gappable as _;
+  ///
+  /// let vec = vec![4, 1, 4, 3];
+  /// let mut gaps = vec.iter().gaps_sorted(0..=6);
+  /// assert_eq!(gaps.next(), Some(Gap::new(Bound::Included(0), Bound::Excluded(1))));
+  /// assert_eq!(gaps.next(), Some(Gap::new(Bound::Excluded(1), Bound::Excluded(3))));
+  /// assert_eq!(gaps.next(), Some(Gap::new(Bound::Excluded(4), Bound::Included(6))));
+  /// assert_eq!(gaps.next(), None);
+  /// ```
+  fn gaps_sorted<R>(self, range: R) -> GapIter<vec::IntoIter<T>, T>
+  where
+    Self: Sized,
+    R: RangeBounds<T>,
+    I: Iterator<Item = T>,
+    T: Clone + Ord + Inc,
+  {
+    let (iter, start, end) = self.gaps(range).into_parts();
+    let iter = iter.map(|iter| {
+      let mut values = iter.collect::<Vec<_>>();
+      values.sort_unstable();
+      values.dedup();
+      values.into_iter()
+    });
+    GapIter::from_parts(iter, start, end)
+  }
+
+  /// Create a new [`LenientGapIter`] that yields ranges identifying
+  /// the gaps in a certain range of a collection, skipping over
+  /// elements that are less than a preceding one instead of treating
+  /// them as an error.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use gaps::Gap;
+  /// # use gaps::Gappable as _;
+  ///
+  /// let vec = vec![1, 4, 2, 5];
+  /// let mut gaps = vec.iter().gaps_lenient(1..=5);
+  /// assert_eq!(gaps.next(), Some(Gap::new(Bound::Excluded(1), Bound::Excluded(4))));
+  /// assert_eq!(gaps.next(), None);
+  /// assert_eq!(gaps.skipped(), 1);
+  /// ```
+  fn gaps_lenient<R>(self, range: R) -> LenientGapIter<I, T>
+  where
+    Self: Sized,
+    R: RangeBounds<T>,
+    I: Iterator<Item = T>,
+    T: Clone + Ord + Inc,
+  {
+    let (iter, start, end) = self.gaps(range).into_parts();
+    LenientGapIter::from_parts(iter, start, end)
+  }
+
+  /// Find the first gap in a certain range of a collection, if any.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use gaps::Gap;
+  /// # use gaps::Gappable as _;
+  ///
+  /// let vec = vec![1, 2, 4];
+  /// let gap = vec.iter().copied().first_gap(1..);
+  /// assert_eq!(gap, Some(Gap::new(Bound::Excluded(2), Bound::Excluded(4))));
+  /// ```
+  fn first_gap<R>(self, range: R) -> Option<Gap<T>>
+  where
+    Self: Sized,
+    R: RangeBounds<T>,
+    I: Iterator<Item = T>,
+    T: Clone + Ord + Inc,
+  {
+    self.gaps(range).next()
+  }
+
+  /// Check whether there is any gap in a certain range of a
+  /// collection.
+  ///
+  /// ```rust
+  /// # use gaps::Gappable as _;
+  ///
+  /// let vec = vec![1, 2, 4];
+  /// assert!(vec.iter().copied().has_gaps(1..=4));
+  /// assert!(!vec.iter().copied().has_gaps(1..=2));
+  /// ```
+  fn has_gaps<R>(self, range: R) -> bool
+  where
+    Self: Sized,
+    R: RangeBounds<T>,
+    I: Iterator<Item = T>,
+    T: Clone + Ord + Inc,
+  {
+    self.first_gap(range).is_some()
+  }
+
+  /// Find the smallest value missing in a certain range of a
+  /// collection.
+  ///
+  /// `None` is returned if no value is missing or if the first gap has
+  /// an unbounded start, in which case there is no way of knowing the
+  /// smallest missing value.
+  ///
+  /// ```rust
+  /// # use gaps::Gappable as _;
+  ///
+  /// let vec = vec![1, 2, 4];
+  /// assert_eq!(vec.iter().copied().next_free(1..), Some(3));
+  /// ```
+  fn next_free<R>(self, range: R) -> Option<T>
+  where
+    Self: Sized,
+    R: RangeBounds<T>,
+    I: Iterator<Item = T>,
+    T: Clone + Ord + Inc,
+  {
+    first_value(self.first_gap(range)?)
+  }
+
+  /// Find the largest gap in a certain range of a collection, if any.
+  ///
+  /// Gaps with an unbounded start or end are considered larger than
+  /// any other gap. If there are multiple largest gaps, the first one
+  /// is reported.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use gaps::Gap;
+  /// # use gaps::Gappable as _;
+  ///
+  /// let vec = vec![2, 3, 7, 9];
+  /// let gap = vec.iter().copied().largest_gap(0..=10);
+  /// assert_eq!(gap, Some(Gap::new(Bound::Excluded(3), Bound::Excluded(7))));
+  /// ```
+  fn largest_gap<R>(self, range: R) -> Option<Gap<T>>
+  where
+    Self: Sized,
+    R: RangeBounds<T>,
+    I: Iterator<Item = T>,
+    T: Clone + Ord + Distance,
+  {
+    largest(self.gaps(range))
+  }
+
+  /// Find the `k` largest gaps in a certain range of a collection,
+  /// largest first.
+  ///
+  /// Gaps are ordered as described for [`Gappable::largest_gap`], with
+  /// gaps of the same length being reported in ascending order. Only
+  /// up to `k` gaps are kept in memory at any point in time.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use gaps::Gap;
+  /// # use gaps::Gappable as _;
+  ///
+  /// let vec = vec![2, 3, 7, 9];
+  /// let gaps = vec.iter().copied().k_largest_gaps(0..=10, 2);
+  /// assert_eq!(gaps, vec![
+  ///   Gap::new(Bound::Excluded(3), Bound::Excluded(7)),
+  ///   Gap::new(Bound::Included(0), Bound::Excluded(2)),
+  /// ]);
+  /// ```
+  fn k_largest_gaps<R>(self, range: R, k: usize) -> Vec<Gap<T>>
+  where
+    Self: Sized,
+    R: RangeBounds<T>,
+    I: Iterator<Item = T>,
+    T: Clone + Ord + Distance,
+  {
+    k_largest(self.gaps(range), k)
+  }
+
+  /// Count the number of values missing in a certain range of a
+  /// collection.
+  ///
+  /// `None` is returned if the range is unbounded (and not fully
+  /// covered) or if the count can not be represented as `usize`.
+  ///
+  /// ```rust
+  /// # use gaps::Gappable as _;
+  ///
+  /// let vec = vec![2, 3, 7, 9];
+  /// assert_eq!(vec.iter().copied().missing_count(0..=10), Some(7));
+  /// ```
+  fn missing_count<R>(self, range: R) -> Option<usize>
+  where
+    Self: Sized,
+    R: RangeBounds<T>,
+    I: Iterator<Item = T>,
+    T: Clone + Ord + Distance,
+  {
+    count(self.gaps(range))
+  }
+
+  /// Gather statistics about the gaps in a certain range of a
+  /// collection, in a single pass.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use gaps::Gap;
+  /// # use gaps::Gappable as _;
+  ///
+  /// let vec = vec![2, 3, 7, 9];
+  /// let stats = vec.iter().copied().stats(0..=10);
+  /// assert_eq!(stats.count, 4);
+  /// assert_eq!(stats.missing, Some(7));
+  /// assert_eq!(stats.min_len, Some(1));
+  /// assert_eq!(stats.max_len, Some(3));
+  /// assert_eq!(stats.mean_len, Some(1.75));
+  /// assert_eq!(stats.largest, Some(Gap::new(Bound::Excluded(3), Bound::Excluded(7))));
+  /// ```
+  fn stats<R>(self, range: R) -> GapStats<T>
+  where
+    Self: Sized,
+    R: RangeBounds<T>,
+    I: Iterator<Item = T>,
+    T: Clone + Ord + Distance,
+  {
+    stats(self.gaps(range))
+  }
+
+  /// Calculate the fraction of values present in a certain range of a
+  /// collection, with `1.0` meaning that there are no gaps.
+  ///
+  /// `None` is returned if the range is unbounded or the number of
+  /// values in it can not be represented as `usize`.
+  ///
+  /// ```rust
+  /// # use gaps::Gappable as _;
+  ///
+  /// let vec = vec![1, 2, 3];
+  /// assert_eq!(vec.iter().copied().coverage(0..4), Some(0.75));
+  /// ```
+  fn coverage<R>(self, range: R) -> Option<f64>
+  where
+    Self: Sized,
+    R: RangeBounds<T>,
+    I: Iterator<Item = T>,
+    T: Clone + Ord + Distance,
+  {
+    let (start, end) = bounds(&range);
+    coverage(&start, &end, self.gaps(range))
+  }
+
+  /// Find a gap of at least `len` values in a certain range of a
+  /// collection, using the provided [`Fit`] strategy.
+  ///
+  /// The part of the gap covering its first `len` values is reported.
+  /// Gaps with an unbounded start are considered large enough but are
+  /// reported in their entirety. Ties between equally sized gaps are
+  /// broken in favor of the first one.
+  ///
+  /// # Panics
+  /// This method panics if `len` is zero.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use gaps::Fit;
+  /// # use gaps::Gap;
+  /// # use gaps::Gappable as _;
+  ///
+  /// let vec = vec![3, 5, 10, 13];
+  /// let gap = vec.iter().copied().find_gap(0..=20, 2, Fit::Best);
+  /// assert_eq!(gap, Some(Gap::new(Bound::Excluded(10), Bound::Excluded(13))));
+  /// ```
+  fn find_gap<R>(self, range: R, len: usize, fit: Fit) -> Option<Gap<T>>
+  where
+    Self: Sized,
+    R: RangeBounds<T>,
+    I: Iterator<Item = T>,
+    T: Clone + Ord + Step,
+  {
+    find(self.gaps(range), len, fit)
+  }
+
+  /// Create a new iterator yielding the gaps in a certain range of a
+  /// collection, snapped to multiples of `step`.
+  ///
+  /// Please refer to [`GapIter::with_step`] for details.
+  fn gaps_with_step<R>(self, range: R, step: T) -> Stepped<GapIter<I, T>, T>
+  where
+    Self: Sized,
+    R: RangeBounds<T>,
+    I: Iterator<Item = T>,
+    T: Clone + Ord + Inc + Multiple,
+  {
+    self.gaps(range).with_step(step)
+  }
+
+  /// Create a new iterator yielding the individual values missing in a
+  /// certain range of a collection.
+  ///
+  /// Please refer to [`GapIter::values`] for details.
+  fn missing<R>(self, range: R) -> Values<GapIter<I, T>, T>
+  where
+    Self: Sized,
+    R: RangeBounds<T>,
+    I: Iterator<Item = T>,
+    T: Clone + Ord + Inc,
+  {
+    self.gaps(range).values()
+  }
+
+  /// Create a new [`RunIter`] that yields ranges identifying the runs
+  /// of contiguous elements in a certain range of a collection.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use gaps::Gap;
+  /// # use gaps::Gappable as _;
+  ///
+  /// let vec = vec![1, 3, 4, 5, 8];
+  /// let mut runs = vec.iter().copied().runs(0..=6);
+  /// assert_eq!(runs.next(), Some(Gap::new(Bound::Included(1), Bound::Included(1))));
+  /// assert_eq!(runs.next(), Some(Gap::new(Bound::Included(3), Bound::Included(5))));
+  /// assert_eq!(runs.next(), None);
+  /// ```
+  fn runs<R>(self, range: R) -> RunIter<I, T>
+  where
+    Self: Sized,
+    R: RangeBounds<T>,
+    I: Iterator<Item = T>,
+    T: Clone + Ord + Inc,
+  {
+    let (iter, start, end) = self.gaps(range).into_parts();
+    RunIter::from_parts(iter, start, end)
+  }
+
+  /// Create a new [`SegmentIter`] that yields the alternating runs of
+  /// present and missing values in a certain range of a collection.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use gaps::Gap;
+  /// # use gaps::Gappable as _;
+  /// # use gaps::Segment;
+  ///
+  /// let vec = vec![1, 2, 5];
+  /// let mut segments = vec.iter().copied().segments(0..=5);
+  /// assert_eq!(segments.next(), Some(Segment::Missing(Gap::new(Bound::Included(0), Bound::Excluded(1)))));
+  /// assert_eq!(segments.next(), Some(Segment::Present(Gap::new(Bound::Included(1), Bound::Included(2)))));
+  /// assert_eq!(segments.next(), Some(Segment::Missing(Gap::new(Bound::Excluded(2), Bound::Excluded(5)))));
+  /// assert_eq!(segments.next(), Some(Segment::Present(Gap::new(Bound::Included(5), Bound::Included(5)))));
+  /// assert_eq!(segments.next(), None);
+  /// ```
+  fn segments<R>(self, range: R) -> SegmentIter<I, T>
+  where
+    Self: Sized,
+    R: RangeBounds<T>,
+    I: Iterator<Item = T>,
+    T: Clone + Ord + Inc,
+  {
+    let (iter, start, end) = self.gaps(range).into_parts();
+    SegmentIter::from_parts(iter, start, end)
+  }
 }
 
-impl<I, T> Iterator for GapIter<I, T>
+impl<I, T> Gappable<I, T> for I
 where
   I: Iterator<Item = T>,
-  T: Copy + Ord + Inc,
+  T: Clone + Ord + Inc,
 {
-  type Item = (Bound<T>, Bound<T>);
-
-  fn next(&mut self) -> Option<Self::Item> {
-    loop {
-      match self.iter.as_mut() {
-        Some(iter) => {
-          let (start, end) = if let Some(this) = iter.next() {
-            #[cfg(debug_assertions)]
-            {
-              debug_assert!(
-                self.last.unwrap_or(this) <= this,
-                "sequence is not ascending"
-              );
-              self.last = Some(this);
-            }
-
-            let end = Excluded(this);
-            if self.start != Unbounded && start_le_start(&Included(this), &self.start) {
-              // As long as our current element is still less than or
-              // even equal to the actual start of the range that we
-              // consider, we just continue.
-              if !start_lt_start(&Included(this), &self.start) {
-                // But if it is equal to the start bound then we adjust
-                // the start bound to exclude this element.
-                self.start = end;
-              }
-              continue
-            }
+  fn gaps<R>(self, range: R) -> GapIter<I, T>
+  where
+    R: RangeBounds<T>,
+  {
+    let (start, end) = bounds(&range);
+    GapIter::new(self, start, end)
+  }
 
-            let start = self.start;
-            self.start = end;
+  fn try_gaps<R>(self, range: R) -> TryGapIter<I, T>
+  where
+    R: RangeBounds<T>,
+  {
+    TryGapIter(self.gaps(range))
+  }
+}
 
-            if !end_lt_end(&end, &self.end) {
-              // Once we see an element being produced that is at or
-              // past our overarching range's end, we are done.
-              self.iter = None;
-              (start, self.end)
-            } else {
-              if !start_le_end(&self.start, &self.end) {
-                // If our start has caught up with our end, we are done.
-                self.iter = None;
-              }
-              (start, end)
-            }
-          } else {
-            // The iterator is out of items and we are done.
-            self.iter = None;
-            (self.start, self.end)
-          };
+/// Iterators over references are supported directly, without the need
+/// for [`Iterator::copied`] or [`Iterator::cloned`].
+///
+/// ```rust
+/// use std::ops::Bound;
+/// # use gaps::Gap;
+/// # use gaps::Gappable as _;
+///
+/// let vec = vec![1, 3];
+/// let mut gaps = vec.iter().gaps(0..4);
+/// assert_eq!(gaps.next(), Some(Gap::new(Bound::Included(0), Bound::Excluded(1))));
+/// assert_eq!(gaps.next(), Some(Gap::new(Bound::Excluded(1), Bound::Excluded(3))));
+/// assert_eq!(gaps.next(), None);
+/// ```
+impl<'t, I, T> Gappable<Cloned<I>, T> for I
+where
+  I: Iterator<Item = &'t T>,
+  T: Clone + Ord + Inc + 't,
+{
+  fn gaps<R>(self, range: R) -> GapIter<Cloned<I>, T>
+  where
+    R: RangeBounds<T>,
+  {
+    self.cloned().gaps(range)
+  }
 
-          // We could still end up with a range that is empty (or even
-          // descending). Don't report those.
-          if start_le_end(&start, &end) {
-            break Some((start, end))
-          }
-        },
-        None => break None,
-      }
-    }
+  fn try_gaps<R>(self, range: R) -> TryGapIter<Cloned<I>, T>
+  where
+    R: RangeBounds<T>,
+  {
+    self.cloned().try_gaps(range)
   }
 }
 
 
 /// An extension trait that provides range based access to the "gaps"
-/// between ordered elements yielded by an iterator.
-///
-/// E.g., given an ordered set {1, 3, 4}, the gaps in the range
-/// `0..=6` would be: `[0..1, 2..3, 5..=6]`.
+/// between ordered keys extracted from the items yielded by an
+/// iterator.
 ///
 /// ```rust
 /// use std::ops::Bound;
-/// # use gaps::Gappable as _;
+/// # use gaps::Gap;
+/// # use gaps::GappableByKey as _;
 ///
-/// let vec = vec![1, 3, 4];
-/// let mut gaps = vec.iter().copied().gaps(0..=6);
-/// assert_eq!(gaps.next(), Some((Bound::Included(0), Bound::Excluded(1))));
-/// assert_eq!(gaps.next(), Some((Bound::Excluded(1), Bound::Excluded(3))));
-/// assert_eq!(gaps.next(), Some((Bound::Excluded(4), Bound::Included(6))));
+/// struct Record {
+///   seq: u64,
+/// }
+///
+/// let records = vec![Record { seq: 1 }, Record { seq: 2 }, Record { seq: 5 }];
+/// let mut gaps = records.iter().gaps_by_key(1..=6, |record| record.seq);
+/// assert_eq!(gaps.next(), Some(Gap::new(Bound::Excluded(2), Bound::Excluded(5))));
+/// assert_eq!(gaps.next(), Some(Gap::new(Bound::Excluded(5), Bound::Included(6))));
 /// assert_eq!(gaps.next(), None);
 /// ```
-pub trait Gappable<I, T> {
+pub trait GappableByKey<I>
+where
+  I: Iterator,
+{
   /// Create a new [`GapIter`] that yields ranges identifying the gaps
-  /// in a certain range of a collection.
-  fn gaps<R>(self, range: R) -> GapIter<I, T>
+  /// between the keys extracted from the iterator's items using `key`.
+  ///
+  /// # Notes
+  /// - the extracted keys are assumed to be in ascending order
+  fn gaps_by_key<R, F, T>(self, range: R, key: F) -> GapIter<Map<I, F>, T>
   where
-    R: RangeBounds<T>;
+    R: RangeBounds<T>,
+    F: FnMut(I::Item) -> T,
+    T: Clone + Ord + Inc;
+
+  /// Create a new [`GroupedGapIter`] that yields ranges identifying the
+  /// gaps between values in a certain range, separately for each group
+  /// of values.
+  ///
+  /// `key` maps each item to its group and value. Items of different
+  /// groups may be interleaved arbitrarily, but values are assumed to be
+  /// in ascending order within their group. Values not ascending are
+  /// ignored. Gaps following the last value of each group are reported
+  /// once the iterator is exhausted, in ascending order of groups.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use gaps::Gap;
+  /// # use gaps::GappableByKey as _;
+  ///
+  /// let offsets = vec![("p0", 0), ("p1", 0), ("p0", 1), ("p1", 3), ("p0", 4)];
+  /// let mut gaps = offsets.into_iter().gaps_grouped_by(0..=4, |item| item);
+  /// assert_eq!(gaps.next(), Some(("p1", Gap::new(Bound::Excluded(0), Bound::Excluded(3)))));
+  /// assert_eq!(gaps.next(), Some(("p0", Gap::new(Bound::Excluded(1), Bound::Excluded(4)))));
+  /// assert_eq!(gaps.next(), Some(("p1", Gap::new(Bound::Excluded(3), Bound::Included(4)))));
+  /// assert_eq!(gaps.next(), None);
+  /// ```
+  fn gaps_grouped_by<R, F, G, T>(self, range: R, key: F) -> GroupedGapIter<I, F, G, T>
+  where
+    R: RangeBounds<T>,
+    F: FnMut(I::Item) -> (G, T),
+    G: Clone + Ord,
+    T: Clone + Ord + Inc;
 }
 
-impl<I, T> Gappable<I, T> for I
+impl<I> GappableByKey<I> for I
 where
-  I: Iterator<Item = T>,
-  T: Copy + Ord + Inc,
+  I: Iterator,
 {
-  fn gaps<R>(self, range: R) -> GapIter<I, T>
+  fn gaps_by_key<R, F, T>(self, range: R, key: F) -> GapIter<Map<I, F>, T>
+  where
+    R: RangeBounds<T>,
+    F: FnMut(I::Item) -> T,
+    T: Clone + Ord + Inc,
+  {
+    self.map(key).gaps(range)
+  }
+
+  fn gaps_grouped_by<R, F, G, T>(self, range: R, key: F) -> GroupedGapIter<I, F, G, T>
   where
     R: RangeBounds<T>,
+    F: FnMut(I::Item) -> (G, T),
+    G: Clone + Ord,
+    T: Clone + Ord + Inc,
   {
     let (start, end) = bounds(&range);
-    GapIter::new(self, start, end)
+    GroupedGapIter::new(self, key, start, end)
   }
 }
 
@@ -170,18 +1356,20 @@ where
 /// collections with a `range` method.
 ///
 /// `BTreeSet` and `BTreeMap` are the two most prominent examples of
-/// such collections.
+/// such collections. A `VecDeque` sorted in ascending order is
+/// supported as well.
 ///
 /// ```rust
 /// use std::ops::Bound;
 /// # use maplit::btreeset;
+/// # use gaps::Gap;
 /// # use gaps::RangeGappable as _;
 ///
 /// let set = btreeset!{1, 3, 4};
 /// let mut gaps = set.gaps(0..=6);
-/// assert_eq!(gaps.next(), Some((Bound::Included(0), Bound::Excluded(1))));
-/// assert_eq!(gaps.next(), Some((Bound::Excluded(1), Bound::Excluded(3))));
-/// assert_eq!(gaps.next(), Some((Bound::Excluded(4), Bound::Included(6))));
+/// assert_eq!(gaps.next(), Some(Gap::new(Bound::Included(0), Bound::Excluded(1))));
+/// assert_eq!(gaps.next(), Some(Gap::new(Bound::Excluded(1), Bound::Excluded(3))));
+/// assert_eq!(gaps.next(), Some(Gap::new(Bound::Excluded(4), Bound::Included(6))));
 /// assert_eq!(gaps.next(), None);
 /// ```
 pub trait RangeGappable<'s, T> {
@@ -193,47 +1381,465 @@ pub trait RangeGappable<'s, T> {
   fn gaps<R>(&'s self, range: R) -> GapIter<Self::Iter, T>
   where
     R: RangeBounds<T>;
+
+  /// Create a new [`GapIter`] that yields ranges identifying the gaps
+  /// in the full [`Domain`] of the element type.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use maplit::btreeset;
+  /// # use gaps::Gap;
+  /// # use gaps::RangeGappable as _;
+  ///
+  /// let set = btreeset!{0u16, 7};
+  /// let mut gaps = set.gaps_full();
+  /// assert_eq!(gaps.next(), Some(Gap::new(Bound::Excluded(0), Bound::Excluded(7))));
+  /// assert_eq!(gaps.next(), Some(Gap::new(Bound::Excluded(7), Bound::Included(u16::MAX))));
+  /// assert_eq!(gaps.next(), None);
+  /// ```
+  fn gaps_full(&'s self) -> GapIter<Self::Iter, T>
+  where
+    T: Domain,
+  {
+    self.gaps(T::MIN..=T::MAX)
+  }
+
+  /// Find the first gap in a certain range of a collection, if any.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use maplit::btreeset;
+  /// # use gaps::Gap;
+  /// # use gaps::RangeGappable as _;
+  ///
+  /// let set = btreeset!{1, 2, 4};
+  /// assert_eq!(set.first_gap(1..), Some(Gap::new(Bound::Excluded(2), Bound::Excluded(4))));
+  /// assert_eq!(set.first_gap(1..=2), None);
+  /// ```
+  fn first_gap<R>(&'s self, range: R) -> Option<Gap<T>>
+  where
+    R: RangeBounds<T>,
+    Self::Iter: Iterator<Item = T>,
+    T: Clone + Ord + Inc,
+  {
+    self.gaps(range).next()
+  }
+
+  /// Check whether there is any gap in a certain range of a
+  /// collection.
+  ///
+  /// ```rust
+  /// # use maplit::btreeset;
+  /// # use gaps::RangeGappable as _;
+  ///
+  /// let set = btreeset!{1, 2, 4};
+  /// assert!(set.has_gaps(1..=4));
+  /// assert!(!set.has_gaps(1..=2));
+  /// ```
+  fn has_gaps<R>(&'s self, range: R) -> bool
+  where
+    R: RangeBounds<T>,
+    Self::Iter: Iterator<Item = T>,
+    T: Clone + Ord + Inc,
+  {
+    self.first_gap(range).is_some()
+  }
+
+  /// Check whether there is any gap in a certain range of a
+  /// collection, possibly deciding based on the number of elements
+  /// alone.
+  ///
+  /// The implementations for [`BTreeSet`] and [`BTreeMap`] can
+  /// frequently answer this question without iterating over the
+  /// elements in the range. All others behave like
+  /// [`has_gaps`][RangeGappable::has_gaps].
+  ///
+  /// ```rust
+  /// # use gaps::RangeGappable as _;
+  /// use std::collections::BTreeSet;
+  ///
+  /// let set = (0..1000).collect::<BTreeSet<u32>>();
+  /// assert!(!set.has_gaps_by_count(0..1000));
+  /// assert!(set.has_gaps_by_count(0..=1000));
+  /// ```
+  fn has_gaps_by_count<R>(&'s self, range: R) -> bool
+  where
+    R: RangeBounds<T>,
+    Self::Iter: Iterator<Item = T>,
+    T: Clone + Ord + Distance,
+  {
+    self.has_gaps(range)
+  }
+
+  /// Find the smallest value missing in a certain range of a
+  /// collection.
+  ///
+  /// `None` is returned if no value is missing or if the first gap has
+  /// an unbounded start, in which case there is no way of knowing the
+  /// smallest missing value.
+  ///
+  /// ```rust
+  /// # use maplit::btreeset;
+  /// # use gaps::RangeGappable as _;
+  ///
+  /// let ids = btreeset!{0, 1, 2, 4};
+  /// assert_eq!(ids.next_free(0..), Some(3));
+  /// assert_eq!(ids.next_free(0..=2), None);
+  /// ```
+  fn next_free<R>(&'s self, range: R) -> Option<T>
+  where
+    R: RangeBounds<T>,
+    Self::Iter: Iterator<Item = T>,
+    T: Clone + Ord + Inc,
+  {
+    first_value(self.first_gap(range)?)
+  }
+
+  /// Find the largest gap in a certain range of a collection, if any.
+  ///
+  /// Gaps with an unbounded start or end are considered larger than
+  /// any other gap. If there are multiple largest gaps, the first one
+  /// is reported.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use maplit::btreeset;
+  /// # use gaps::Gap;
+  /// # use gaps::RangeGappable as _;
+  ///
+  /// let set = btreeset!{2, 3, 7, 9};
+  /// assert_eq!(set.largest_gap(0..=10), Some(Gap::new(Bound::Excluded(3), Bound::Excluded(7))));
+  /// assert_eq!(set.largest_gap(0..), Some(Gap::new(Bound::Excluded(9), Bound::Unbounded)));
+  /// ```
+  fn largest_gap<R>(&'s self, range: R) -> Option<Gap<T>>
+  where
+    R: RangeBounds<T>,
+    Self::Iter: Iterator<Item = T>,
+    T: Clone + Ord + Distance,
+  {
+    largest(self.gaps(range))
+  }
+
+  /// Find the `k` largest gaps in a certain range of a collection,
+  /// largest first.
+  ///
+  /// Gaps are ordered as described for
+  /// [`RangeGappable::largest_gap`], with gaps of the same length being
+  /// reported in ascending order. Only up to `k` gaps are kept in
+  /// memory at any point in time.
+  fn k_largest_gaps<R>(&'s self, range: R, k: usize) -> Vec<Gap<T>>
+  where
+    R: RangeBounds<T>,
+    Self::Iter: Iterator<Item = T>,
+    T: Clone + Ord + Distance,
+  {
+    k_largest(self.gaps(range), k)
+  }
+
+  /// Count the number of values missing in a certain range of a
+  /// collection.
+  ///
+  /// `None` is returned if the range is unbounded (and not fully
+  /// covered) or if the count can not be represented as `usize`.
+  ///
+  /// ```rust
+  /// # use maplit::btreeset;
+  /// # use gaps::RangeGappable as _;
+  ///
+  /// let set = btreeset!{2, 3, 7, 9};
+  /// assert_eq!(set.missing_count(0..=10), Some(7));
+  /// assert_eq!(set.missing_count(0..), None);
+  /// ```
+  fn missing_count<R>(&'s self, range: R) -> Option<usize>
+  where
+    R: RangeBounds<T>,
+    Self::Iter: Iterator<Item = T>,
+    T: Clone + Ord + Distance,
+  {
+    count(self.gaps(range))
+  }
+
+  /// Gather statistics about the gaps in a certain range of a
+  /// collection, in a single pass.
+  ///
+  /// ```rust
+  /// # use maplit::btreeset;
+  /// # use gaps::RangeGappable as _;
+  ///
+  /// let set = btreeset!{2, 3, 7, 9};
+  /// let stats = set.stats(0..=10);
+  /// assert_eq!(stats.count, 4);
+  /// assert_eq!(stats.missing, Some(7));
+  /// ```
+  fn stats<R>(&'s self, range: R) -> GapStats<T>
+  where
+    R: RangeBounds<T>,
+    Self::Iter: Iterator<Item = T>,
+    T: Clone + Ord + Distance,
+  {
+    stats(self.gaps(range))
+  }
+
+  /// Calculate the fraction of values present in a certain range of a
+  /// collection, with `1.0` meaning that there are no gaps.
+  ///
+  /// `None` is returned if the range is unbounded or the number of
+  /// values in it can not be represented as `usize`.
+  ///
+  /// ```rust
+  /// # use maplit::btreeset;
+  /// # use gaps::RangeGappable as _;
+  ///
+  /// let set = btreeset!{1, 2, 3};
+  /// assert_eq!(set.coverage(0..4), Some(0.75));
+  /// assert_eq!(set.coverage(0..), None);
+  /// ```
+  fn coverage<R>(&'s self, range: R) -> Option<f64>
+  where
+    R: RangeBounds<T>,
+    Self::Iter: Iterator<Item = T>,
+    T: Clone + Ord + Distance,
+  {
+    let (start, end) = bounds(&range);
+    coverage(&start, &end, self.gaps(range))
+  }
+
+  /// Find a gap of at least `len` values in a certain range of a
+  /// collection, using the provided [`Fit`] strategy.
+  ///
+  /// Please refer to [`Gappable::find_gap`] for details.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use maplit::btreeset;
+  /// # use gaps::Fit;
+  /// # use gaps::Gap;
+  /// # use gaps::RangeGappable as _;
+  ///
+  /// let set = btreeset!{3, 5, 10, 13};
+  /// let gap = set.find_gap(0..=20, 2, Fit::First);
+  /// assert_eq!(gap, Some(Gap::new(Bound::Included(0), Bound::Included(1))));
+  /// let gap = set.find_gap(0..=20, 2, Fit::Worst);
+  /// assert_eq!(gap, Some(Gap::new(Bound::Excluded(13), Bound::Included(15))));
+  /// ```
+  fn find_gap<R>(&'s self, range: R, len: usize, fit: Fit) -> Option<Gap<T>>
+  where
+    R: RangeBounds<T>,
+    Self::Iter: Iterator<Item = T>,
+    T: Clone + Ord + Step,
+  {
+    find(self.gaps(range), len, fit)
+  }
+
+  /// Create a new iterator yielding the gaps in a certain range of a
+  /// collection, snapped to multiples of `step`.
+  ///
+  /// Please refer to [`GapIter::with_step`] for details.
+  fn gaps_with_step<R>(&'s self, range: R, step: T) -> Stepped<GapIter<Self::Iter, T>, T>
+  where
+    R: RangeBounds<T>,
+    Self::Iter: Iterator<Item = T>,
+    T: Clone + Ord + Inc + Multiple,
+  {
+    self.gaps(range).with_step(step)
+  }
+
+  /// Create a new iterator yielding the individual values missing in a
+  /// certain range of a collection.
+  ///
+  /// ```rust
+  /// # use maplit::btreeset;
+  /// # use gaps::RangeGappable as _;
+  ///
+  /// let set = btreeset!{0, 1, 3};
+  /// let free = set.missing(0..100).take(3).collect::<Vec<_>>();
+  /// assert_eq!(free, vec![2, 4, 5]);
+  /// ```
+  fn missing<R>(&'s self, range: R) -> Values<GapIter<Self::Iter, T>, T>
+  where
+    R: RangeBounds<T>,
+    Self::Iter: Iterator<Item = T>,
+    T: Clone + Ord + Inc,
+  {
+    self.gaps(range).values()
+  }
+
+  /// Create a new [`RunIter`] that yields ranges identifying the runs
+  /// of contiguous elements in a certain range of a collection.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use maplit::btreeset;
+  /// # use gaps::Gap;
+  /// # use gaps::RangeGappable as _;
+  ///
+  /// let set = btreeset!{1, 3, 4, 5, 8};
+  /// let mut runs = set.runs(2..);
+  /// assert_eq!(runs.next(), Some(Gap::new(Bound::Included(3), Bound::Included(5))));
+  /// assert_eq!(runs.next(), Some(Gap::new(Bound::Included(8), Bound::Included(8))));
+  /// assert_eq!(runs.next(), None);
+  /// ```
+  fn runs<R>(&'s self, range: R) -> RunIter<Self::Iter, T>
+  where
+    R: RangeBounds<T>,
+    Self::Iter: Iterator<Item = T>,
+    T: Clone + Ord + Inc,
+  {
+    let (iter, start, end) = self.gaps(range).into_parts();
+    RunIter::from_parts(iter, start, end)
+  }
+
+  /// Create a new [`SegmentIter`] that yields the alternating runs of
+  /// present and missing values in a certain range of a collection.
+  fn segments<R>(&'s self, range: R) -> SegmentIter<Self::Iter, T>
+  where
+    R: RangeBounds<T>,
+    Self::Iter: Iterator<Item = T>,
+    T: Clone + Ord + Inc,
+  {
+    let (iter, start, end) = self.gaps(range).into_parts();
+    SegmentIter::from_parts(iter, start, end)
+  }
+
+  /// Create a new [`RevGapIter`] that yields ranges identifying the
+  /// gaps in a certain range of a collection, from high to low.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use maplit::btreeset;
+  /// # use gaps::Gap;
+  /// # use gaps::RangeGappable as _;
+  ///
+  /// let set = btreeset!{1, 3, 4};
+  /// let mut gaps = set.rgaps(0..=6);
+  /// assert_eq!(gaps.next(), Some(Gap::new(Bound::Excluded(4), Bound::Included(6))));
+  /// assert_eq!(gaps.next(), Some(Gap::new(Bound::Excluded(1), Bound::Excluded(3))));
+  /// assert_eq!(gaps.next(), Some(Gap::new(Bound::Included(0), Bound::Excluded(1))));
+  /// assert_eq!(gaps.next(), None);
+  /// ```
+  fn rgaps<R>(&'s self, range: R) -> RevGapIter<Rev<<Self as Ranged<'s, T>>::Iter>, T>
+  where
+    R: RangeBounds<T>,
+    Self: Ranged<'s, T>,
+    <Self as Ranged<'s, T>>::Iter: DoubleEndedIterator,
+    T: Clone + Ord + Inc,
+  {
+    let (start, end) = bounds(&range);
+    let iter = self.range_iter(start.clone(), end.clone()).rev();
+    RevGapIter::new(iter, start, end)
+  }
 }
 
 impl<'s, V> RangeGappable<'s, V> for BTreeSet<V>
 where
-  V: Copy + Ord + Inc + 's,
+  V: Clone + Ord + Inc + 's,
 {
-  type Iter = Copied<BTreeSetRange<'s, V>>;
+  type Iter = RangeIter<'s, Self, V>;
 
   fn gaps<R>(&'s self, range: R) -> GapIter<Self::Iter, V>
   where
     R: RangeBounds<V>,
   {
     let (start, end) = bounds(&range);
-    let range = self.range(range).copied();
-    GapIter::new(range, start, end)
+    let range = RangeIter::new(self, start.clone(), end.clone());
+    GapIter::with_seek(range, start, end, RangeIter::seek)
+  }
+
+  fn has_gaps_by_count<R>(&'s self, range: R) -> bool
+  where
+    R: RangeBounds<V>,
+    V: Distance,
+  {
+    let (start, end) = bounds(&range);
+    let first_last = self.first().zip(self.last());
+    decide_by_count(self.len(), first_last, start, end)
+      .unwrap_or_else(|| self.has_gaps(range))
   }
 }
 
 
 impl<'s, K, V> RangeGappable<'s, K> for BTreeMap<K, V>
 where
-  K: Copy + Ord + Inc + 's,
+  K: Clone + Ord + Inc + 's,
   V: 's,
 {
-  #[allow(clippy::type_complexity)]
-  type Iter = Map<BTreeMapRange<'s, K, V>, fn((&'_ K, &'_ V)) -> K>;
+  type Iter = RangeIter<'s, Self, K>;
 
   fn gaps<R>(&'s self, range: R) -> GapIter<Self::Iter, K>
   where
     R: RangeBounds<K>,
   {
-    fn map<I, J>(x: (&I, &J)) -> I
-    where
-      I: Copy,
-    {
-      *x.0
-    }
+    let (start, end) = bounds(&range);
+    let range = RangeIter::new(self, start.clone(), end.clone());
+    GapIter::with_seek(range, start, end, RangeIter::seek)
+  }
+
+  fn has_gaps_by_count<R>(&'s self, range: R) -> bool
+  where
+    R: RangeBounds<K>,
+    K: Distance,
+  {
+    let (start, end) = bounds(&range);
+    let first = self.first_key_value().map(|(key, _)| key);
+    let last = self.last_key_value().map(|(key, _)| key);
+    decide_by_count(self.len(), first.zip(last), start, end)
+      .unwrap_or_else(|| self.has_gaps(range))
+  }
+}
+
+
+impl<'s, V> RangeGappable<'s, V> for VecDeque<V>
+where
+  V: Clone + Ord + Inc + 's,
+{
+  type Iter = RangeIter<'s, Self, V>;
+
+  fn gaps<R>(&'s self, range: R) -> GapIter<Self::Iter, V>
+  where
+    R: RangeBounds<V>,
+  {
+    let (start, end) = bounds(&range);
+    let range = RangeIter::new(self, start.clone(), end.clone());
+    GapIter::with_seek(range, start, end, RangeIter::seek)
+  }
+}
+
+
+/// A `HashSet` does not store its elements in any order. All elements
+/// in the range of interest are collected and sorted upfront, resulting
+/// in `O(n log n)` time and `O(n)` memory cost.
+///
+/// ```rust
+/// use std::collections::HashSet;
+/// use std::ops::Bound;
+/// # use gaps::Gap;
+/// # use gaps::RangeGappable as _;
+///
+/// let set = vec![7, 2, 3].into_iter().collect::<HashSet<_>>();
+/// let mut gaps = set.gaps(2..=8);
+/// assert_eq!(gaps.next(), Some(Gap::new(Bound::Excluded(3), Bound::Excluded(7))));
+/// assert_eq!(gaps.next(), Some(Gap::new(Bound::Excluded(7), Bound::Included(8))));
+/// assert_eq!(gaps.next(), None);
+/// ```
+impl<'s, V, S> RangeGappable<'s, V> for HashSet<V, S>
+where
+  V: Clone + Ord + Inc + 's,
+{
+  type Iter = vec::IntoIter<V>;
+
+  fn gaps<R>(&'s self, range: R) -> GapIter<Self::Iter, V>
+  where
+    R: RangeBounds<V>,
+  {
+    let mut values = self
+      .iter()
+      .filter(|value| range.contains(value))
+      .cloned()
+      .collect::<Vec<_>>();
+    values.sort_unstable();
 
     let (start, end) = bounds(&range);
-    let range = self.range(range).map(map as _);
-    GapIter::new(range, start, end)
+    GapIter::new(values.into_iter(), start, end)
   }
 }
 
@@ -247,10 +1853,19 @@ mod tests {
   #[cfg(debug_assertions)]
   #[should_panic(expected = "sequence is not ascending")]
   fn panic_when_non_ascending() {
-    vec![1, 2, 1, 4, 5]
-      .iter()
-      .copied()
-      .gaps(..)
-      .for_each(|_| ());
+    [1, 2, 1, 4, 5].iter().copied().gaps(..).for_each(|_| ());
+  }
+
+  /// Check that a non-ascending sequence is reported as an error by
+  /// [`TryGapIter`].
+  #[test]
+  fn error_when_non_ascending() {
+    let mut it = [1, 2, 1, 4, 5].iter().copied().try_gaps(..);
+    assert_eq!(it.next(), Some(Ok(Gap::new(Unbounded, Excluded(1)))));
+    assert_eq!(
+      it.next(),
+      Some(Err(GapError::Unsorted { prev: 2, next: 1 }))
+    );
+    assert_eq!(it.next(), None);
   }
 }
@@ -0,0 +1,170 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::collections::btree_map::Entry;
+use std::collections::btree_map::IntoIter as BTreeMapIntoIter;
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+use std::iter::FusedIterator;
+use std::mem::replace;
+use std::mem::take;
+use std::ops::Bound;
+use std::ops::Bound::Excluded;
+use std::ops::RangeBounds;
+
+use crate::bounds::lower_le_lower;
+use crate::bounds::start_le_end;
+use crate::Gap;
+use crate::Inc;
+
+
+/// An iterator over the gaps in a sequence of values partitioned into
+/// groups.
+///
+/// Objects of this type are created by
+/// [`GappableByKey::gaps_grouped_by`][crate::GappableByKey::gaps_grouped_by].
+pub struct GroupedGapIter<I, F, G, T> {
+  /// The iterator that we wrap.
+  iter: Option<I>,
+  /// The function mapping items to their group and value.
+  key: F,
+  /// The start of the range to iterate over.
+  start: Bound<T>,
+  /// The end of the range to iterate over.
+  end: Bound<T>,
+  /// The start of the remaining range of each group seen so far.
+  groups: BTreeMap<G, Bound<T>>,
+  /// The groups whose trailing gap is yet to be reported, once the
+  /// wrapped iterator is exhausted.
+  trailing: Option<BTreeMapIntoIter<G, Bound<T>>>,
+}
+
+impl<I, F, G, T> GroupedGapIter<I, F, G, T> {
+  /// Create a new `GroupedGapIter` wrapping the provided iterator.
+  pub(crate) fn new(iter: I, key: F, start: Bound<T>, end: Bound<T>) -> Self {
+    Self {
+      iter: Some(iter),
+      key,
+      start,
+      end,
+      groups: BTreeMap::new(),
+      trailing: None,
+    }
+  }
+}
+
+impl<I, F, G, T> Debug for GroupedGapIter<I, F, G, T>
+where
+  G: Debug,
+  T: Debug,
+{
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    f.debug_struct("GroupedGapIter")
+      .field("start", &self.start)
+      .field("end", &self.end)
+      .field("groups", &self.groups)
+      .finish()
+  }
+}
+
+impl<I, F, G, T> Iterator for GroupedGapIter<I, F, G, T>
+where
+  I: Iterator,
+  F: FnMut(I::Item) -> (G, T),
+  G: Clone + Ord,
+  T: Clone + Ord + Inc,
+{
+  type Item = (G, Gap<T>);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    while let Some(iter) = &mut self.iter {
+      let (group, value) = match iter.next() {
+        Some(item) => (self.key)(item),
+        None => {
+          self.iter = None;
+          self.trailing = Some(take(&mut self.groups).into_iter());
+          break
+        },
+      };
+
+      let start = match self.groups.entry(group.clone()) {
+        Entry::Occupied(entry) => entry.into_mut(),
+        Entry::Vacant(entry) => entry.insert(self.start.clone()),
+      };
+      if !(self.start.as_ref(), self.end.as_ref()).contains(&&value) {
+        continue
+      }
+
+      let next = Excluded(value.clone());
+      if !lower_le_lower(start, &next) {
+        // The value is not ascending with respect to the group's
+        // previous one.
+        continue
+      }
+
+      let gap_start = replace(start, next);
+      let gap_end = Excluded(value);
+      if start_le_end(&gap_start, &gap_end) {
+        return Some((group, Gap::new(gap_start, gap_end)))
+      }
+    }
+
+    let trailing = self.trailing.as_mut()?;
+    for (group, start) in trailing {
+      if start_le_end(&start, &self.end) {
+        return Some((group, Gap::new(start, self.end.clone())))
+      }
+    }
+    None
+  }
+}
+
+impl<I, F, G, T> FusedIterator for GroupedGapIter<I, F, G, T>
+where
+  I: Iterator,
+  F: FnMut(I::Item) -> (G, T),
+  G: Clone + Ord,
+  T: Clone + Ord + Inc,
+{
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use std::ops::Bound::Included;
+  use std::ops::Bound::Unbounded;
+
+
+  /// Check that groups only seen outside of the range of interest are
+  /// reported as missing in full.
+  #[test]
+  fn values_outside_range() {
+    let values = vec![('a', 1), ('b', 8), ('a', 4)];
+    let gaps =
+      GroupedGapIter::new(values.into_iter(), |x| x, Included(2), Included(5)).collect::<Vec<_>>();
+    let expected = vec![
+      ('a', Gap::new(Included(2), Excluded(4))),
+      ('a', Gap::new(Excluded(4), Included(5))),
+      ('b', Gap::new(Included(2), Included(5))),
+    ];
+    assert_eq!(gaps, expected);
+  }
+
+  /// Check that values not ascending within their group are ignored.
+  #[test]
+  fn non_ascending_values() {
+    let values = vec![(0, 5), (0, 2), (0, 7)];
+    let gaps =
+      GroupedGapIter::new(values.into_iter(), |x| x, Unbounded, Unbounded).collect::<Vec<_>>();
+    let expected = vec![
+      (0, Gap::new(Unbounded, Excluded(5))),
+      (0, Gap::new(Excluded(5), Excluded(7))),
+      (0, Gap::new(Excluded(7), Unbounded)),
+    ];
+    assert_eq!(gaps, expected);
+  }
+}
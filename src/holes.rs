@@ -0,0 +1,127 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::borrow::Borrow;
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+
+use crate::Gap;
+
+
+/// An iterator over the index ranges of `None` entries in a sequence of
+/// options.
+///
+/// Objects of this type are created by [`HoleGappable::holes`].
+#[derive(Clone, Debug)]
+pub struct HoleIter<I, T> {
+  /// The iterator that we wrap.
+  iter: Option<I>,
+  /// The index of the next entry.
+  index: usize,
+  /// The index of the first entry of the hole currently being
+  /// traversed, if any.
+  start: Option<usize>,
+  /// Phantom data for the option's value type.
+  _phantom: PhantomData<fn() -> T>,
+}
+
+impl<I, T> Iterator for HoleIter<I, T>
+where
+  I: Iterator,
+  I::Item: Borrow<Option<T>>,
+{
+  type Item = Gap<usize>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    while let Some(iter) = &mut self.iter {
+      let index = self.index;
+      match iter.next() {
+        Some(entry) if entry.borrow().is_none() => {
+          if self.start.is_none() {
+            self.start = Some(index);
+          }
+          self.index += 1;
+          continue
+        },
+        Some(_) => self.index += 1,
+        None => self.iter = None,
+      }
+
+      if let Some(start) = self.start.take() {
+        return Some(Gap::from(start..index))
+      }
+    }
+    None
+  }
+}
+
+impl<I, T> FusedIterator for HoleIter<I, T>
+where
+  I: Iterator,
+  I::Item: Borrow<Option<T>>,
+{
+}
+
+
+/// An extension trait that provides access to the "holes" in a
+/// positionally indexed sequence of options, i.e., the index ranges of
+/// entries that are `None`.
+///
+/// ```rust
+/// use std::ops::Bound;
+/// # use gaps::Gap;
+/// # use gaps::HoleGappable as _;
+///
+/// let slots = [Some('a'), None, None, Some('b'), None];
+/// let mut holes = slots.iter().holes();
+/// assert_eq!(holes.next(), Some(Gap::new(Bound::Included(1), Bound::Excluded(3))));
+/// assert_eq!(holes.next(), Some(Gap::new(Bound::Included(4), Bound::Excluded(5))));
+/// assert_eq!(holes.next(), None);
+/// ```
+pub trait HoleGappable<I, T> {
+  /// Create a new [`HoleIter`] that yields the index ranges of
+  /// consecutive `None` entries.
+  fn holes(self) -> HoleIter<I, T>;
+}
+
+impl<I, T> HoleGappable<I, T> for I
+where
+  I: Iterator,
+  I::Item: Borrow<Option<T>>,
+{
+  fn holes(self) -> HoleIter<I, T> {
+    HoleIter {
+      iter: Some(self),
+      index: 0,
+      start: None,
+      _phantom: PhantomData,
+    }
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+
+  /// Check that holes are reported correctly for sequences with holes at
+  /// either end.
+  #[test]
+  fn holes_at_boundaries() {
+    let slots = vec![None, Some(1), Some(2), None, Some(3), None, None];
+    let holes = slots.into_iter().holes().collect::<Vec<_>>();
+    let expected = vec![Gap::from(0..1), Gap::from(3..4), Gap::from(5..7)];
+    assert_eq!(holes, expected);
+  }
+
+  /// Check that no holes are reported for sequences without any.
+  #[test]
+  fn no_holes() {
+    let slots = [Some(1), Some(2)];
+    assert_eq!(slots.iter().holes().next(), None);
+
+    let slots = Vec::<Option<u8>>::new();
+    assert_eq!(slots.iter().holes().next(), None);
+  }
+}
@@ -0,0 +1,105 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::iter::Cloned;
+use std::iter::FilterMap;
+use std::ops::Bound;
+use std::ops::RangeBounds;
+
+use im::ordmap::Iter as OrdMapIter;
+use im::ordset::RangedIter as OrdSetRange;
+use im::OrdMap;
+use im::OrdSet;
+
+use crate::bounds::bounds;
+use crate::GapIter;
+use crate::Inc;
+use crate::RangeGappable;
+use crate::RangeIter;
+use crate::Ranged;
+
+
+impl<'s, V> Ranged<'s, V> for OrdSet<V>
+where
+  V: Clone + Ord + 's,
+{
+  type Iter = Cloned<OrdSetRange<'s, V>>;
+
+  fn range_iter(&'s self, start: Bound<V>, end: Bound<V>) -> Self::Iter {
+    self.range((start, end)).cloned()
+  }
+}
+
+impl<'s, K, V> Ranged<'s, K> for OrdMap<K, V>
+where
+  K: Clone + Ord + 's,
+  V: 's,
+{
+  #[allow(clippy::type_complexity)]
+  type Iter = FilterMap<OrdMapIter<'s, K, V>, fn((&'_ K, &'_ V)) -> Option<K>>;
+
+  fn range_iter(&'s self, start: Bound<K>, end: Bound<K>) -> Self::Iter {
+    // The range iterator of `OrdMap` reports the number of entries in
+    // the entire map as its exact size. Filtering discards this bogus
+    // size hint, which would otherwise be passed on by `RangeIter`.
+    fn key<I, J>((key, _): (&I, &J)) -> Option<I>
+    where
+      I: Clone,
+    {
+      Some(key.clone())
+    }
+
+    self.range((start, end)).filter_map(key)
+  }
+}
+
+
+/// Elements are retrieved using the set's range iteration.
+///
+/// ```rust
+/// use std::ops::Bound;
+/// # use gaps::Gap;
+/// # use gaps::RangeGappable as _;
+/// use im::ordset;
+///
+/// let set = ordset!{1, 3, 4};
+/// let mut gaps = set.gaps(0..=6);
+/// assert_eq!(gaps.next(), Some(Gap::new(Bound::Included(0), Bound::Excluded(1))));
+/// assert_eq!(gaps.next(), Some(Gap::new(Bound::Excluded(1), Bound::Excluded(3))));
+/// assert_eq!(gaps.next(), Some(Gap::new(Bound::Excluded(4), Bound::Included(6))));
+/// assert_eq!(gaps.next(), None);
+/// ```
+impl<'s, V> RangeGappable<'s, V> for OrdSet<V>
+where
+  V: Clone + Ord + Inc + 's,
+{
+  type Iter = RangeIter<'s, Self, V>;
+
+  fn gaps<R>(&'s self, range: R) -> GapIter<Self::Iter, V>
+  where
+    R: RangeBounds<V>,
+  {
+    let (start, end) = bounds(&range);
+    let range = RangeIter::new(self, start.clone(), end.clone());
+    GapIter::with_seek(range, start, end, RangeIter::seek)
+  }
+}
+
+
+/// Keys are retrieved using the map's range iteration.
+impl<'s, K, V> RangeGappable<'s, K> for OrdMap<K, V>
+where
+  K: Clone + Ord + Inc + 's,
+  V: 's,
+{
+  type Iter = RangeIter<'s, Self, K>;
+
+  fn gaps<R>(&'s self, range: R) -> GapIter<Self::Iter, K>
+  where
+    R: RangeBounds<K>,
+  {
+    let (start, end) = bounds(&range);
+    let range = RangeIter::new(self, start.clone(), end.clone());
+    GapIter::with_seek(range, start, end, RangeIter::seek)
+  }
+}
@@ -1,13 +1,47 @@
 // Copyright (C) 2020 Daniel Mueller <deso@posteo.net>
 // SPDX-License-Identifier: GPL-3.0-or-later
 
+use std::convert::TryFrom as _;
+use std::num::NonZeroI128;
+use std::num::NonZeroI16;
+use std::num::NonZeroI32;
+use std::num::NonZeroI64;
+use std::num::NonZeroI8;
+use std::num::NonZeroIsize;
+use std::num::NonZeroU128;
+use std::num::NonZeroU16;
+use std::num::NonZeroU32;
+use std::num::NonZeroU64;
+use std::num::NonZeroU8;
+use std::num::NonZeroUsize;
 use std::ops::Add as _;
+use std::ops::Sub as _;
+use std::time::Duration;
+
+#[cfg(feature = "chrono")]
+use chrono::NaiveDate;
+#[cfg(feature = "time")]
+use time::Date;
+
+use crate::Domain;
 
 
 /// A trait representing the capability to increment a value.
 pub trait Inc {
   /// Increment self and return the new value.
   fn inc(self) -> Self;
+
+  /// Increment self and return the new value, or `None` if self is
+  /// the maximum value of its type.
+  ///
+  /// The default implementation assumes that there is no such maximum
+  /// and always succeeds.
+  fn checked_inc(self) -> Option<Self>
+  where
+    Self: Sized,
+  {
+    Some(self.inc())
+  }
 }
 
 macro_rules! inc {
@@ -16,6 +50,10 @@ macro_rules! inc {
       fn inc(self) -> Self {
         self.add(1)
       }
+
+      fn checked_inc(self) -> Option<Self> {
+        self.checked_add(1)
+      }
     }
   };
 }
@@ -33,6 +71,286 @@ inc!(i128);
 inc!(usize);
 inc!(isize);
 
+macro_rules! inc_non_zero {
+  ( $t:ty ) => {
+    impl Inc for $t {
+      fn inc(self) -> Self {
+        self
+          .checked_inc()
+          .expect("attempt to increment past maximum value")
+      }
+
+      fn checked_inc(self) -> Option<Self> {
+        let value = self.get().checked_add(1)?;
+        // Incrementing a signed value of -1 skips over zero.
+        Self::new(value).or_else(|| Self::new(1))
+      }
+    }
+  };
+}
+
+inc_non_zero!(NonZeroU8);
+inc_non_zero!(NonZeroI8);
+inc_non_zero!(NonZeroU16);
+inc_non_zero!(NonZeroI16);
+inc_non_zero!(NonZeroU32);
+inc_non_zero!(NonZeroI32);
+inc_non_zero!(NonZeroU64);
+inc_non_zero!(NonZeroI64);
+inc_non_zero!(NonZeroU128);
+inc_non_zero!(NonZeroI128);
+inc_non_zero!(NonZeroUsize);
+inc_non_zero!(NonZeroIsize);
+
+/// Durations, incrementing by one nanosecond.
+///
+/// Use [`Ticked`][crate::Ticked] for incrementing by a coarser
+/// granularity.
+impl Inc for Duration {
+  fn inc(self) -> Self {
+    self
+      .checked_inc()
+      .expect("attempt to increment past maximum value")
+  }
+
+  fn checked_inc(self) -> Option<Self> {
+    self.checked_add(Duration::from_nanos(1))
+  }
+}
+
+/// Dates, incrementing by one day.
+#[cfg(feature = "chrono")]
+impl Inc for NaiveDate {
+  fn inc(self) -> Self {
+    self
+      .checked_inc()
+      .expect("attempt to increment past maximum value")
+  }
+
+  fn checked_inc(self) -> Option<Self> {
+    self.succ_opt()
+  }
+}
+
+/// Dates, incrementing by one day.
+#[cfg(feature = "time")]
+impl Inc for Date {
+  fn inc(self) -> Self {
+    self
+      .checked_inc()
+      .expect("attempt to increment past maximum value")
+  }
+
+  fn checked_inc(self) -> Option<Self> {
+    self.next_day()
+  }
+}
+
+/// Composite keys, incrementing lexicographically.
+///
+/// The second component is incremented, carrying over into the first
+/// one when it wraps around at the end of its [`Domain`].
+///
+/// ```rust
+/// use std::ops::Bound;
+/// # use maplit::btreeset;
+/// # use gaps::Gap;
+/// # use gaps::RangeGappable as _;
+///
+/// let offsets = btreeset!{(0u32, 254u8), (0, 255), (1, 1)};
+/// let mut gaps = offsets.gaps((0, 254)..=(1, 1));
+/// assert_eq!(gaps.next(), Some(Gap::new(Bound::Excluded((0, 255)), Bound::Excluded((1, 1)))));
+/// assert_eq!(gaps.next(), None);
+/// assert_eq!(offsets.missing((0, 254)..=(1, 1)).collect::<Vec<_>>(), vec![(1, 0)]);
+/// ```
+impl<A, B> Inc for (A, B)
+where
+  A: Inc,
+  B: Inc + Domain,
+{
+  fn inc(self) -> Self {
+    let (a, b) = self;
+    match b.checked_inc() {
+      Some(b) => (a, b),
+      None => (a.inc(), B::MIN),
+    }
+  }
+
+  fn checked_inc(self) -> Option<Self> {
+    let (a, b) = self;
+    match b.checked_inc() {
+      Some(b) => Some((a, b)),
+      None => Some((a.checked_inc()?, B::MIN)),
+    }
+  }
+}
+
+/// Fixed-width binary keys, incrementing as big-endian counters.
+///
+/// ```rust
+/// use std::ops::Bound;
+/// # use maplit::btreeset;
+/// # use gaps::RangeGappable as _;
+///
+/// let keys = btreeset!{[0x00, 0xfe], [0x01, 0x00]};
+/// let missing = keys.missing([0x00, 0xfe]..=[0x01, 0x01]).collect::<Vec<_>>();
+/// assert_eq!(missing, vec![[0x00, 0xff], [0x01, 0x01]]);
+/// ```
+impl<const N: usize> Inc for [u8; N] {
+  fn inc(self) -> Self {
+    self
+      .checked_inc()
+      .expect("attempt to increment past maximum value")
+  }
+
+  fn checked_inc(mut self) -> Option<Self> {
+    for byte in self.iter_mut().rev() {
+      match byte.checked_add(1) {
+        Some(value) => {
+          *byte = value;
+          return Some(self)
+        },
+        None => *byte = 0,
+      }
+    }
+    None
+  }
+}
+
+
+/// A trait representing the capability to decrement a value.
+pub trait Dec {
+  /// Decrement self and return the new value.
+  fn dec(self) -> Self;
+
+  /// Decrement self and return the new value, or `None` if self is
+  /// the minimum value of its type.
+  ///
+  /// The default implementation assumes that there is no such minimum
+  /// and always succeeds.
+  fn checked_dec(self) -> Option<Self>
+  where
+    Self: Sized,
+  {
+    Some(self.dec())
+  }
+}
+
+macro_rules! dec {
+  ( $t:ty ) => {
+    impl Dec for $t {
+      fn dec(self) -> Self {
+        self.sub(1)
+      }
+
+      fn checked_dec(self) -> Option<Self> {
+        self.checked_sub(1)
+      }
+    }
+  };
+}
+
+dec!(u8);
+dec!(i8);
+dec!(u16);
+dec!(i16);
+dec!(u32);
+dec!(i32);
+dec!(u64);
+dec!(i64);
+dec!(u128);
+dec!(i128);
+dec!(usize);
+dec!(isize);
+
+
+/// A trait representing the capability to determine the distance
+/// between two values, in terms of increments.
+pub trait Distance: Inc {
+  /// Retrieve the number of times `self` has to be incremented to
+  /// reach `other`.
+  ///
+  /// `None` is returned if `other` is less than `self` or if the
+  /// distance can not be represented as `usize`.
+  fn distance(self, other: Self) -> Option<usize>;
+}
+
+macro_rules! distance {
+  ( $t:ty, $u:ty ) => {
+    impl Distance for $t {
+      #[allow(trivial_numeric_casts)]
+      fn distance(self, other: Self) -> Option<usize> {
+        if other < self {
+          None
+        } else {
+          // The difference always fits into the unsigned counterpart.
+          usize::try_from((other as $u).wrapping_sub(self as $u)).ok()
+        }
+      }
+    }
+  };
+}
+
+distance!(u8, u8);
+distance!(i8, u8);
+distance!(u16, u16);
+distance!(i16, u16);
+distance!(u32, u32);
+distance!(i32, u32);
+distance!(u64, u64);
+distance!(i64, u64);
+distance!(u128, u128);
+distance!(i128, u128);
+distance!(usize, usize);
+distance!(isize, usize);
+
+
+/// A trait representing the capability to advance a value by an
+/// arbitrary number of increments.
+///
+/// Together with [`Distance`] this trait provides the functionality of
+/// the unstable `std::iter::Step`. [`Inc`] remains the minimal
+/// requirement for gap iteration, while this trait enables
+/// functionality needing to skip over many values at once.
+pub trait Step: Distance {
+  /// Increment `self` `n` times and return the new value, or `None` if
+  /// doing so would overflow.
+  fn forward(self, n: usize) -> Option<Self>
+  where
+    Self: Sized;
+}
+
+macro_rules! step {
+  ( $t:ty, $u:ty ) => {
+    impl Step for $t {
+      #[allow(trivial_numeric_casts)]
+      fn forward(self, n: usize) -> Option<Self> {
+        let n = <$u>::try_from(n).ok()?;
+        // As `n` covers at most the full range of the type, an overflow
+        // always results in a value less than `self`.
+        let value = (self as $u).wrapping_add(n) as $t;
+        if value < self {
+          None
+        } else {
+          Some(value)
+        }
+      }
+    }
+  };
+}
+
+step!(u8, u8);
+step!(i8, u8);
+step!(u16, u16);
+step!(i16, u16);
+step!(u32, u32);
+step!(i32, u32);
+step!(u64, u64);
+step!(i64, u64);
+step!(u128, u128);
+step!(i128, u128);
+step!(usize, usize);
+step!(isize, usize);
 
 #[cfg(test)]
 mod tests {
@@ -52,5 +370,93 @@ mod tests {
     assert_eq!(inc(-1i16), 0);
     assert_eq!(inc(129_012u32), 129_013);
     assert_eq!(inc(42usize), 43);
+
+    assert_eq!(1u8.checked_inc(), Some(2));
+    assert_eq!(u8::MAX.checked_inc(), None);
+    assert_eq!(i64::MAX.checked_inc(), None);
+  }
+
+  #[test]
+  fn increment_non_zero() {
+    let one = NonZeroU8::new(1).unwrap();
+    assert_eq!(one.inc().get(), 2);
+    assert_eq!(NonZeroU8::new(u8::MAX).unwrap().checked_inc(), None);
+
+    let minus_one = NonZeroI16::new(-1).unwrap();
+    assert_eq!(minus_one.inc().get(), 1);
+    assert_eq!(NonZeroI16::new(-2).unwrap().inc().get(), -1);
+    assert_eq!(NonZeroI16::new(i16::MAX).unwrap().checked_inc(), None);
+  }
+
+  #[test]
+  #[should_panic(expected = "attempt to increment past maximum value")]
+  fn increment_non_zero_overflow() {
+    let _x = NonZeroUsize::new(usize::MAX).unwrap().inc();
+  }
+
+  #[test]
+  fn increment_tuple() {
+    assert_eq!((1u8, 2u8).inc(), (1, 3));
+    assert_eq!((1u8, u16::MAX).inc(), (2, 0));
+    assert_eq!((-1i32, i8::MAX).inc(), (0, i8::MIN));
+    assert_eq!((u8::MAX, u8::MAX).checked_inc(), None);
+    assert_eq!((0u8, (0u8, u8::MAX)).checked_inc(), Some((0, (1, 0))));
+  }
+
+  #[test]
+  fn increment_bytes() {
+    assert_eq!([0u8, 1].inc(), [0, 2]);
+    assert_eq!([0u8, 0xff, 0xff].inc(), [1, 0, 0]);
+    assert_eq!([0xffu8; 4].checked_inc(), None);
+    assert_eq!([0u8; 0].checked_inc(), None);
+  }
+
+  #[test]
+  fn increment_duration() {
+    assert_eq!(Duration::from_secs(1).inc(), Duration::new(1, 1));
+    assert_eq!(Duration::new(1, 999_999_999).inc(), Duration::from_secs(2));
+    assert_eq!(Duration::MAX.checked_inc(), None);
+  }
+
+  #[test]
+  fn decrement() {
+    fn dec<T>(x: T) -> T
+    where
+      T: Dec,
+    {
+      x.dec()
+    }
+
+    assert_eq!(dec(2u8), 1);
+    assert_eq!(dec(0i16), -1);
+    assert_eq!(dec(129_013u32), 129_012);
+    assert_eq!(dec(43usize), 42);
+
+    assert_eq!(2u8.checked_dec(), Some(1));
+    assert_eq!(u8::MIN.checked_dec(), None);
+    assert_eq!(i64::MIN.checked_dec(), None);
+  }
+
+  #[test]
+  fn distance() {
+    assert_eq!(1u8.distance(1), Some(0));
+    assert_eq!(1u8.distance(5), Some(4));
+    assert_eq!(5u8.distance(1), None);
+    assert_eq!(i8::MIN.distance(i8::MAX), Some(255));
+    assert_eq!((-3i64).distance(2), Some(5));
+    assert_eq!(0u128.distance(u128::MAX), None);
+  }
+
+  #[test]
+  fn forward() {
+    assert_eq!(1u8.forward(0), Some(1));
+    assert_eq!(1u8.forward(4), Some(5));
+    assert_eq!(1u8.forward(254), Some(255));
+    assert_eq!(1u8.forward(255), None);
+    assert_eq!(0u8.forward(256), None);
+    assert_eq!(i8::MIN.forward(255), Some(i8::MAX));
+    assert_eq!((-3i64).forward(5), Some(2));
+    assert_eq!(i64::MAX.forward(1), None);
+    assert_eq!(0u128.forward(usize::MAX), Some(usize::MAX as u128));
   }
 }
@@ -0,0 +1,295 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+use std::ops::Bound;
+use std::ops::Bound::Unbounded;
+use std::ops::RangeBounds;
+
+use crate::bounds::bounds;
+use crate::bounds::cmp_end;
+use crate::bounds::cmp_start;
+use crate::bounds::end_lt_end;
+use crate::bounds::flip;
+use crate::bounds::start_le_end;
+use crate::bounds::start_lt_start;
+use crate::Gap;
+use crate::Inc;
+
+
+/// An interval pending in a [`Reordered`] iterator, ordered such that
+/// the interval with the smallest start is the greatest.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Pending<T>((Bound<T>, Bound<T>));
+
+impl<T> PartialOrd for Pending<T>
+where
+  T: Ord,
+{
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl<T> Ord for Pending<T>
+where
+  T: Ord,
+{
+  fn cmp(&self, other: &Self) -> Ordering {
+    let ((start1, end1), (start2, end2)) = (&self.0, &other.0);
+    cmp_start(start1, start2)
+      .then_with(|| cmp_end(end1, end2))
+      .reverse()
+  }
+}
+
+
+/// An iterator yielding the intervals of the wrapped iterator ordered
+/// by their start, as long as no interval is displaced by more than a
+/// certain number of positions.
+///
+/// Objects of this type are created by
+/// [`IntervalGappable::interval_gaps_unordered`].
+#[derive(Clone, Debug)]
+pub struct Reordered<I, T> {
+  /// The iterator that we wrap.
+  iter: I,
+  /// The intervals read but not yet yielded.
+  pending: BinaryHeap<Pending<T>>,
+  /// The maximum number of intervals to buffer.
+  window: usize,
+}
+
+impl<I, R, T> Iterator for Reordered<I, T>
+where
+  I: Iterator<Item = R>,
+  R: RangeBounds<T>,
+  T: Clone + Ord,
+{
+  type Item = (Bound<T>, Bound<T>);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    while self.pending.len() < self.window {
+      match self.iter.next() {
+        Some(interval) => self.pending.push(Pending(bounds(&interval))),
+        None => break,
+      }
+    }
+    self.pending.pop().map(|pending| pending.0)
+  }
+}
+
+impl<I, R, T> FusedIterator for Reordered<I, T>
+where
+  I: FusedIterator<Item = R>,
+  R: RangeBounds<T>,
+  T: Clone + Ord,
+{
+}
+
+
+/// An iterator over the gaps between the intervals represented by an
+/// iterator.
+#[derive(Clone, Debug)]
+pub struct IntervalGapIter<I, R, T> {
+  /// The iterator that we wrap.
+  iter: Option<I>,
+  /// The start of the remaining range that is not covered by any of the
+  /// intervals seen so far.
+  start: Bound<T>,
+  /// The end of the range to iterate over.
+  end: Bound<T>,
+  /// Phantom data for the interval type.
+  _phantom: PhantomData<fn() -> R>,
+}
+
+impl<I, R, T> IntervalGapIter<I, R, T>
+where
+  I: Iterator<Item = R>,
+  R: RangeBounds<T>,
+  T: Clone + Ord + Inc,
+{
+  /// Create a new `IntervalGapIter` wrapping the provided iterator and
+  /// yielding ranges identifying the gaps between the intervals, if
+  /// any.
+  ///
+  /// # Notes
+  /// - the provided iterator is assumed to yield intervals in ascending
+  ///   order of their start bounds; intervals may overlap or be
+  ///   adjacent to each other
+  pub fn new(iter: I, start: Bound<T>, end: Bound<T>) -> Self {
+    Self {
+      iter: Some(iter),
+      start,
+      end,
+      _phantom: PhantomData,
+    }
+  }
+}
+
+impl<I, R, T> Iterator for IntervalGapIter<I, R, T>
+where
+  I: Iterator<Item = R>,
+  R: RangeBounds<T>,
+  T: Clone + Ord + Inc,
+{
+  type Item = Gap<T>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    loop {
+      let iter = self.iter.as_mut()?;
+      let (start, end) = if let Some(interval) = iter.next() {
+        let (interval_start, interval_end) = bounds(&interval);
+        let start = self.start.clone();
+        let end = match interval_start {
+          // There can't be a gap before an interval without a start.
+          Unbounded => None,
+          interval_start => Some(flip(interval_start)),
+        };
+
+        match end {
+          Some(end) if !end_lt_end(&end, &self.end) => {
+            // The interval starts at or past the end of our overarching
+            // range. We are done.
+            self.iter = None;
+            (start, self.end.clone())
+          },
+          end => {
+            if interval_end == Unbounded {
+              // The interval covers everything past its start.
+              self.iter = None;
+            } else {
+              let next = flip(interval_end);
+              if self.start == Unbounded || start_lt_start(&self.start, &next) {
+                self.start = next;
+              }
+
+              if !start_le_end(&self.start, &self.end) {
+                // If our start has caught up with our end, we are done.
+                self.iter = None;
+              }
+            }
+
+            match end {
+              Some(end) => (start, end),
+              None => continue,
+            }
+          },
+        }
+      } else {
+        // The iterator is out of items and we are done.
+        self.iter = None;
+        (self.start.clone(), self.end.clone())
+      };
+
+      // We could still end up with a range that is empty (or even
+      // descending). Don't report those.
+      if start_le_end(&start, &end) {
+        break Some(Gap::new(start, end))
+      }
+    }
+  }
+}
+
+impl<I, R, T> FusedIterator for IntervalGapIter<I, R, T>
+where
+  I: Iterator<Item = R>,
+  R: RangeBounds<T>,
+  T: Clone + Ord + Inc,
+{
+}
+
+
+/// An extension trait that provides range based access to the "gaps"
+/// between ordered intervals yielded by an iterator.
+///
+/// E.g., given the intervals `[1..3, 2..=4, 8..9]`, the gaps in the
+/// range `0..=10` would be: `[0..1, 5..8, 9..=10]`.
+///
+/// ```rust
+/// use std::ops::Bound;
+/// # use gaps::Gap;
+/// # use gaps::IntervalGappable as _;
+///
+/// let vec = vec![(Bound::Included(1), Bound::Excluded(3)),
+///                (Bound::Included(2), Bound::Included(4)),
+///                (Bound::Included(8), Bound::Excluded(9))];
+/// let mut gaps = vec.into_iter().interval_gaps(0..=10);
+/// assert_eq!(gaps.next(), Some(Gap::new(Bound::Included(0), Bound::Excluded(1))));
+/// assert_eq!(gaps.next(), Some(Gap::new(Bound::Excluded(4), Bound::Excluded(8))));
+/// assert_eq!(gaps.next(), Some(Gap::new(Bound::Included(9), Bound::Included(10))));
+/// assert_eq!(gaps.next(), None);
+/// ```
+pub trait IntervalGappable<I, R, T> {
+  /// Create a new [`IntervalGapIter`] that yields ranges identifying
+  /// the gaps between intervals in a certain range.
+  fn interval_gaps<S>(self, range: S) -> IntervalGapIter<I, R, T>
+  where
+    S: RangeBounds<T>;
+
+  /// Create a new [`IntervalGapIter`] that yields ranges identifying
+  /// the gaps between intervals in a certain range, with intervals not
+  /// necessarily being ordered by their start.
+  ///
+  /// Up to `window` intervals are buffered and reordered, meaning that
+  /// an interval may appear up to `window - 1` positions after one
+  /// starting later than it. Intervals displaced further do not cause
+  /// gaps to be retracted after they have been reported, but they are
+  /// still taken into account for gaps not yet reported.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use gaps::Gap;
+  /// # use gaps::IntervalGappable as _;
+  ///
+  /// let busy = vec![4..6, 1..3, 2..5, 9..10];
+  /// let mut gaps = busy.into_iter().interval_gaps_unordered(0..12, 2);
+  /// assert_eq!(gaps.next(), Some(Gap::new(Bound::Included(0), Bound::Excluded(1))));
+  /// assert_eq!(gaps.next(), Some(Gap::new(Bound::Included(6), Bound::Excluded(9))));
+  /// assert_eq!(gaps.next(), Some(Gap::new(Bound::Included(10), Bound::Excluded(12))));
+  /// assert_eq!(gaps.next(), None);
+  /// ```
+  #[allow(clippy::type_complexity)]
+  fn interval_gaps_unordered<S>(
+    self,
+    range: S,
+    window: usize,
+  ) -> IntervalGapIter<Reordered<I, T>, (Bound<T>, Bound<T>), T>
+  where
+    S: RangeBounds<T>;
+}
+
+impl<I, R, T> IntervalGappable<I, R, T> for I
+where
+  I: Iterator<Item = R>,
+  R: RangeBounds<T>,
+  T: Clone + Ord + Inc,
+{
+  fn interval_gaps<S>(self, range: S) -> IntervalGapIter<I, R, T>
+  where
+    S: RangeBounds<T>,
+  {
+    let (start, end) = bounds(&range);
+    IntervalGapIter::new(self, start, end)
+  }
+
+  #[allow(clippy::type_complexity)]
+  fn interval_gaps_unordered<S>(
+    self,
+    range: S,
+    window: usize,
+  ) -> IntervalGapIter<Reordered<I, T>, (Bound<T>, Bound<T>), T>
+  where
+    S: RangeBounds<T>,
+  {
+    let reordered = Reordered {
+      iter: self,
+      pending: BinaryHeap::new(),
+      window: window.max(1),
+    };
+    reordered.interval_gaps(range)
+  }
+}
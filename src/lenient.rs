@@ -0,0 +1,158 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::iter::FusedIterator;
+use std::ops::Bound;
+
+use crate::bounds::start_le_end;
+use crate::gaps::step;
+use crate::Gap;
+use crate::Inc;
+
+
+/// An iterator over the gaps in a sequence represented by an iterator,
+/// skipping over elements that are not in ascending order.
+///
+/// Objects of this type are created by
+/// [`Gappable::gaps_lenient`][crate::Gappable::gaps_lenient].
+#[derive(Clone, Debug)]
+pub struct LenientGapIter<I, T> {
+  /// The iterator that we wrap, if iteration has not yet finished.
+  iter: Option<I>,
+  /// The start of the remaining range we iterate.
+  start: Bound<T>,
+  /// The end of the range to iterate over.
+  end: Bound<T>,
+  /// The last element not skipped, if any.
+  last: Option<T>,
+  /// The number of elements skipped so far.
+  skipped: usize,
+}
+
+impl<I, T> LenientGapIter<I, T>
+where
+  I: Iterator<Item = T>,
+  T: Clone + Ord + Inc,
+{
+  /// Create a new `LenientGapIter` from the parts of a
+  /// [`GapIter`][crate::GapIter], which may have finished already.
+  pub(crate) fn from_parts(iter: Option<I>, start: Bound<T>, end: Bound<T>) -> Self {
+    Self {
+      iter,
+      start,
+      end,
+      last: None,
+      skipped: 0,
+    }
+  }
+}
+
+impl<I, T> LenientGapIter<I, T> {
+  /// Retrieve the number of elements skipped so far, because they were
+  /// less than a preceding one.
+  ///
+  /// Elements following the end of the range of interest may never be
+  /// inspected and are not accounted for.
+  pub fn skipped(&self) -> usize {
+    self.skipped
+  }
+}
+
+impl<I, T> Iterator for LenientGapIter<I, T>
+where
+  I: Iterator<Item = T>,
+  T: Clone + Ord + Inc,
+{
+  type Item = Gap<T>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    loop {
+      let iter = self.iter.as_mut()?;
+      if let Some(this) = iter.next() {
+        match &self.last {
+          Some(last) if this < *last => {
+            self.skipped += 1;
+            continue
+          },
+          _ => self.last = Some(this.clone()),
+        }
+
+        let (gap, done) = step(&mut self.start, &self.end, this);
+        if done {
+          self.iter = None;
+        }
+        if let Some((start, end)) = gap {
+          break Some(Gap::new(start, end))
+        }
+      } else {
+        // The iterator is out of items and we are done.
+        self.iter = None;
+        if start_le_end(&self.start, &self.end) {
+          break Some(Gap::new(self.start.clone(), self.end.clone()))
+        }
+      }
+    }
+  }
+}
+
+impl<I, T> FusedIterator for LenientGapIter<I, T>
+where
+  I: Iterator<Item = T>,
+  T: Clone + Ord + Inc,
+{
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use std::ops::Bound::Excluded;
+  use std::ops::Bound::Included;
+
+
+  /// Check that elements out of order are skipped and counted.
+  #[test]
+  fn skipping() {
+    let values = vec![1, 4, 2, 4, 3, 6, 0, 8];
+    let mut gaps = LenientGapIter::from_parts(Some(values.into_iter()), Included(0), Included(9));
+    assert_eq!(gaps.skipped(), 0);
+
+    let gaps = gaps.by_ref().collect::<Vec<_>>();
+    let expected = vec![
+      Gap::new(Included(0), Excluded(1)),
+      Gap::new(Excluded(1), Excluded(4)),
+      Gap::new(Excluded(4), Excluded(6)),
+      Gap::new(Excluded(6), Excluded(8)),
+      Gap::new(Excluded(8), Included(9)),
+    ];
+    assert_eq!(gaps, expected);
+  }
+
+  /// Check that the number of skipped elements is reported correctly.
+  #[test]
+  fn skip_count() {
+    let values = vec![5, 3, 7, 6, 6, 7];
+    let mut gaps = LenientGapIter::from_parts(Some(values.into_iter()), Included(5), Included(9));
+    let _ = gaps.by_ref().count();
+    assert_eq!(gaps.skipped(), 3);
+  }
+
+  /// Check that a `LenientGapIter` can be cloned and sent to a
+  /// different thread.
+  #[test]
+  fn clone_send() {
+    let values = vec![1, 4, 2, 6];
+    let mut gaps = LenientGapIter::from_parts(Some(values.into_iter()), Included(0), Included(6));
+    assert_eq!(gaps.next(), Some(Gap::new(Included(0), Excluded(1))));
+
+    let clone = gaps.clone();
+    let handle = std::thread::spawn(move || {
+      let count = gaps.by_ref().count();
+      (count, gaps.skipped())
+    });
+    assert_eq!(handle.join().unwrap(), (2, 1));
+    assert_eq!(clone.skipped(), 0);
+    assert_eq!(clone.count(), 2);
+  }
+}
@@ -10,7 +10,6 @@
   broken_intra_doc_links,
   dead_code,
   future_incompatible,
-  illegal_floating_point_literal_pattern,
   improper_ctypes,
   late_bound_lifetime_arguments,
   missing_copy_implementations,
@@ -22,7 +21,6 @@
   overflowing_literals,
   path_statements,
   patterns_in_fns_without_body,
-  private_in_public,
   proc_macro_derive_resolution_fallback,
   renamed_and_removed_lints,
   rust_2018_compatibility,
@@ -32,7 +30,6 @@
   trivial_numeric_casts,
   type_alias_bounds,
   tyvar_behind_raw_pointer,
-  unaligned_references,
   unconditional_recursion,
   unreachable_code,
   unreachable_patterns,
@@ -45,20 +42,147 @@
   unused_lifetimes,
   unused_qualifications,
   unused_results,
-  where_clauses_object_safety,
   while_true
 )]
 
+mod adapter;
+pub mod bits;
 mod bounds;
+mod combine;
+mod domain;
+mod error;
+mod fallible;
+#[cfg(any(feature = "proptest", feature = "quickcheck"))]
+mod fixture;
+#[cfg(feature = "float")]
+mod float;
+pub mod fmt;
+mod gap;
 mod gaps;
+mod grouped;
+mod holes;
+#[cfg(feature = "im")]
+mod im;
 mod inc;
+mod interval;
+mod lenient;
+mod list;
+mod map;
+mod multiple;
+#[cfg(feature = "num")]
+mod num;
+mod probe;
+#[cfg(feature = "proptest")]
+mod proptest;
+mod query;
+#[cfg(feature = "quickcheck")]
+mod quickcheck;
+pub mod range;
+mod ranged;
+#[cfg(feature = "rangemap")]
+mod rangemap;
+mod rev;
+#[cfg(feature = "roaring")]
+mod roaring;
+mod runs;
+mod serial;
+mod set;
+mod step_by;
+mod ticked;
+mod tracker;
+mod view;
+mod window;
 
+pub use crate::adapter::Aligned;
+pub use crate::adapter::Chunks;
+pub use crate::adapter::Clip;
+pub use crate::adapter::Coalesce;
+pub use crate::adapter::Stepped;
+pub use crate::adapter::Values;
+pub use crate::combine::complement;
+pub use crate::combine::Difference;
+pub use crate::combine::Intersection;
+pub use crate::combine::SymmetricDifference;
+pub use crate::combine::Union;
+pub use crate::domain::Domain;
+pub use crate::error::DecodeError;
+pub use crate::error::GapError;
+pub use crate::error::ParseError;
+pub use crate::fallible::FallibleGapIter;
+pub use crate::fallible::FallibleGappable;
+#[cfg(any(feature = "proptest", feature = "quickcheck"))]
+pub use crate::fixture::GapFixture;
+#[cfg(feature = "float")]
+pub use crate::float::Quantized;
+pub use crate::gap::DisplayList;
+pub use crate::gap::Gap;
 pub use crate::gaps::GapIter;
 pub use crate::gaps::Gappable;
+pub use crate::gaps::GappableByKey;
 pub use crate::gaps::RangeGappable;
+pub use crate::gaps::TryGapIter;
+pub use crate::grouped::GroupedGapIter;
+pub use crate::holes::HoleGappable;
+pub use crate::holes::HoleIter;
+pub use crate::inc::Dec;
+pub use crate::inc::Distance;
 pub use crate::inc::Inc;
+pub use crate::inc::Step;
+pub use crate::interval::IntervalGapIter;
+pub use crate::interval::IntervalGappable;
+pub use crate::interval::Reordered;
+pub use crate::lenient::LenientGapIter;
+pub use crate::list::GapDelta;
+pub use crate::list::GapList;
+pub use crate::map::MapGappable;
+pub use crate::map::Matching;
+pub use crate::map::NeighborIter;
+pub use crate::map::NeighboredGap;
+pub use crate::multiple::Multiple;
+#[cfg(feature = "num")]
+pub use crate::num::Numeric;
+pub use crate::probe::gaps_by;
+pub use crate::probe::ProbeGapIter;
+pub use crate::query::Fit;
+pub use crate::query::GapStats;
+pub use crate::ranged::RangeIter;
+pub use crate::ranged::Ranged;
+#[cfg(feature = "rangemap")]
+pub use crate::rangemap::RangeSetGappable;
+pub use crate::rev::RevGapIter;
+#[cfg(feature = "roaring")]
+pub use crate::roaring::TreemapRange;
+pub use crate::runs::RunIter;
+pub use crate::runs::Segment;
+pub use crate::runs::SegmentIter;
+pub use crate::serial::Serial;
+pub use crate::serial::SerialGapIter;
+pub use crate::serial::SerialGappable;
+pub use crate::set::GapSet;
+pub use crate::step_by::StepBy;
+pub use crate::ticked::Ticked;
+pub use crate::tracker::GapTracker;
+pub use crate::view::GapSetRef;
+pub use crate::view::RangesRef;
+pub use crate::window::SeqWindow;
 
-/// A module providing utility functionality for working with ranges.
-pub mod range {
-  pub use crate::bounds::bounds;
-}
+/// Derive `Dec` for a newtype by delegating to its only field.
+#[cfg(feature = "derive")]
+pub use gaps_derive::Dec;
+/// Derive `Inc` for a newtype by delegating to its only field.
+///
+/// ```rust
+/// # use std::ops::Bound;
+/// # use maplit::btreeset;
+/// # use gaps::Gap;
+/// # use gaps::RangeGappable as _;
+/// #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, gaps::Inc)]
+/// struct OrderId(u64);
+///
+/// let orders = btreeset!{OrderId(1), OrderId(3)};
+/// let mut gaps = orders.gaps(OrderId(1)..=OrderId(3));
+/// assert_eq!(gaps.next(), Some(Gap::new(Bound::Excluded(OrderId(1)), Bound::Excluded(OrderId(3)))));
+/// assert_eq!(gaps.next(), None);
+/// ```
+#[cfg(feature = "derive")]
+pub use gaps_derive::Inc;
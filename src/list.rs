@@ -0,0 +1,551 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::cmp::Ordering;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::iter::empty;
+use std::iter::FromIterator;
+use std::ops::BitAnd;
+use std::ops::BitOr;
+use std::ops::BitXor;
+use std::ops::Bound;
+use std::ops::Bound::Included;
+use std::ops::Bound::Unbounded;
+use std::ops::RangeBounds;
+use std::ops::Sub;
+use std::slice;
+use std::vec;
+
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+#[cfg(feature = "serde")]
+use serde::Deserializer;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+#[cfg(feature = "serde")]
+use serde::Serializer;
+
+use crate::bounds::bounds;
+use crate::bounds::contains;
+use crate::bounds::end_lt_end;
+use crate::bounds::len;
+use crate::bounds::lower_le_lower;
+use crate::bounds::overlap;
+use crate::bounds::start_le_end;
+use crate::complement;
+use crate::range;
+use crate::Difference;
+use crate::Distance;
+use crate::Gap;
+use crate::Inc;
+use crate::Intersection;
+use crate::SymmetricDifference;
+use crate::Union;
+
+
+/// The changes between two snapshots of gaps, as produced by
+/// [`GapList::diff`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct GapDelta<T> {
+  /// Earlier gaps that have been filled completely.
+  pub closed: Vec<Gap<T>>,
+  /// Later gaps that did not overlap with any earlier gap.
+  pub opened: Vec<Gap<T>>,
+  /// Pairs of overlapping earlier and later gaps, where the later gap
+  /// lacks some values of the earlier one.
+  pub shrank: Vec<(Gap<T>, Gap<T>)>,
+  /// Pairs of overlapping earlier and later gaps, where the later gap
+  /// covers additional values.
+  pub grew: Vec<(Gap<T>, Gap<T>)>,
+}
+
+impl<T> GapDelta<T> {
+  /// Check whether no changes at all were found.
+  pub fn is_empty(&self) -> bool {
+    self.closed.is_empty()
+      && self.opened.is_empty()
+      && self.shrank.is_empty()
+      && self.grew.is_empty()
+  }
+}
+
+
+/// Bring `gap` into a canonical form for comparison purposes, i.e., an
+/// included start and an excluded end, with an end including the
+/// type's maximum value being treated as unbounded.
+fn canonical<T>(gap: &Gap<T>) -> (Bound<T>, Bound<T>)
+where
+  T: Clone + Inc,
+{
+  match range::normalize(gap) {
+    (start, Included(_)) => (start, Unbounded),
+    bounds => bounds,
+  }
+}
+
+
+/// A collected list of gaps, sorted in ascending order.
+///
+/// A `GapList` is typically created by collecting the gaps produced by
+/// a [`GapIter`][crate::GapIter]. Gaps passed in are sorted and
+/// overlapping or adjacent ones merged, so that the list always
+/// contains disjoint, maximal gaps. That, in turn, allows for lookups
+/// in logarithmic time.
+///
+/// Gaps are kept as provided, but lists covering the same values
+/// compare (and hash) equal, irrespective of the bounds used. Because
+/// no minimum value is known, an unbounded start is not considered
+/// equal to one including the type's minimum, though.
+///
+/// ```rust
+/// use std::ops::Bound;
+/// # use maplit::btreeset;
+/// # use gaps::Gap;
+/// # use gaps::GapList;
+/// # use gaps::RangeGappable as _;
+///
+/// let set = btreeset!{2, 3, 7};
+/// let list = set.gaps(0..10).collect::<GapList<_>>();
+/// assert_eq!(list.len(), 3);
+/// assert!(list.contains(5));
+/// assert!(!list.contains(7));
+/// assert_eq!(list.get(8), Some(&Gap::new(Bound::Excluded(7), Bound::Excluded(10))));
+/// assert_eq!(list.total_missing(), Some(7));
+/// ```
+#[derive(Clone, Debug)]
+pub struct GapList<T> {
+  /// The sorted, disjoint, and non-adjacent gaps.
+  gaps: Vec<Gap<T>>,
+}
+
+impl<T> GapList<T> {
+  /// Create a new, empty `GapList`.
+  pub fn new() -> Self {
+    Self { gaps: Vec::new() }
+  }
+
+  /// Retrieve the number of gaps in the list.
+  pub fn len(&self) -> usize {
+    self.gaps.len()
+  }
+
+  /// Check whether the list contains no gaps.
+  pub fn is_empty(&self) -> bool {
+    self.gaps.is_empty()
+  }
+
+  /// Retrieve an iterator over the gaps in the list.
+  pub fn iter(&self) -> slice::Iter<'_, Gap<T>> {
+    self.gaps.iter()
+  }
+
+  /// Retrieve the gaps in the list as a slice.
+  pub fn as_slice(&self) -> &[Gap<T>] {
+    &self.gaps
+  }
+}
+
+impl<T> GapList<T>
+where
+  T: Clone + Ord + Inc,
+{
+  /// Find the index of the first gap that does not end before `start`.
+  fn lower_bound(&self, start: &Bound<T>) -> usize {
+    self
+      .gaps
+      .partition_point(|gap| !start_le_end(start, gap.end()))
+  }
+
+  /// Retrieve the gap containing `value`, if any.
+  pub fn get(&self, value: T) -> Option<&Gap<T>> {
+    let value = Included(value);
+    self
+      .gaps
+      .get(self.lower_bound(&value))
+      .filter(|gap| lower_le_lower(gap.start(), &value))
+  }
+
+  /// Check whether `value` is part of any of the gaps.
+  pub fn contains(&self, value: T) -> bool {
+    self.get(value).is_some()
+  }
+
+  /// Check whether any of the gaps overlaps with the provided range.
+  pub fn overlaps<R>(&self, range: R) -> bool
+  where
+    R: RangeBounds<T>,
+  {
+    let range = Gap::from(bounds(&range));
+    self
+      .gaps
+      .get(self.lower_bound(range.start()))
+      .is_some_and(|gap| overlap((gap.start(), gap.end()), (range.start(), range.end())))
+  }
+
+  /// Create a new list containing the ranges that are gaps in `self`,
+  /// `other`, or both.
+  pub fn union(&self, other: &Self) -> Self {
+    let gaps = Union::new(self.iter().cloned(), other.iter().cloned()).collect();
+    Self { gaps }
+  }
+
+  /// Create a new list containing the ranges that are gaps in both
+  /// `self` and `other`.
+  pub fn intersection(&self, other: &Self) -> Self {
+    let gaps = Intersection::new(self.iter().cloned(), other.iter().cloned()).collect();
+    Self { gaps }
+  }
+
+  /// Create a new list containing the ranges that are gaps in `self`
+  /// but not in `other`.
+  pub fn difference(&self, other: &Self) -> Self {
+    let gaps = Difference::new(self.iter().cloned(), other.iter().cloned()).collect();
+    Self { gaps }
+  }
+
+  /// Create a new list containing the ranges that are gaps in either
+  /// `self` or `other`, but not in both.
+  pub fn symmetric_difference(&self, other: &Self) -> Self {
+    let gaps = SymmetricDifference::new(self.iter().cloned(), other.iter().cloned()).collect();
+    Self { gaps }
+  }
+
+  /// Create a new list containing the ranges within `domain` that are
+  /// not gaps, i.e., that are covered by values.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use maplit::btreeset;
+  /// # use gaps::Gap;
+  /// # use gaps::GapList;
+  /// # use gaps::RangeGappable as _;
+  ///
+  /// let list = btreeset!{2, 3, 7}.gaps(0..10).collect::<GapList<_>>();
+  /// let present = list.complement(0..10);
+  /// assert_eq!(
+  ///   present.as_slice(),
+  ///   &[
+  ///     Gap::new(Bound::Included(2), Bound::Included(3)),
+  ///     Gap::new(Bound::Included(7), Bound::Included(7)),
+  ///   ],
+  /// );
+  /// ```
+  pub fn complement<R>(&self, domain: R) -> Self
+  where
+    R: RangeBounds<T>,
+  {
+    let gaps = complement(self.iter().cloned(), domain).collect();
+    Self { gaps }
+  }
+
+  /// Determine how gaps changed between two snapshots.
+  ///
+  /// Overlapping gaps of `earlier` and `later` are paired up. A pair
+  /// may be reported as having both shrunk and grown, if the gap moved
+  /// in parts. A gap that was split into multiple ones, or multiple
+  /// gaps that got merged, are reported as multiple pairs.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use maplit::btreeset;
+  /// # use gaps::Gap;
+  /// # use gaps::GapList;
+  /// # use gaps::RangeGappable as _;
+  ///
+  /// let earlier = btreeset!{2, 7}.gaps(0..10).collect::<GapList<_>>();
+  /// let later = btreeset!{2, 3, 4, 5, 6, 7}.gaps(0..10).collect::<GapList<_>>();
+  /// let delta = GapList::diff(&earlier, &later);
+  /// assert_eq!(delta.closed, vec![Gap::new(Bound::Excluded(2), Bound::Excluded(7))]);
+  /// assert!(delta.opened.is_empty());
+  /// assert!(delta.shrank.is_empty());
+  /// assert!(delta.grew.is_empty());
+  /// ```
+  pub fn diff(earlier: &Self, later: &Self) -> GapDelta<T> {
+    let mut delta = GapDelta {
+      closed: Vec::new(),
+      opened: Vec::new(),
+      shrank: Vec::new(),
+      grew: Vec::new(),
+    };
+
+    let mut i = 0;
+    let mut j = 0;
+    // Whether the current earlier and later gap, respectively, overlap
+    // with any gap of the other snapshot.
+    let mut overlapped = (false, false);
+
+    while i < earlier.len() || j < later.len() {
+      match (earlier.gaps.get(i), later.gaps.get(j)) {
+        (Some(old), Some(new)) => {
+          if overlap((old.start(), old.end()), (new.start(), new.end())) {
+            overlapped = (true, true);
+            if old != new {
+              if !contains((new.start(), new.end()), (old.start(), old.end())) {
+                delta.shrank.push((old.clone(), new.clone()));
+              }
+              if !contains((old.start(), old.end()), (new.start(), new.end())) {
+                delta.grew.push((old.clone(), new.clone()));
+              }
+            }
+          }
+
+          if end_lt_end(old.end(), new.end()) {
+            if !overlapped.0 {
+              delta.closed.push(old.clone());
+            }
+            i += 1;
+            overlapped.0 = false;
+          } else {
+            if !overlapped.1 {
+              delta.opened.push(new.clone());
+            }
+            j += 1;
+            overlapped.1 = false;
+          }
+        },
+        (Some(old), None) => {
+          if !overlapped.0 {
+            delta.closed.push(old.clone());
+          }
+          i += 1;
+          overlapped.0 = false;
+        },
+        (None, Some(new)) => {
+          if !overlapped.1 {
+            delta.opened.push(new.clone());
+          }
+          j += 1;
+          overlapped.1 = false;
+        },
+        (None, None) => unreachable!(),
+      }
+    }
+    delta
+  }
+
+  /// Retrieve the total number of values missing, i.e., the number of
+  /// values in all gaps combined.
+  ///
+  /// `None` is returned if any of the gaps is unbounded or if the
+  /// count can not be represented as `usize`.
+  pub fn total_missing(&self) -> Option<usize>
+  where
+    T: Distance,
+  {
+    self.gaps.iter().try_fold(0usize, |count, gap| {
+      count.checked_add(len(gap.start(), gap.end())?)
+    })
+  }
+}
+
+impl<T> PartialEq for GapList<T>
+where
+  T: Clone + Inc + PartialEq,
+{
+  fn eq(&self, other: &Self) -> bool {
+    self.gaps.len() == other.gaps.len()
+      && self
+        .gaps
+        .iter()
+        .zip(other.gaps.iter())
+        .all(|(gap1, gap2)| canonical(gap1) == canonical(gap2))
+  }
+}
+
+impl<T> Eq for GapList<T> where T: Clone + Inc + Eq {}
+
+impl<T> Hash for GapList<T>
+where
+  T: Clone + Inc + Hash,
+{
+  fn hash<H>(&self, state: &mut H)
+  where
+    H: Hasher,
+  {
+    self.gaps.len().hash(state);
+    self.gaps.iter().for_each(|gap| canonical(gap).hash(state))
+  }
+}
+
+impl<T> Default for GapList<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T> FromIterator<Gap<T>> for GapList<T>
+where
+  T: Clone + Ord + Inc,
+{
+  fn from_iter<I>(iter: I) -> Self
+  where
+    I: IntoIterator<Item = Gap<T>>,
+  {
+    let mut gaps = iter.into_iter().collect::<Vec<_>>();
+    // The sort is stable and linear for already sorted input, as
+    // produced by `GapIter`.
+    gaps.sort_by(|gap1, gap2| {
+      let (start1, start2) = (gap1.start(), gap2.start());
+      match (
+        lower_le_lower(start1, start2),
+        lower_le_lower(start2, start1),
+      ) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Less,
+        (false, _) => Ordering::Greater,
+      }
+    });
+
+    let gaps = Union::new(gaps.into_iter(), empty()).collect();
+    Self { gaps }
+  }
+}
+
+/// A `GapList` is serialized as a sequence of gaps.
+#[cfg(feature = "serde")]
+impl<T> Serialize for GapList<T>
+where
+  T: Serialize,
+{
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    self.gaps.serialize(serializer)
+  }
+}
+
+/// A `GapList` is deserialized from a sequence of gaps. The gaps are
+/// sorted and merged, just as when collecting a `GapList` from an
+/// iterator.
+#[cfg(feature = "serde")]
+impl<'de, T> Deserialize<'de> for GapList<T>
+where
+  T: Clone + Ord + Inc + Deserialize<'de>,
+{
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    let gaps = Vec::<Gap<T>>::deserialize(deserializer)?;
+    Ok(Self::from_iter(gaps))
+  }
+}
+
+impl<T> BitOr<&GapList<T>> for &GapList<T>
+where
+  T: Clone + Ord + Inc,
+{
+  type Output = GapList<T>;
+
+  /// Create the union of two lists of gaps.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use gaps::Gap;
+  /// # use gaps::GapList;
+  ///
+  /// let a = vec![Gap::new(Bound::Included(0), Bound::Excluded(5))].into_iter().collect::<GapList<_>>();
+  /// let b = vec![Gap::new(Bound::Included(5), Bound::Excluded(8))].into_iter().collect::<GapList<_>>();
+  /// let union = &a | &b;
+  /// assert_eq!(union.as_slice(), &[Gap::new(Bound::Included(0), Bound::Excluded(8))]);
+  /// ```
+  fn bitor(self, other: &GapList<T>) -> GapList<T> {
+    self.union(other)
+  }
+}
+
+impl<T> BitAnd<&GapList<T>> for &GapList<T>
+where
+  T: Clone + Ord + Inc,
+{
+  type Output = GapList<T>;
+
+  /// Create the intersection of two lists of gaps.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use gaps::Gap;
+  /// # use gaps::GapList;
+  ///
+  /// let a = vec![Gap::new(Bound::Included(0), Bound::Excluded(5))].into_iter().collect::<GapList<_>>();
+  /// let b = vec![Gap::new(Bound::Included(3), Bound::Excluded(8))].into_iter().collect::<GapList<_>>();
+  /// let intersection = &a & &b;
+  /// assert_eq!(intersection.as_slice(), &[Gap::new(Bound::Included(3), Bound::Excluded(5))]);
+  /// ```
+  fn bitand(self, other: &GapList<T>) -> GapList<T> {
+    self.intersection(other)
+  }
+}
+
+impl<T> Sub<&GapList<T>> for &GapList<T>
+where
+  T: Clone + Ord + Inc,
+{
+  type Output = GapList<T>;
+
+  /// Create the difference of two lists of gaps.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use gaps::Gap;
+  /// # use gaps::GapList;
+  ///
+  /// let a = vec![Gap::new(Bound::Included(0), Bound::Excluded(5))].into_iter().collect::<GapList<_>>();
+  /// let b = vec![Gap::new(Bound::Included(3), Bound::Excluded(8))].into_iter().collect::<GapList<_>>();
+  /// let difference = &a - &b;
+  /// assert_eq!(difference.as_slice(), &[Gap::new(Bound::Included(0), Bound::Excluded(3))]);
+  /// ```
+  fn sub(self, other: &GapList<T>) -> GapList<T> {
+    self.difference(other)
+  }
+}
+
+impl<T> BitXor<&GapList<T>> for &GapList<T>
+where
+  T: Clone + Ord + Inc,
+{
+  type Output = GapList<T>;
+
+  /// Create the symmetric difference of two lists of gaps.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use gaps::Gap;
+  /// # use gaps::GapList;
+  ///
+  /// let a = vec![Gap::new(Bound::Included(0), Bound::Excluded(5))].into_iter().collect::<GapList<_>>();
+  /// let b = vec![Gap::new(Bound::Included(3), Bound::Excluded(8))].into_iter().collect::<GapList<_>>();
+  /// let symmetric_difference = &a ^ &b;
+  /// assert_eq!(
+  ///   symmetric_difference.as_slice(),
+  ///   &[
+  ///     Gap::new(Bound::Included(0), Bound::Excluded(3)),
+  ///     Gap::new(Bound::Included(5), Bound::Excluded(8)),
+  ///   ],
+  /// );
+  /// ```
+  fn bitxor(self, other: &GapList<T>) -> GapList<T> {
+    self.symmetric_difference(other)
+  }
+}
+
+impl<T> IntoIterator for GapList<T> {
+  type Item = Gap<T>;
+  type IntoIter = vec::IntoIter<Gap<T>>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.gaps.into_iter()
+  }
+}
+
+impl<'l, T> IntoIterator for &'l GapList<T> {
+  type Item = &'l Gap<T>;
+  type IntoIter = slice::Iter<'l, Gap<T>>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.gaps.iter()
+  }
+}
@@ -0,0 +1,226 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::collections::btree_map::Range as BTreeMapRange;
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+use std::iter::FusedIterator;
+use std::iter::Peekable;
+use std::ops::Bound;
+use std::ops::Bound::Excluded;
+use std::ops::RangeBounds;
+
+use crate::bounds::bounds;
+use crate::Gap;
+use crate::GapIter;
+use crate::Gappable as _;
+use crate::Inc;
+use crate::RangeGappable as _;
+use crate::RangeIter;
+
+
+/// An iterator over the keys of a map range whose values satisfy a
+/// predicate.
+pub struct Matching<'s, K, V, P> {
+  /// The iterator over the map's entries.
+  iter: BTreeMapRange<'s, K, V>,
+  /// The predicate deciding which entries count as present.
+  pred: P,
+}
+
+impl<K, V, P> Debug for Matching<'_, K, V, P>
+where
+  K: Debug,
+  V: Debug,
+{
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    f.debug_struct("Matching")
+      .field("iter", &self.iter)
+      .finish()
+  }
+}
+
+impl<'s, K, V, P> Iterator for Matching<'s, K, V, P>
+where
+  K: Clone,
+  P: FnMut(&V) -> bool,
+{
+  type Item = K;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let pred = &mut self.pred;
+    self
+      .iter
+      .find(|(_key, value)| pred(value))
+      .map(|(key, _value)| key.clone())
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (0, self.iter.size_hint().1)
+  }
+}
+
+impl<'s, K, V, P> FusedIterator for Matching<'s, K, V, P>
+where
+  K: Clone,
+  P: FnMut(&V) -> bool,
+{
+}
+
+
+/// A gap together with the map entries delimiting it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct NeighboredGap<'s, K, V> {
+  /// The entry directly preceding the gap, if any.
+  pub before: Option<(&'s K, &'s V)>,
+  /// The gap itself.
+  pub gap: Gap<K>,
+  /// The entry directly following the gap, if any.
+  pub after: Option<(&'s K, &'s V)>,
+}
+
+
+/// An iterator over the gaps among the keys of a map, yielding each
+/// together with its neighboring entries.
+#[derive(Debug)]
+pub struct NeighborIter<'s, K, V>
+where
+  K: Clone + Ord,
+{
+  /// The iterator over the gaps among the map's keys.
+  gaps: GapIter<RangeIter<'s, BTreeMap<K, V>, K>, K>,
+  /// The iterator over the map's entries, kept in lockstep with the
+  /// gaps.
+  entries: Peekable<BTreeMapRange<'s, K, V>>,
+}
+
+impl<'s, K, V> NeighborIter<'s, K, V>
+where
+  K: Clone + Ord,
+{
+  /// Advance the entry iterator up to the entry delimiting the gap at
+  /// `bound`, if it exists.
+  fn neighbor(&mut self, bound: &Bound<K>) -> Option<(&'s K, &'s V)> {
+    match bound {
+      Excluded(key) => {
+        while self.entries.next_if(|(k, _v)| *k < key).is_some() {}
+        self.entries.peek().copied().filter(|(k, _v)| *k == key)
+      },
+      // A gap not bounded by an excluded key starts or ends at the
+      // boundary of the range of interest.
+      _ => None,
+    }
+  }
+}
+
+impl<'s, K, V> Iterator for NeighborIter<'s, K, V>
+where
+  K: Clone + Ord + Inc,
+{
+  type Item = NeighboredGap<'s, K, V>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let gap = self.gaps.next()?;
+    let before = self.neighbor(gap.start());
+    let after = self.neighbor(gap.end());
+    Some(NeighboredGap { before, gap, after })
+  }
+}
+
+impl<'s, K, V> FusedIterator for NeighborIter<'s, K, V> where K: Clone + Ord + Inc {}
+
+
+/// An extension trait providing gap detection functionality specific
+/// to maps, taking into account the values associated with keys.
+pub trait MapGappable<'s, K, V> {
+  /// Create a new [`GapIter`] that yields ranges identifying the gaps
+  /// among the map's keys in a certain range, treating keys whose value
+  /// does not satisfy `pred` as absent.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use maplit::btreemap;
+  /// # use gaps::Gap;
+  /// # use gaps::MapGappable as _;
+  ///
+  /// #[derive(PartialEq)]
+  /// enum Status {
+  ///   Pending,
+  ///   Complete,
+  /// }
+  ///
+  /// let chunks = btreemap! {
+  ///   0 => Status::Complete,
+  ///   1 => Status::Pending,
+  ///   2 => Status::Complete,
+  /// };
+  /// let mut gaps = chunks.gaps_where(0..4, |status| *status == Status::Complete);
+  /// assert_eq!(gaps.next(), Some(Gap::new(Bound::Excluded(0), Bound::Excluded(2))));
+  /// assert_eq!(gaps.next(), Some(Gap::new(Bound::Excluded(2), Bound::Excluded(4))));
+  /// assert_eq!(gaps.next(), None);
+  /// ```
+  fn gaps_where<R, P>(&'s self, range: R, pred: P) -> GapIter<Matching<'s, K, V, P>, K>
+  where
+    R: RangeBounds<K>,
+    P: FnMut(&V) -> bool;
+
+  /// Create a new iterator that yields the gaps among the map's keys
+  /// in a certain range, each together with the entries directly
+  /// preceding and following it.
+  ///
+  /// Only entries inside of `range` are reported as neighbors.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use maplit::btreemap;
+  /// # use gaps::Gap;
+  /// # use gaps::MapGappable as _;
+  ///
+  /// let checkpoints = btreemap! {1 => "a", 4 => "b"};
+  /// let mut gaps = checkpoints.gaps_with_neighbors(1..=5);
+  /// let gap = gaps.next().unwrap();
+  /// assert_eq!(gap.before, Some((&1, &"a")));
+  /// assert_eq!(gap.gap, Gap::new(Bound::Excluded(1), Bound::Excluded(4)));
+  /// assert_eq!(gap.after, Some((&4, &"b")));
+  ///
+  /// let gap = gaps.next().unwrap();
+  /// assert_eq!(gap.before, Some((&4, &"b")));
+  /// assert_eq!(gap.after, None);
+  /// assert_eq!(gaps.next(), None);
+  /// ```
+  fn gaps_with_neighbors<R>(&'s self, range: R) -> NeighborIter<'s, K, V>
+  where
+    R: RangeBounds<K>,
+    K: Clone + Ord;
+}
+
+impl<'s, K, V> MapGappable<'s, K, V> for BTreeMap<K, V>
+where
+  K: Clone + Ord + Inc,
+{
+  fn gaps_where<R, P>(&'s self, range: R, pred: P) -> GapIter<Matching<'s, K, V, P>, K>
+  where
+    R: RangeBounds<K>,
+    P: FnMut(&V) -> bool,
+  {
+    let matching = Matching {
+      iter: self.range(bounds(&range)),
+      pred,
+    };
+    matching.gaps(range)
+  }
+
+  fn gaps_with_neighbors<R>(&'s self, range: R) -> NeighborIter<'s, K, V>
+  where
+    R: RangeBounds<K>,
+    K: Clone + Ord,
+  {
+    NeighborIter {
+      entries: self.range(bounds(&range)).peekable(),
+      gaps: self.gaps(range),
+    }
+  }
+}
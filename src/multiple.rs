@@ -0,0 +1,89 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+
+/// A trait representing the capability to round a value to a multiple
+/// of a (positive) step.
+///
+/// All methods return `None` if the result is not representable.
+pub trait Multiple: Sized {
+  /// Find the smallest multiple of `step` that is greater than or
+  /// equal to `self`.
+  fn round_up(self, step: Self) -> Option<Self>;
+
+  /// Find the largest multiple of `step` that is less than or equal to
+  /// `self`.
+  fn round_down(self, step: Self) -> Option<Self>;
+
+  /// Find the smallest multiple of `step` that is greater than `self`.
+  fn next_multiple(self, step: Self) -> Option<Self>;
+
+  /// Find the largest multiple of `step` that is less than `self`.
+  fn prev_multiple(self, step: Self) -> Option<Self>;
+}
+
+macro_rules! multiple {
+  ( $t:ty ) => {
+    impl Multiple for $t {
+      fn round_up(self, step: Self) -> Option<Self> {
+        match self.rem_euclid(step) {
+          0 => Some(self),
+          rem => self.checked_add(step - rem),
+        }
+      }
+
+      fn round_down(self, step: Self) -> Option<Self> {
+        self.checked_sub(self.rem_euclid(step))
+      }
+
+      fn next_multiple(self, step: Self) -> Option<Self> {
+        self.round_down(step)?.checked_add(step)
+      }
+
+      fn prev_multiple(self, step: Self) -> Option<Self> {
+        self.round_up(step)?.checked_sub(step)
+      }
+    }
+  };
+}
+
+multiple!(u8);
+multiple!(i8);
+multiple!(u16);
+multiple!(i16);
+multiple!(u32);
+multiple!(i32);
+multiple!(u64);
+multiple!(i64);
+multiple!(u128);
+multiple!(i128);
+multiple!(usize);
+multiple!(isize);
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+
+  #[test]
+  fn rounding() {
+    assert_eq!(7u8.round_up(4), Some(8));
+    assert_eq!(8u8.round_up(4), Some(8));
+    assert_eq!(255u8.round_up(4), None);
+    assert_eq!(7u8.round_down(4), Some(4));
+    assert_eq!(8u8.round_down(4), Some(8));
+    assert_eq!(8u8.next_multiple(4), Some(12));
+    assert_eq!(7u8.next_multiple(4), Some(8));
+    assert_eq!(8u8.prev_multiple(4), Some(4));
+    assert_eq!(9u8.prev_multiple(4), Some(8));
+    assert_eq!(3u8.prev_multiple(4), Some(0));
+    assert_eq!(0u8.prev_multiple(4), None);
+
+    assert_eq!((-5i32).round_up(10), Some(0));
+    assert_eq!((-5i32).round_down(10), Some(-10));
+    assert_eq!((-10i32).next_multiple(10), Some(0));
+    assert_eq!((-10i32).prev_multiple(10), Some(-20));
+    assert_eq!(i8::MIN.round_down(3), None);
+  }
+}
@@ -0,0 +1,63 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::ops::Add;
+use std::ops::Sub;
+
+use num_traits::CheckedAdd;
+use num_traits::CheckedSub;
+use num_traits::One;
+
+use crate::Dec;
+use crate::Inc;
+
+
+/// A wrapper making any numeric type implementing the relevant
+/// `num-traits` traits usable for gap detection.
+///
+/// A blanket implementation of [`Inc`] for all such types is not
+/// possible, as it would conflict with the implementations for
+/// primitive integers and other foreign types provided by this crate.
+///
+/// ```rust
+/// use std::ops::Bound;
+/// # use maplit::btreeset;
+/// # use gaps::Gap;
+/// # use gaps::Numeric;
+/// # use gaps::RangeGappable as _;
+///
+/// let set = btreeset!{Numeric(1u32), Numeric(4)};
+/// let mut gaps = set.gaps(Numeric(0)..Numeric(6));
+/// assert_eq!(gaps.next(), Some(Gap::new(Bound::Included(Numeric(0)), Bound::Excluded(Numeric(1)))));
+/// assert_eq!(gaps.next(), Some(Gap::new(Bound::Excluded(Numeric(1)), Bound::Excluded(Numeric(4)))));
+/// assert_eq!(gaps.next(), Some(Gap::new(Bound::Excluded(Numeric(4)), Bound::Excluded(Numeric(6)))));
+/// assert_eq!(gaps.next(), None);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Numeric<T>(pub T);
+
+impl<T> Inc for Numeric<T>
+where
+  T: Add<Output = T> + CheckedAdd + One,
+{
+  fn inc(self) -> Self {
+    Self(self.0 + T::one())
+  }
+
+  fn checked_inc(self) -> Option<Self> {
+    self.0.checked_add(&T::one()).map(Self)
+  }
+}
+
+impl<T> Dec for Numeric<T>
+where
+  T: Sub<Output = T> + CheckedSub + One,
+{
+  fn dec(self) -> Self {
+    Self(self.0 - T::one())
+  }
+
+  fn checked_dec(self) -> Option<Self> {
+    self.0.checked_sub(&T::one()).map(Self)
+  }
+}
@@ -0,0 +1,291 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+use std::iter::FusedIterator;
+use std::ops::Bound;
+use std::ops::Bound::Excluded;
+use std::ops::Bound::Included;
+use std::ops::Bound::Unbounded;
+use std::ops::RangeBounds;
+
+use crate::bounds::bounds;
+use crate::bounds::resolve;
+use crate::Domain;
+use crate::Gap;
+use crate::Inc;
+use crate::Step;
+
+
+/// The type of a function finding the last value of the run of values
+/// sharing the presence of the given one.
+type ScanFn<T, P> = fn(&mut P, T, &Bound<T>, bool) -> T;
+
+
+/// Check whether `value` does not exceed the end of a range.
+fn within<T>(value: &T, end: &Bound<T>) -> bool
+where
+  T: Ord,
+{
+  match end {
+    Included(end) => value <= end,
+    Excluded(end) => value < end,
+    Unbounded => true,
+  }
+}
+
+/// Retrieve the value following `value`, if it is still in range.
+fn following<T>(value: T, end: &Bound<T>) -> Option<T>
+where
+  T: Ord + Inc,
+{
+  value.checked_inc().filter(|value| within(value, end))
+}
+
+/// Find the last value of the run starting at `value`, probing values
+/// one by one.
+fn scan_linear<T, P>(pred: &mut P, mut value: T, end: &Bound<T>, present: bool) -> T
+where
+  T: Clone + Ord + Inc,
+  P: FnMut(&T) -> bool,
+{
+  while let Some(next) = following(value.clone(), end) {
+    if pred(&next) != present {
+      break
+    }
+    value = next;
+  }
+  value
+}
+
+/// Find the last value of the run starting at `value`, probing values
+/// at exponentially growing distances and then bisecting.
+fn scan_galloping<T, P>(pred: &mut P, mut value: T, end: &Bound<T>, present: bool) -> T
+where
+  T: Clone + Ord + Step,
+  P: FnMut(&T) -> bool,
+{
+  let mut step = 1usize;
+  loop {
+    // The number of values following `value` that are still in range.
+    let remaining = match end {
+      Included(end) => value.clone().distance(end.clone()).unwrap_or(usize::MAX),
+      Excluded(end) => value
+        .clone()
+        .distance(end.clone())
+        .map_or(usize::MAX, |distance| distance - 1),
+      Unbounded => usize::MAX,
+    };
+    let n = step.min(remaining);
+    let probe = match value.clone().forward(n) {
+      Some(probe) if n > 0 => probe,
+      _ => return value,
+    };
+
+    if pred(&probe) == present {
+      value = probe;
+      step = step.saturating_mul(2);
+      continue
+    }
+
+    // The end of the run lies somewhere before `probe`.
+    let mut distance = n;
+    while distance > 1 {
+      let half = distance / 2;
+      let mid = match value.clone().forward(half) {
+        Some(mid) => mid,
+        None => break,
+      };
+      if pred(&mid) == present {
+        value = mid;
+        distance -= half;
+      } else {
+        distance = half;
+      }
+    }
+    return value
+  }
+}
+
+
+/// An iterator over the gaps in a range as determined by probing a
+/// membership predicate.
+///
+/// Objects of this type are created by [`gaps_by`].
+pub struct ProbeGapIter<T, P> {
+  /// The predicate reporting whether a value is present.
+  pred: P,
+  /// The next value to inspect, if any.
+  next: Option<T>,
+  /// The end of the range of interest.
+  end: Bound<T>,
+  /// The function used for finding the end of runs.
+  scan: ScanFn<T, P>,
+}
+
+impl<T, P> ProbeGapIter<T, P>
+where
+  T: Clone + Ord + Step,
+  P: FnMut(&T) -> bool,
+{
+  /// Find the ends of runs of present and missing values by means of a
+  /// galloping search, instead of probing each value.
+  ///
+  /// The search probes values at exponentially growing distances and
+  /// bisects once it found one of differing presence. It is assumed
+  /// that all values between two probed ones of equal presence share
+  /// it. Runs shorter than the distance between probes may hence be
+  /// missed, but long runs are traversed using a logarithmic number of
+  /// probes.
+  ///
+  /// ```rust
+  /// use std::cell::Cell;
+  /// use std::ops::Bound;
+  /// # use gaps::Gap;
+  ///
+  /// let probes = Cell::new(0);
+  /// let is_present = |value: &u64| {
+  ///   probes.set(probes.get() + 1);
+  ///   !(1_000..2_000).contains(value)
+  /// };
+  /// let mut gaps = gaps::gaps_by(0..1_000_000, is_present).galloping();
+  /// assert_eq!(gaps.next(), Some(Gap::new(Bound::Included(1_000), Bound::Included(1_999))));
+  /// assert_eq!(gaps.next(), None);
+  /// assert!(probes.get() < 100);
+  /// ```
+  pub fn galloping(self) -> Self {
+    Self {
+      scan: scan_galloping,
+      ..self
+    }
+  }
+}
+
+impl<T, P> Debug for ProbeGapIter<T, P>
+where
+  T: Debug,
+{
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    f.debug_struct("ProbeGapIter")
+      .field("next", &self.next)
+      .field("end", &self.end)
+      .finish()
+  }
+}
+
+impl<T, P> Iterator for ProbeGapIter<T, P>
+where
+  T: Clone + Ord + Inc,
+  P: FnMut(&T) -> bool,
+{
+  type Item = Gap<T>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let mut value = self.next.take()?;
+    if (self.pred)(&value) {
+      let last = (self.scan)(&mut self.pred, value, &self.end, true);
+      value = following(last, &self.end)?;
+    }
+
+    let first = value.clone();
+    let last = (self.scan)(&mut self.pred, value, &self.end, false);
+    self.next = following(last.clone(), &self.end);
+    Some(Gap::new(Included(first), Included(last)))
+  }
+}
+
+impl<T, P> FusedIterator for ProbeGapIter<T, P>
+where
+  T: Clone + Ord + Inc,
+  P: FnMut(&T) -> bool,
+{
+}
+
+
+/// Create an iterator over the gaps in a range, probing the provided
+/// predicate for whether a value is present.
+///
+/// This function is meant for cases in which membership is answered by
+/// an external index or service, rather than by an in-memory
+/// collection. By default each value in the range is probed once. Use
+/// [`ProbeGapIter::galloping`] to probe fewer values when presence
+/// changes rarely.
+///
+/// Gaps are reported with inclusive bounds.
+///
+/// ```rust
+/// use std::ops::Bound;
+/// # use gaps::Gap;
+///
+/// let blocks = [true, true, false, true, false, false];
+/// let mut gaps = gaps::gaps_by(0..6, |i: &usize| blocks[*i]);
+/// assert_eq!(gaps.next(), Some(Gap::new(Bound::Included(2), Bound::Included(2))));
+/// assert_eq!(gaps.next(), Some(Gap::new(Bound::Included(4), Bound::Included(5))));
+/// assert_eq!(gaps.next(), None);
+/// ```
+pub fn gaps_by<R, T, P>(range: R, pred: P) -> ProbeGapIter<T, P>
+where
+  R: RangeBounds<T>,
+  T: Clone + Ord + Inc + Domain,
+  P: FnMut(&T) -> bool,
+{
+  let (start, end) = resolve(bounds(&range));
+  let next = match start {
+    Included(start) => Some(start),
+    Excluded(start) => start.checked_inc(),
+    Unbounded => unreachable!(),
+  };
+
+  ProbeGapIter {
+    pred,
+    next: next.filter(|next| within(next, &end)),
+    end,
+    scan: scan_linear,
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+
+  /// Check that gaps are reported correctly at the boundaries of a
+  /// type's domain.
+  #[test]
+  fn domain_boundaries() {
+    let gaps = gaps_by(.., |value: &u8| (10..=250).contains(value)).collect::<Vec<_>>();
+    let expected = vec![
+      Gap::new(Included(0), Included(9)),
+      Gap::new(Included(251), Included(255)),
+    ];
+    assert_eq!(gaps, expected);
+
+    let gaps = gaps_by(.., |_: &u8| false).collect::<Vec<_>>();
+    let expected = vec![Gap::new(Included(0), Included(255))];
+    assert_eq!(gaps, expected);
+  }
+
+  /// Check that galloping search finds the same gaps as linear
+  /// probing for runs longer than the probing distance.
+  #[test]
+  fn galloping_search() {
+    let pred = |value: &u32| *value / 7 % 2 == 0;
+    for range in [(Included(0), Excluded(100)), (Excluded(3), Included(98))] {
+      let linear = gaps_by(range, pred).collect::<Vec<_>>();
+      let galloping = gaps_by(range, pred).galloping().collect::<Vec<_>>();
+      assert_eq!(linear, galloping);
+    }
+
+    let gaps = gaps_by(.., |_: &u8| true).galloping().collect::<Vec<_>>();
+    assert_eq!(gaps, Vec::new());
+
+    let gaps = gaps_by(..=255, |value: &u8| *value < 200)
+      .galloping()
+      .collect::<Vec<_>>();
+    let expected = vec![Gap::new(Included(200), Included(255))];
+    assert_eq!(gaps, expected);
+  }
+}
@@ -0,0 +1,100 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::fmt::Debug;
+use std::iter::FromIterator as _;
+use std::ops::Bound;
+use std::ops::Bound::Excluded;
+use std::ops::Bound::Included;
+use std::ops::Bound::Unbounded;
+
+use proptest::arbitrary::any;
+use proptest::arbitrary::any_with;
+use proptest::arbitrary::Arbitrary;
+use proptest::collection::vec;
+use proptest::prop_oneof;
+use proptest::strategy::BoxedStrategy;
+use proptest::strategy::Just;
+use proptest::strategy::Strategy;
+
+use crate::Gap;
+use crate::GapFixture;
+use crate::GapList;
+use crate::Inc;
+
+
+/// The maximum number of values in the range of a generated
+/// `GapFixture`.
+const MAX_FIXTURE_LEN: usize = 64;
+/// The maximum number of gaps generated for a `GapList`.
+const MAX_LIST_LEN: usize = 8;
+
+
+/// Create a strategy for a bound on `value`.
+fn bound<T>(value: T) -> impl Strategy<Value = Bound<T>>
+where
+  T: Clone + Debug,
+{
+  prop_oneof![
+    Just(Included(value.clone())),
+    Just(Excluded(value)),
+    Just(Unbounded),
+  ]
+}
+
+
+/// Generated gaps are never empty, matching the gaps produced by this
+/// crate.
+impl<T> Arbitrary for Gap<T>
+where
+  T: Arbitrary + Clone + Ord + Inc + 'static,
+  T::Parameters: Clone,
+{
+  type Parameters = T::Parameters;
+  type Strategy = BoxedStrategy<Self>;
+
+  fn arbitrary_with(params: Self::Parameters) -> Self::Strategy {
+    (any_with::<T>(params.clone()), any_with::<T>(params))
+      .prop_flat_map(|(a, b)| {
+        let (lo, hi) = if a <= b { (a, b) } else { (b, a) };
+        (bound(lo), bound(hi))
+      })
+      .prop_map(|(start, end)| Gap::new(start, end))
+      .prop_filter("gap must not be empty", |gap| !gap.is_empty())
+      .boxed()
+  }
+}
+
+impl<T> Arbitrary for GapList<T>
+where
+  T: Arbitrary + Clone + Ord + Inc + 'static,
+  T::Parameters: Clone,
+{
+  type Parameters = T::Parameters;
+  type Strategy = BoxedStrategy<Self>;
+
+  fn arbitrary_with(params: Self::Parameters) -> Self::Strategy {
+    vec(any_with::<Gap<T>>(params), 0..=MAX_LIST_LEN)
+      .prop_map(GapList::from_iter)
+      .boxed()
+  }
+}
+
+/// Shrinking removes values and shortens the range, while keeping
+/// `values` sorted.
+impl<T> Arbitrary for GapFixture<T>
+where
+  T: Arbitrary + Clone + Ord + Inc + 'static,
+{
+  type Parameters = T::Parameters;
+  type Strategy = BoxedStrategy<Self>;
+
+  fn arbitrary_with(params: Self::Parameters) -> Self::Strategy {
+    (
+      any_with::<T>(params),
+      vec(any::<bool>(), 1..=MAX_FIXTURE_LEN),
+    )
+      .prop_map(|(start, mask)| GapFixture::from_mask(start, mask))
+      .boxed()
+  }
+}
@@ -0,0 +1,277 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::cmp::Ordering;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::iter::once;
+use std::ops::Bound;
+
+use crate::bounds::len;
+use crate::Chunks;
+use crate::Distance;
+use crate::Gap;
+use crate::Step;
+
+
+/// Create a key for ordering gaps by their length, with gaps of
+/// unknown length (e.g., because they are unbounded) being considered
+/// larger than all others.
+fn len_key<T>(gap: &Gap<T>) -> (bool, usize)
+where
+  T: Clone + Ord + Distance,
+{
+  match len(gap.start(), gap.end()) {
+    Some(len) => (false, len),
+    None => (true, 0),
+  }
+}
+
+
+/// Find the largest of the gaps yielded by an iterator.
+///
+/// If there are multiple largest gaps the first one is reported.
+pub(crate) fn largest<I, T>(gaps: I) -> Option<Gap<T>>
+where
+  I: Iterator<Item = Gap<T>>,
+  T: Clone + Ord + Distance,
+{
+  gaps
+    .fold(None, |largest, gap| {
+      let key = len_key(&gap);
+      match largest {
+        Some((largest_key, _)) if largest_key >= key => largest,
+        _ => Some((key, gap)),
+      }
+    })
+    .map(|(_, gap)| gap)
+}
+
+
+/// A gap along with the information required for ranking it by length.
+struct Ranked<T> {
+  /// The length based key of the gap.
+  key: (bool, usize),
+  /// The position of the gap in the sequence of all gaps.
+  index: usize,
+  /// The gap itself.
+  gap: Gap<T>,
+}
+
+impl<T> Ranked<T> {
+  /// Create a key by which to order ranked gaps, with earlier gaps
+  /// being considered larger than later ones of the same length.
+  fn rank(&self) -> ((bool, usize), Reverse<usize>) {
+    (self.key, Reverse(self.index))
+  }
+}
+
+impl<T> PartialEq for Ranked<T> {
+  fn eq(&self, other: &Self) -> bool {
+    self.rank() == other.rank()
+  }
+}
+
+impl<T> Eq for Ranked<T> {}
+
+impl<T> PartialOrd for Ranked<T> {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl<T> Ord for Ranked<T> {
+  fn cmp(&self, other: &Self) -> Ordering {
+    self.rank().cmp(&other.rank())
+  }
+}
+
+
+/// Find the `k` largest of the gaps yielded by an iterator, largest
+/// first.
+///
+/// Gaps of the same length are reported in the order in which they
+/// were yielded.
+pub(crate) fn k_largest<I, T>(gaps: I, k: usize) -> Vec<Gap<T>>
+where
+  I: Iterator<Item = Gap<T>>,
+  T: Clone + Ord + Distance,
+{
+  if k == 0 {
+    return Vec::new()
+  }
+
+  // A min-heap of the `k` largest gaps seen so far.
+  let mut heap = BinaryHeap::with_capacity(k + 1);
+  for (index, gap) in gaps.enumerate() {
+    heap.push(Reverse(Ranked {
+      key: len_key(&gap),
+      index,
+      gap,
+    }));
+
+    if heap.len() > k {
+      let _ = heap.pop();
+    }
+  }
+
+  heap
+    .into_sorted_vec()
+    .into_iter()
+    .map(|Reverse(ranked)| ranked.gap)
+    .collect()
+}
+
+
+/// Count the number of values inside the gaps yielded by an iterator.
+///
+/// `None` is returned if any gap is unbounded or the count can not be
+/// represented as `usize`.
+pub(crate) fn count<I, T>(mut gaps: I) -> Option<usize>
+where
+  I: Iterator<Item = Gap<T>>,
+  T: Clone + Ord + Distance,
+{
+  gaps.try_fold(0usize, |count, gap| {
+    count.checked_add(len(gap.start(), gap.end())?)
+  })
+}
+
+
+/// Summary statistics about the gaps in a range.
+///
+/// Objects of this type are created by
+/// [`Gappable::stats`][crate::Gappable::stats] and
+/// [`RangeGappable::stats`][crate::RangeGappable::stats].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct GapStats<T> {
+  /// The number of gaps.
+  pub count: usize,
+  /// The total number of missing values, or `None` if it is unknown
+  /// because a gap is unbounded or the number can not be represented
+  /// as `usize`.
+  pub missing: Option<usize>,
+  /// The length of the smallest gap, or `None` if there are no gaps or
+  /// the length is unknown.
+  pub min_len: Option<usize>,
+  /// The length of the largest gap, or `None` if there are no gaps or
+  /// the length is unknown.
+  pub max_len: Option<usize>,
+  /// The mean length of all gaps, or `None` if there are no gaps or the
+  /// total number of missing values is unknown.
+  pub mean_len: Option<f64>,
+  /// The largest gap, if any. If there are multiple largest gaps, the
+  /// first one is reported.
+  pub largest: Option<Gap<T>>,
+}
+
+
+/// Gather statistics about the gaps yielded by an iterator.
+pub(crate) fn stats<I, T>(gaps: I) -> GapStats<T>
+where
+  I: Iterator<Item = Gap<T>>,
+  T: Clone + Ord + Distance,
+{
+  let mut stats = GapStats {
+    count: 0,
+    missing: Some(0),
+    min_len: None,
+    max_len: None,
+    mean_len: None,
+    largest: None,
+  };
+  let mut min_key = None;
+  let mut max_key = None;
+
+  for gap in gaps {
+    let key = len_key(&gap);
+    stats.count += 1;
+    stats.missing = stats
+      .missing
+      .and_then(|missing| missing.checked_add(len(gap.start(), gap.end())?));
+
+    if min_key.map_or(true, |min_key| key < min_key) {
+      min_key = Some(key);
+    }
+    if max_key.map_or(true, |max_key| key > max_key) {
+      max_key = Some(key);
+      stats.largest = Some(gap);
+    }
+  }
+
+  let len = |key: Option<(bool, usize)>| match key? {
+    (false, len) => Some(len),
+    (true, _) => None,
+  };
+  stats.min_len = len(min_key);
+  stats.max_len = len(max_key);
+  stats.mean_len = match (stats.count, stats.missing) {
+    (0, _) => None,
+    (count, missing) => missing.map(|missing| missing as f64 / count as f64),
+  };
+  stats
+}
+
+
+/// Calculate the fraction of the range `start`..`end` that is not
+/// covered by the gaps yielded by an iterator.
+///
+/// `None` is returned if the number of values in the range is unknown.
+pub(crate) fn coverage<I, T>(start: &Bound<T>, end: &Bound<T>, gaps: I) -> Option<f64>
+where
+  I: Iterator<Item = Gap<T>>,
+  T: Clone + Ord + Distance,
+{
+  let total = len(start, end)?;
+  if total == 0 {
+    // An empty range is fully covered, in a way.
+    return Some(1.0)
+  }
+
+  let missing = count(gaps)?;
+  Some((total - missing) as f64 / total as f64)
+}
+
+
+/// The strategy to use when searching for a gap of a certain length.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Fit {
+  /// Pick the first gap that is large enough.
+  First,
+  /// Pick the smallest gap that is large enough.
+  Best,
+  /// Pick the largest gap.
+  Worst,
+}
+
+
+/// Find a gap of at least `len` values among those yielded by an
+/// iterator, using the provided strategy, and report its first `len`
+/// values.
+pub(crate) fn find<I, T>(gaps: I, len: usize, fit: Fit) -> Option<Gap<T>>
+where
+  I: Iterator<Item = Gap<T>>,
+  T: Clone + Ord + Step,
+{
+  let min_key = (false, len);
+  let mut gaps = gaps
+    .map(|gap| (len_key(&gap), gap))
+    .filter(|(key, _)| *key >= min_key);
+
+  let gap = match fit {
+    Fit::First => gaps.next(),
+    Fit::Best => gaps.fold(None, |best, (key, gap)| match best {
+      Some((best_key, _)) if best_key <= key => best,
+      _ => Some((key, gap)),
+    }),
+    Fit::Worst => gaps.fold(None, |worst, (key, gap)| match worst {
+      Some((worst_key, _)) if worst_key >= key => worst,
+      _ => Some((key, gap)),
+    }),
+  };
+
+  // The gap is known to be large enough, so we only need to skip to
+  // the end of its first `len` values.
+  gap.and_then(|(_, gap)| Chunks::new(once(gap), len).striding().next())
+}
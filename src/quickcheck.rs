@@ -0,0 +1,134 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::iter::FromIterator as _;
+use std::ops::Bound;
+use std::ops::Bound::Excluded;
+use std::ops::Bound::Included;
+use std::ops::Bound::Unbounded;
+
+use quickcheck::Arbitrary;
+use quickcheck::Gen;
+
+use crate::Gap;
+use crate::GapFixture;
+use crate::GapList;
+use crate::Inc;
+
+
+/// Create a bound of a randomly chosen kind on `value`.
+fn bound<T>(g: &mut Gen, value: T) -> Bound<T> {
+  match g.choose(&[0, 1, 2]) {
+    Some(0) => Included(value),
+    Some(1) => Excluded(value),
+    _ => Unbounded,
+  }
+}
+
+/// Shrink the value of a bound, keeping its kind.
+fn shrink_bound<T>(bound: &Bound<T>) -> Box<dyn Iterator<Item = Bound<T>>>
+where
+  T: Arbitrary,
+{
+  match bound {
+    Included(value) => Box::new(value.shrink().map(Included)),
+    Excluded(value) => Box::new(value.shrink().map(Excluded)),
+    Unbounded => Box::new(None.into_iter()),
+  }
+}
+
+/// Recover the mask a fixture was created from.
+fn mask<T>(fixture: &GapFixture<T>) -> Vec<bool>
+where
+  T: Clone + Ord + Inc,
+{
+  let mut values = fixture.values.iter().peekable();
+  let mut mask = Vec::new();
+  let mut next = Some(fixture.range.start().clone());
+
+  while let Some(value) = next {
+    mask.push(values.next_if_eq(&&value).is_some());
+    if &value == fixture.range.end() {
+      break
+    }
+    next = value.checked_inc();
+  }
+  mask
+}
+
+
+/// Generated gaps are never empty, matching the gaps produced by this
+/// crate. Shrinking reduces the values of the bounds, but never
+/// yields an empty gap.
+impl<T> Arbitrary for Gap<T>
+where
+  T: Arbitrary + Ord + Inc,
+{
+  fn arbitrary(g: &mut Gen) -> Self {
+    loop {
+      let a = T::arbitrary(g);
+      let b = T::arbitrary(g);
+      let (lo, hi) = if a <= b { (a, b) } else { (b, a) };
+      let gap = Gap::new(bound(g, lo), bound(g, hi));
+      if !gap.is_empty() {
+        break gap
+      }
+    }
+  }
+
+  fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+    let (start, end) = self.clone().into_bounds();
+    let starts = {
+      let end = end.clone();
+      shrink_bound(&start).map(move |start| Gap::new(start, end.clone()))
+    };
+    let ends = shrink_bound(&end).map(move |end| Gap::new(start.clone(), end));
+
+    Box::new(starts.chain(ends).filter(|gap| !gap.is_empty()))
+  }
+}
+
+impl<T> Arbitrary for GapList<T>
+where
+  T: Arbitrary + Clone + Ord + Inc,
+{
+  fn arbitrary(g: &mut Gen) -> Self {
+    Vec::<Gap<T>>::arbitrary(g).into_iter().collect()
+  }
+
+  fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+    Box::new(self.as_slice().to_vec().shrink().map(GapList::from_iter))
+  }
+}
+
+/// The range of a generated fixture spans up to [`Gen::size`] values.
+/// Shrinking removes values, shortens the range, and reduces its start,
+/// while keeping `values` sorted.
+impl<T> Arbitrary for GapFixture<T>
+where
+  T: Arbitrary + Ord + Inc,
+{
+  fn arbitrary(g: &mut Gen) -> Self {
+    let start = T::arbitrary(g);
+    let len = usize::arbitrary(g) % g.size().max(1) + 1;
+    let mask = (0..len).map(|_| bool::arbitrary(g)).collect::<Vec<_>>();
+    GapFixture::from_mask(start, mask)
+  }
+
+  fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+    let start = self.range.start().clone();
+    let mask = mask(self);
+    let masks = {
+      let start = start.clone();
+      mask
+        .shrink()
+        .filter(|mask| !mask.is_empty())
+        .map(move |mask| GapFixture::from_mask(start.clone(), mask))
+    };
+    let starts = start
+      .shrink()
+      .map(move |start| GapFixture::from_mask(start, mask.clone()));
+
+    Box::new(masks.chain(starts))
+  }
+}
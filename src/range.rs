@@ -0,0 +1,664 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! A module providing utility functionality for working with ranges.
+
+use std::ops::Bound;
+use std::ops::Bound::Excluded;
+use std::ops::Bound::Included;
+use std::ops::Bound::Unbounded;
+use std::ops::RangeBounds;
+
+pub use crate::bounds::bounds;
+pub use crate::bounds::resolve;
+
+use crate::bounds::contains;
+use crate::bounds::end_lt_end;
+use crate::bounds::flip;
+use crate::bounds::lower_le_lower;
+use crate::bounds::overlap;
+use crate::bounds::start_le_end;
+use crate::Distance;
+use crate::Inc;
+
+
+/// Comparison primitives for range bounds.
+///
+/// Comparisons of bounded bounds take into account that values are
+/// discrete, i.e., that, say, `Excluded(3)` and `Included(4)` denote
+/// the same start of a range of integers. An unbounded start lies
+/// before all values and an unbounded end after all of them.
+///
+/// Unbounded bounds are treated asymmetrically, though: an end
+/// including the maximum value of the type is considered equal to an
+/// unbounded end, as [`Inc`] can detect the maximum. No
+/// such minimum is known for starts, which is why an unbounded start
+/// is always considered less than a bounded one, even one including
+/// the minimum value of the type.
+///
+/// ```rust
+/// use std::ops::Bound;
+/// # use gaps::range::cmp::end_lt_end;
+/// # use gaps::range::cmp::start_le_end;
+/// # use gaps::range::cmp::start_le_start;
+/// # use gaps::range::cmp::start_lt_start;
+///
+/// assert!(start_le_start(&Bound::Included(4), &Bound::Excluded(3)));
+/// assert!(!start_le_end(&Bound::Excluded(3), &Bound::Excluded(4)));
+/// assert!(end_lt_end(&Bound::Excluded(4), &Bound::Included(4)));
+///
+/// assert!(start_le_start(&Bound::Unbounded, &Bound::Included(0)));
+/// assert!(start_le_end(&Bound::Unbounded, &Bound::Included(0)));
+/// assert!(end_lt_end(&Bound::Included(4), &Bound::Unbounded));
+///
+/// // An end including the maximum is equivalent to an unbounded one...
+/// assert!(!end_lt_end(&Bound::Included(u8::MAX), &Bound::Unbounded));
+/// // ... but a start including the minimum is not.
+/// assert!(start_lt_start(&Bound::Unbounded, &Bound::Included(u8::MIN)));
+/// ```
+pub mod cmp {
+  use std::ops::Bound;
+
+  use crate::bounds::lower_le_lower;
+  use crate::Inc;
+
+  pub use crate::bounds::end_lt_end;
+  pub use crate::bounds::start_le_end;
+
+  /// Check whether a "start" bound is less than another one.
+  ///
+  /// An unbounded start is less than every bounded one, including one
+  /// that includes the minimum value of `T`.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use gaps::range::cmp::start_lt_start;
+  ///
+  /// assert!(start_lt_start(&Bound::Included(1), &Bound::Excluded(1)));
+  /// assert!(!start_lt_start(&Bound::Excluded(0), &Bound::Included(1)));
+  /// assert!(start_lt_start(&Bound::Unbounded, &Bound::Included(i32::MIN)));
+  /// assert!(!start_lt_start(&Bound::Included(i32::MIN), &Bound::Unbounded));
+  /// assert!(!start_lt_start::<i32>(&Bound::Unbounded, &Bound::Unbounded));
+  /// ```
+  pub fn start_lt_start<T>(b1: &Bound<T>, b2: &Bound<T>) -> bool
+  where
+    T: Clone + Ord + Inc,
+  {
+    !start_le_start(b2, b1)
+  }
+
+  /// Check whether a "start" bound is less than or equal to another
+  /// one.
+  ///
+  /// An unbounded start is less than every bounded one, including one
+  /// that includes the minimum value of `T`.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use gaps::range::cmp::start_le_start;
+  ///
+  /// assert!(start_le_start(&Bound::Excluded(0), &Bound::Included(1)));
+  /// assert!(!start_le_start(&Bound::Included(2), &Bound::Excluded(0)));
+  /// assert!(start_le_start(&Bound::Unbounded, &Bound::Included(i32::MIN)));
+  /// assert!(!start_le_start(&Bound::Included(i32::MIN), &Bound::Unbounded));
+  /// assert!(start_le_start::<i32>(&Bound::Unbounded, &Bound::Unbounded));
+  /// ```
+  pub fn start_le_start<T>(b1: &Bound<T>, b2: &Bound<T>) -> bool
+  where
+    T: Clone + Ord + Inc,
+  {
+    lower_le_lower(b1, b2)
+  }
+}
+
+
+/// An extension trait for [`RangeBounds`] providing comparisons
+/// between ranges that take the discrete nature of the values into
+/// account.
+///
+/// ```rust
+/// # use gaps::range::RangeBoundsExt as _;
+///
+/// assert!((1..5).overlaps(&(4..=7)));
+/// assert!(!(1..5).overlaps(&(5..=7)));
+/// assert!((1..5).is_adjacent_to(&(5..=7)));
+/// assert!((1..=5).contains_range(&(2..6)));
+/// assert!((1..=5).contains_value(&5));
+/// ```
+pub trait RangeBoundsExt<T>: RangeBounds<T> {
+  /// Check whether the range has at least one value in common with
+  /// `other`.
+  fn overlaps<R>(&self, other: &R) -> bool
+  where
+    R: RangeBounds<T> + ?Sized,
+    T: Clone + Ord + Inc,
+  {
+    let (start1, end1) = bounds(self);
+    let (start2, end2) = bounds(other);
+    overlap((&start1, &end1), (&start2, &end2))
+  }
+
+  /// Check whether all values of `other` are part of the range.
+  ///
+  /// An empty `other` range is contained in every range.
+  fn contains_range<R>(&self, other: &R) -> bool
+  where
+    R: RangeBounds<T> + ?Sized,
+    T: Clone + Ord + Inc,
+  {
+    let (start1, end1) = bounds(self);
+    let (start2, end2) = bounds(other);
+    !start_le_end(&start2, &end2) || contains((&start1, &end1), (&start2, &end2))
+  }
+
+  /// Check whether `value` is part of the range.
+  fn contains_value(&self, value: &T) -> bool
+  where
+    T: Clone + Ord + Inc,
+  {
+    let value = Included(value.clone());
+    self.overlaps(&(value.clone(), value))
+  }
+
+  /// Check whether the range and `other` do not overlap, but directly
+  /// follow each other, i.e., there are no values in between them.
+  ///
+  /// Empty ranges are not adjacent to any range.
+  fn is_adjacent_to<R>(&self, other: &R) -> bool
+  where
+    R: RangeBounds<T> + ?Sized,
+    T: Clone + Ord + Inc,
+  {
+    let (start1, end1) = bounds(self);
+    let (start2, end2) = bounds(other);
+
+    if !start_le_end(&start1, &end1)
+      || !start_le_end(&start2, &end2)
+      || overlap((&start1, &end1), (&start2, &end2))
+    {
+      return false
+    }
+
+    // Check whether the start bound `start` denotes the value directly
+    // following the end bound `end`.
+    let follows = |end: &Bound<T>, start: &Bound<T>| {
+      let next = flip(end.clone());
+      !matches!(end, Unbounded) && lower_le_lower(&next, start) && lower_le_lower(start, &next)
+    };
+    follows(&end1, &start2) || follows(&end2, &start1)
+  }
+}
+
+impl<R, T> RangeBoundsExt<T> for R where R: RangeBounds<T> + ?Sized {}
+
+
+/// Check whether a range does not contain any values.
+///
+/// ```rust
+/// use std::ops::Bound;
+/// # use gaps::range::is_empty;
+///
+/// assert!(is_empty(&(Bound::Excluded(3), Bound::Excluded(4))));
+/// assert!(is_empty(&(5..3)));
+/// assert!(!is_empty(&(3..=3)));
+/// ```
+pub fn is_empty<R, T>(range: &R) -> bool
+where
+  R: RangeBounds<T> + ?Sized,
+  T: Clone + Ord + Inc,
+{
+  let (start, end) = bounds(range);
+  !start_le_end(&start, &end)
+}
+
+
+/// Determine the number of values in a range.
+///
+/// `None` is returned if the range is unbounded or if the distance
+/// between its first and last value can not be represented as
+/// `usize`. The length itself is computed as `u128`, so that a range
+/// spanning `usize::MAX + 1` values can still be reported.
+///
+/// ```rust
+/// use std::ops::Bound;
+/// # use gaps::range::len;
+///
+/// assert_eq!(len(&(Bound::Excluded(3), Bound::Included(7))), Some(4));
+/// assert_eq!(len(&(5..)), None);
+/// #[cfg(target_pointer_width = "64")]
+/// assert_eq!(len(&(0..=u64::MAX)), Some(1 << 64));
+/// #[cfg(target_pointer_width = "32")]
+/// assert_eq!(len(&(0..=u64::MAX)), None);
+/// ```
+pub fn len<R, T>(range: &R) -> Option<u128>
+where
+  R: RangeBounds<T> + ?Sized,
+  T: Clone + Ord + Distance,
+{
+  let (start, end) = bounds(range);
+  if matches!(start, Unbounded) || matches!(end, Unbounded) {
+    return None
+  }
+
+  if !start_le_end(&start, &end) {
+    return Some(0)
+  }
+
+  // The range is not empty, so incrementing an excluded start can not
+  // overflow.
+  let first = match start {
+    Included(start) => start,
+    Excluded(start) => start.inc(),
+    Unbounded => unreachable!(),
+  };
+
+  match end {
+    Included(end) => Some(first.distance(end)? as u128 + 1),
+    Excluded(end) => Some(first.distance(end)? as u128),
+    Unbounded => unreachable!(),
+  }
+}
+
+
+/// Convert a range into canonical form, with an included start and an
+/// excluded end bound.
+///
+/// Unbounded ends are kept as they are. An included end at the maximum
+/// value of `T` can not be expressed as an excluded one and is kept
+/// included. A range starting after the maximum value is converted
+/// into the empty range `(Included(max), Excluded(max))`.
+///
+/// ```rust
+/// use std::ops::Bound;
+/// # use gaps::range::normalize;
+///
+/// assert_eq!(
+///   normalize(&(Bound::Excluded(3), Bound::Included(7))),
+///   (Bound::Included(4), Bound::Excluded(8))
+/// );
+/// assert_eq!(
+///   normalize(&(Bound::Excluded(3), Bound::Included(u8::MAX))),
+///   (Bound::Included(4), Bound::Included(u8::MAX))
+/// );
+/// ```
+pub fn normalize<R, T>(range: &R) -> (Bound<T>, Bound<T>)
+where
+  R: RangeBounds<T> + ?Sized,
+  T: Clone + Inc,
+{
+  let (start, end) = bounds(range);
+  let start = match start {
+    Excluded(start) => match start.clone().checked_inc() {
+      Some(start) => Included(start),
+      None => return (Included(start.clone()), Excluded(start)),
+    },
+    start => start,
+  };
+  let end = match end {
+    Included(end) => match end.clone().checked_inc() {
+      Some(end) => Excluded(end),
+      None => Included(end),
+    },
+    end => end,
+  };
+  (start, end)
+}
+
+
+/// Compute the overlap of two ranges.
+///
+/// `None` is returned if the ranges do not have any value in common.
+///
+/// ```rust
+/// use std::ops::Bound;
+/// # use gaps::range::intersect;
+///
+/// assert_eq!(
+///   intersect(1..5, (Bound::Excluded(2), Bound::Unbounded)),
+///   Some((Bound::Excluded(2), Bound::Excluded(5)))
+/// );
+/// assert_eq!(intersect(1..5, 5..), None);
+/// ```
+pub fn intersect<A, B, T>(a: A, b: B) -> Option<(Bound<T>, Bound<T>)>
+where
+  A: RangeBounds<T>,
+  B: RangeBounds<T>,
+  T: Clone + Ord + Inc,
+{
+  let (start1, end1) = bounds(&a);
+  let (start2, end2) = bounds(&b);
+
+  let start = if lower_le_lower(&start1, &start2) {
+    start2
+  } else {
+    start1
+  };
+  let end = if end_lt_end(&end1, &end2) { end1 } else { end2 };
+
+  if start_le_end(&start, &end) {
+    Some((start, end))
+  } else {
+    None
+  }
+}
+
+
+/// Restrict a range to the portion that lies within `domain`.
+///
+/// Bounds of `inner` are preserved wherever they do not lie outside of
+/// `domain`. `None` is returned if `inner` and `domain` do not have any
+/// value in common.
+///
+/// ```rust
+/// use std::ops::Bound;
+/// # use gaps::range::clamp;
+///
+/// assert_eq!(
+///   clamp((Bound::Excluded(2), Bound::Unbounded), 0..10),
+///   Some((Bound::Excluded(2), Bound::Excluded(10)))
+/// );
+/// assert_eq!(clamp(10.., 0..10), None);
+/// ```
+pub fn clamp<R, D, T>(inner: R, domain: D) -> Option<(Bound<T>, Bound<T>)>
+where
+  R: RangeBounds<T>,
+  D: RangeBounds<T>,
+  T: Clone + Ord + Inc,
+{
+  // `intersect` prefers the bounds of its second argument if they
+  // denote the same value.
+  intersect(domain, inner)
+}
+
+
+/// Split a range at a value.
+///
+/// The first returned range contains all values of `range` that are
+/// less than `value`, the second one all that are greater than or
+/// equal to it. Empty parts are reported as `None`.
+///
+/// ```rust
+/// use std::ops::Bound;
+/// # use gaps::range::split_at;
+///
+/// assert_eq!(
+///   split_at(2..=9, 5),
+///   (
+///     Some((Bound::Included(2), Bound::Excluded(5))),
+///     Some((Bound::Included(5), Bound::Included(9))),
+///   )
+/// );
+/// assert_eq!(split_at(2..=9, 2), (None, Some((Bound::Included(2), Bound::Included(9)))));
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn split_at<R, T>(
+  range: R,
+  value: T,
+) -> (Option<(Bound<T>, Bound<T>)>, Option<(Bound<T>, Bound<T>)>)
+where
+  R: RangeBounds<T>,
+  T: Clone + Ord + Inc,
+{
+  let lower = clamp(bounds(&range), (Unbounded, Excluded(value.clone())));
+  let upper = clamp(range, (Included(value), Unbounded));
+  (lower, upper)
+}
+
+
+/// Check whether two ranges touch each other without overlapping,
+/// i.e., there are no values in between them.
+///
+/// This function is equivalent to [`RangeBoundsExt::is_adjacent_to`].
+///
+/// ```rust
+/// use std::ops::Bound;
+/// # use gaps::range::adjacent;
+///
+/// assert!(adjacent(1..=4, (Bound::Excluded(4), Bound::Unbounded)));
+/// assert!(!adjacent(1..=4, 4..6));
+/// assert!(!adjacent(1..=4, 6..7));
+/// ```
+pub fn adjacent<A, B, T>(a: A, b: B) -> bool
+where
+  A: RangeBounds<T>,
+  B: RangeBounds<T>,
+  T: Clone + Ord + Inc,
+{
+  a.is_adjacent_to(&b)
+}
+
+
+/// Merge two ranges into one if they overlap or are adjacent to each
+/// other.
+///
+/// `None` is returned if the ranges are separated by at least one
+/// value or if either of them is empty.
+///
+/// ```rust
+/// use std::ops::Bound;
+/// # use gaps::range::try_merge;
+///
+/// assert_eq!(
+///   try_merge(1..5, 5..=7),
+///   Some((Bound::Included(1), Bound::Included(7)))
+/// );
+/// assert_eq!(try_merge(1..5, 6..=7), None);
+/// ```
+pub fn try_merge<A, B, T>(a: A, b: B) -> Option<(Bound<T>, Bound<T>)>
+where
+  A: RangeBounds<T>,
+  B: RangeBounds<T>,
+  T: Clone + Ord + Inc,
+{
+  if !a.overlaps(&b) && !a.is_adjacent_to(&b) {
+    return None
+  }
+
+  let (start1, end1) = bounds(&a);
+  let (start2, end2) = bounds(&b);
+
+  let start = if lower_le_lower(&start1, &start2) {
+    start1
+  } else {
+    start2
+  };
+  let end = if end_lt_end(&end1, &end2) { end2 } else { end1 };
+  Some((start, end))
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+
+  /// Check that overlapping ranges are detected correctly.
+  #[test]
+  fn overlapping() {
+    assert!((1..5).overlaps(&(4..6)));
+    assert!((4..6).overlaps(&(1..5)));
+    assert!((1..=5).overlaps(&(5..6)));
+    assert!(!(1..5).overlaps(&(5..6)));
+    assert!((..).overlaps(&(5..6)));
+    assert!(!(Excluded(4), Excluded(5)).overlaps(&(0..10)));
+    assert!(!(Excluded(u8::MAX), Unbounded).overlaps(&(0..)));
+    assert!((Excluded(3), Unbounded).overlaps(&(..=4)));
+    assert!(!(Excluded(3), Unbounded).overlaps(&(..4)));
+  }
+
+  /// Check that range containment is detected correctly.
+  #[test]
+  fn range_containment() {
+    assert!((1..5).contains_range(&(1..5)));
+    assert!((1..5).contains_range(&(Excluded(0), Included(4))));
+    assert!(!(1..5).contains_range(&(0..5)));
+    assert!(!(1..5).contains_range(&(1..=5)));
+    assert!((..).contains_range(&(1..=5)));
+    assert!(!(1..).contains_range(&(..)));
+    assert!((3..4).contains_range(&(7..7)));
+  }
+
+  /// Check that value containment is determined correctly.
+  #[test]
+  fn value_containment() {
+    assert!((1..5).contains_value(&1));
+    assert!(!(1..5).contains_value(&5));
+    assert!((Excluded(0), Unbounded).contains_value(&u8::MAX));
+    assert!(!(Excluded(0), Unbounded).contains_value(&0));
+  }
+
+  /// Check that empty ranges are classified correctly.
+  #[test]
+  fn emptiness() {
+    assert!(is_empty(&(3..3)));
+    assert!(is_empty(&(Excluded(3), Included(3))));
+    assert!(is_empty(&(Excluded(u8::MAX), Unbounded)));
+    assert!(!is_empty(&(3..4)));
+    assert!(!is_empty(&(Excluded(3), Excluded(5))));
+    assert!(!is_empty(&(Excluded(u8::MAX - 1), Unbounded)));
+    assert!(!is_empty(&(..0)));
+    assert!(!is_empty::<_, u8>(&(..)));
+  }
+
+  /// Check that the number of values in a range is determined
+  /// correctly.
+  #[test]
+  fn length() {
+    assert_eq!(len(&(3..3)), Some(0));
+    assert_eq!(len(&(Included(5), Excluded(3))), Some(0));
+    assert_eq!(len(&(3..=3)), Some(1));
+    assert_eq!(len(&(Excluded(3), Excluded(5))), Some(1));
+    assert_eq!(len(&(Excluded(u8::MAX), Included(u8::MAX))), Some(0));
+    assert_eq!(len(&(i8::MIN..=i8::MAX)), Some(256));
+    assert_eq!(len(&(0..=usize::MAX)), Some(usize::MAX as u128 + 1));
+    assert_eq!(len(&(0..=u128::MAX)), None);
+    assert_eq!(len(&(..5)), None);
+    assert_eq!(len::<_, u8>(&(..)), None);
+  }
+
+  /// Check that ranges are normalized correctly.
+  #[test]
+  fn normalization() {
+    assert_eq!(normalize(&(3..7)), (Included(3), Excluded(7)));
+    assert_eq!(normalize(&(3..=7)), (Included(3), Excluded(8)));
+    assert_eq!(
+      normalize(&(Excluded(3), Excluded(7))),
+      (Included(4), Excluded(7))
+    );
+    assert_eq!(normalize(&(..=7)), (Unbounded, Excluded(8)));
+    assert_eq!(
+      normalize(&(Excluded(3), Unbounded)),
+      (Included(4), Unbounded)
+    );
+    assert_eq!(normalize::<_, u8>(&(..)), (Unbounded, Unbounded));
+    assert_eq!(normalize(&(0..=u8::MAX)), (Included(0), Included(u8::MAX)));
+    assert_eq!(
+      normalize(&(Excluded(u8::MAX), Unbounded)),
+      (Included(u8::MAX), Excluded(u8::MAX))
+    );
+  }
+
+  /// Check that the intersection of two ranges is computed correctly.
+  #[test]
+  fn intersection() {
+    assert_eq!(intersect(1..5, 2..=3), Some((Included(2), Included(3))));
+    assert_eq!(intersect(2..=3, 1..5), Some((Included(2), Included(3))));
+    assert_eq!(intersect(1..5, 4..), Some((Included(4), Excluded(5))));
+    assert_eq!(intersect(1..5, 5..), None);
+    assert_eq!(intersect(1..=5, 5..), Some((Included(5), Included(5))));
+    assert_eq!(intersect(..=5, ..3), Some((Unbounded, Excluded(3))));
+    assert_eq!(intersect::<_, _, u8>(.., ..), Some((Unbounded, Unbounded)));
+    assert_eq!(
+      intersect((Excluded(1), Excluded(4)), (Included(2), Included(4))),
+      Some((Included(2), Excluded(4)))
+    );
+    assert_eq!(
+      intersect((Excluded(1), Unbounded), (Excluded(0), Included(2))),
+      Some((Excluded(1), Included(2)))
+    );
+    assert_eq!(intersect((Excluded(1), Excluded(2)), 0..10), None);
+    assert_eq!(intersect((Excluded(u8::MAX), Unbounded), ..), None);
+  }
+
+  /// Check that ranges are clamped to a domain correctly.
+  #[test]
+  fn clamping() {
+    assert_eq!(clamp(.., 0..10), Some((Included(0), Excluded(10))));
+    assert_eq!(clamp(2..5, 0..10), Some((Included(2), Excluded(5))));
+    assert_eq!(clamp(5..=20, 0..10), Some((Included(5), Excluded(10))));
+    assert_eq!(
+      clamp((Excluded(1), Included(9)), 2..10),
+      Some((Excluded(1), Included(9)))
+    );
+    assert_eq!(clamp(..0, 0..10), None);
+    assert_eq!(clamp(10.., 0..10), None);
+  }
+
+  /// Check that ranges are split at a value correctly.
+  #[test]
+  fn splitting() {
+    assert_eq!(
+      split_at(2..9, 5),
+      (
+        Some((Included(2), Excluded(5))),
+        Some((Included(5), Excluded(9)))
+      )
+    );
+    assert_eq!(
+      split_at((Excluded(2), Unbounded), 3),
+      (None, Some((Excluded(2), Unbounded)))
+    );
+    assert_eq!(
+      split_at(..9, 8),
+      (
+        Some((Unbounded, Excluded(8))),
+        Some((Included(8), Excluded(9)))
+      )
+    );
+    assert_eq!(split_at(2..9, 9), (Some((Included(2), Excluded(9))), None));
+    assert_eq!(split_at(2..9, 20), (Some((Included(2), Excluded(9))), None));
+    assert_eq!(split_at(5..5, 5), (None, None));
+  }
+
+  /// Check that the free `adjacent` function agrees with
+  /// `RangeBoundsExt::is_adjacent_to`.
+  #[test]
+  fn adjacent_ranges() {
+    assert!(adjacent(5..7, 1..5));
+    assert!(adjacent((Excluded(4), Excluded(6)), 1..=4));
+    assert!(!adjacent(1..5, 5..5));
+    assert!(!adjacent(1..=5, 5..7));
+  }
+
+  /// Check that overlapping and adjacent ranges are merged correctly.
+  #[test]
+  fn merging() {
+    assert_eq!(try_merge(1..5, 2..=3), Some((Included(1), Excluded(5))));
+    assert_eq!(try_merge(2..=3, 1..5), Some((Included(1), Excluded(5))));
+    assert_eq!(try_merge(1..5, 4..), Some((Included(1), Unbounded)));
+    assert_eq!(try_merge(5.., 1..5), Some((Included(1), Unbounded)));
+    assert_eq!(try_merge(..=4, 5..), Some((Unbounded, Unbounded)));
+    assert_eq!(
+      try_merge((Excluded(1), Included(3)), (Excluded(3), Excluded(6))),
+      Some((Excluded(1), Excluded(6)))
+    );
+    assert_eq!(try_merge(1..5, 6..), None);
+    assert_eq!(try_merge(1..5, 5..5), None);
+    assert_eq!(try_merge((Excluded(1), Excluded(2)), 2..4), None);
+  }
+
+  /// Check that adjacent ranges are detected correctly.
+  #[test]
+  fn adjacency() {
+    assert!((1..5).is_adjacent_to(&(5..7)));
+    assert!((5..7).is_adjacent_to(&(1..5)));
+    assert!((1..=4).is_adjacent_to(&(5..7)));
+    assert!((1..=4).is_adjacent_to(&(Excluded(4), Unbounded)));
+    assert!((..=4).is_adjacent_to(&(5..)));
+    assert!(!(1..5).is_adjacent_to(&(6..7)));
+    assert!(!(1..5).is_adjacent_to(&(4..7)));
+    assert!(!(1..5).is_adjacent_to(&(5..5)));
+    assert!(!(..).is_adjacent_to(&(5..7)));
+    assert!(!(0..=u8::MAX).is_adjacent_to(&(Excluded(u8::MAX), Unbounded)));
+  }
+}
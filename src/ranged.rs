@@ -0,0 +1,165 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::collections::btree_map::Range as BTreeMapRange;
+use std::collections::btree_set::Range as BTreeSetRange;
+use std::collections::vec_deque::Iter as VecDequeIter;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+use std::iter::Cloned;
+use std::iter::FusedIterator;
+use std::iter::Map;
+use std::ops::Bound;
+use std::ops::Bound::Excluded;
+use std::ops::Bound::Included;
+use std::ops::Bound::Unbounded;
+
+
+/// A trait for collections that can provide an iterator over the
+/// (ordered) elements they contain in a certain range.
+pub trait Ranged<'s, T> {
+  /// The type of the iterator over a range of elements.
+  type Iter: Iterator<Item = T>;
+
+  /// Create an iterator over the elements in the given range.
+  fn range_iter(&'s self, start: Bound<T>, end: Bound<T>) -> Self::Iter;
+}
+
+impl<'s, V> Ranged<'s, V> for BTreeSet<V>
+where
+  V: Clone + Ord + 's,
+{
+  type Iter = Cloned<BTreeSetRange<'s, V>>;
+
+  fn range_iter(&'s self, start: Bound<V>, end: Bound<V>) -> Self::Iter {
+    self.range((start, end)).cloned()
+  }
+}
+
+impl<'s, K, V> Ranged<'s, K> for BTreeMap<K, V>
+where
+  K: Clone + Ord + 's,
+  V: 's,
+{
+  #[allow(clippy::type_complexity)]
+  type Iter = Map<BTreeMapRange<'s, K, V>, fn((&'_ K, &'_ V)) -> K>;
+
+  fn range_iter(&'s self, start: Bound<K>, end: Bound<K>) -> Self::Iter {
+    fn map<I, J>(x: (&I, &J)) -> I
+    where
+      I: Clone,
+    {
+      x.0.clone()
+    }
+
+    self.range((start, end)).map(map as _)
+  }
+}
+
+/// A `VecDeque` is expected to be sorted in ascending order. Elements
+/// in the range are located using binary search.
+impl<'s, V> Ranged<'s, V> for VecDeque<V>
+where
+  V: Clone + Ord + 's,
+{
+  type Iter = Cloned<VecDequeIter<'s, V>>;
+
+  fn range_iter(&'s self, start: Bound<V>, end: Bound<V>) -> Self::Iter {
+    let first = match start {
+      Included(start) => self.partition_point(|x| *x < start),
+      Excluded(start) => self.partition_point(|x| *x <= start),
+      Unbounded => 0,
+    };
+    let last = match end {
+      Included(end) => self.partition_point(|x| *x <= end),
+      Excluded(end) => self.partition_point(|x| *x < end),
+      Unbounded => self.len(),
+    };
+    self.range(first..last.max(first)).cloned()
+  }
+}
+
+
+/// An iterator over a range of elements of a [`Ranged`] collection.
+///
+/// In contrast to the iterator provided by [`Ranged::range_iter`], this
+/// type keeps a reference to the collection and is able to efficiently
+/// reposition itself.
+pub struct RangeIter<'s, C, T>
+where
+  C: Ranged<'s, T> + ?Sized,
+{
+  /// The collection we iterate over.
+  collection: &'s C,
+  /// The iterator over the current range of elements.
+  iter: C::Iter,
+}
+
+impl<'s, C, T> RangeIter<'s, C, T>
+where
+  C: Ranged<'s, T> + ?Sized,
+{
+  /// Create a new `RangeIter` over the given range of a collection.
+  pub fn new(collection: &'s C, start: Bound<T>, end: Bound<T>) -> Self {
+    Self {
+      collection,
+      iter: collection.range_iter(start, end),
+    }
+  }
+
+  /// Reposition the iterator to cover the given range.
+  pub(crate) fn seek(&mut self, start: Bound<T>, end: Bound<T>) {
+    self.iter = self.collection.range_iter(start, end);
+  }
+}
+
+impl<'s, C, T> Clone for RangeIter<'s, C, T>
+where
+  C: Ranged<'s, T> + ?Sized,
+  C::Iter: Clone,
+{
+  fn clone(&self) -> Self {
+    Self {
+      collection: self.collection,
+      iter: self.iter.clone(),
+    }
+  }
+}
+
+impl<'s, C, T> Debug for RangeIter<'s, C, T>
+where
+  C: Ranged<'s, T> + ?Sized,
+  C::Iter: Debug,
+{
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    f.debug_struct("RangeIter")
+      .field("iter", &self.iter)
+      .finish()
+  }
+}
+
+impl<'s, C, T> Iterator for RangeIter<'s, C, T>
+where
+  C: Ranged<'s, T> + ?Sized,
+{
+  type Item = T;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.iter.next()
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+}
+
+impl<'s, C, T> FusedIterator for RangeIter<'s, C, T>
+where
+  C: Ranged<'s, T> + ?Sized,
+  C::Iter: FusedIterator,
+{
+}
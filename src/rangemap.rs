@@ -0,0 +1,169 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::convert::TryFrom;
+use std::iter::Cloned;
+use std::ops::Range;
+use std::ops::RangeBounds;
+use std::ops::RangeInclusive;
+
+use rangemap::inclusive_set::Iter as RangeInclusiveSetIter;
+use rangemap::set::Iter as RangeSetIter;
+use rangemap::RangeInclusiveSet;
+use rangemap::RangeSet;
+use rangemap::StepLite;
+
+use crate::bounds::bounds;
+use crate::Dec;
+use crate::Gap;
+use crate::GapError;
+use crate::GapList;
+use crate::Inc;
+use crate::IntervalGapIter;
+
+
+/// An extension trait providing gap detection between the ranges
+/// covered by a `rangemap` set.
+///
+/// ```rust
+/// use std::ops::Bound;
+/// # use gaps::Gap;
+/// # use gaps::RangeSetGappable as _;
+/// use rangemap::RangeSet;
+///
+/// let mut set = RangeSet::new();
+/// set.insert(1..3);
+/// set.insert(5..8);
+/// let mut gaps = set.interval_gaps(0..=10);
+/// assert_eq!(gaps.next(), Some(Gap::new(Bound::Included(0), Bound::Excluded(1))));
+/// assert_eq!(gaps.next(), Some(Gap::new(Bound::Included(3), Bound::Excluded(5))));
+/// assert_eq!(gaps.next(), Some(Gap::new(Bound::Included(8), Bound::Included(10))));
+/// assert_eq!(gaps.next(), None);
+/// ```
+pub trait RangeSetGappable<'s, T> {
+  /// The type of the iterator over the covered ranges.
+  type Iter;
+  /// The type of the covered ranges.
+  type Range;
+
+  /// Create a new [`IntervalGapIter`] that yields ranges identifying
+  /// the gaps between the covered ranges in a certain range.
+  fn interval_gaps<R>(&'s self, range: R) -> IntervalGapIter<Self::Iter, Self::Range, T>
+  where
+    R: RangeBounds<T>;
+}
+
+impl<'s, T> RangeSetGappable<'s, T> for RangeSet<T>
+where
+  T: Clone + Ord + Inc + 's,
+{
+  type Iter = Cloned<RangeSetIter<'s, T>>;
+  type Range = Range<T>;
+
+  fn interval_gaps<R>(&'s self, range: R) -> IntervalGapIter<Self::Iter, Self::Range, T>
+  where
+    R: RangeBounds<T>,
+  {
+    let (start, end) = bounds(&range);
+    IntervalGapIter::new(self.iter().cloned(), start, end)
+  }
+}
+
+impl<'s, T> RangeSetGappable<'s, T> for RangeInclusiveSet<T>
+where
+  T: Clone + Ord + Inc + StepLite + 's,
+{
+  type Iter = Cloned<RangeInclusiveSetIter<'s, T>>;
+  type Range = RangeInclusive<T>;
+
+  fn interval_gaps<R>(&'s self, range: R) -> IntervalGapIter<Self::Iter, Self::Range, T>
+  where
+    R: RangeBounds<T>,
+  {
+    let (start, end) = bounds(&range);
+    IntervalGapIter::new(self.iter().cloned(), start, end)
+  }
+}
+
+
+/// Convert the ranges of a `RangeSet` into a `GapList`.
+///
+/// ```rust
+/// use std::ops::Bound;
+/// # use gaps::Gap;
+/// # use gaps::GapList;
+/// use rangemap::RangeSet;
+///
+/// let mut set = RangeSet::new();
+/// set.insert(1..3);
+/// set.insert(5..8);
+/// let list = GapList::from(set);
+/// assert_eq!(list.get(6), Some(&Gap::new(Bound::Included(5), Bound::Excluded(8))));
+/// ```
+impl<T> From<RangeSet<T>> for GapList<T>
+where
+  T: Clone + Ord + Inc,
+{
+  fn from(set: RangeSet<T>) -> Self {
+    set.into_iter().map(Gap::from).collect()
+  }
+}
+
+/// Convert the ranges of a `RangeInclusiveSet` into a `GapList`.
+impl<T> From<RangeInclusiveSet<T>> for GapList<T>
+where
+  T: Clone + Ord + Inc,
+{
+  fn from(set: RangeInclusiveSet<T>) -> Self {
+    set.into_iter().map(Gap::from).collect()
+  }
+}
+
+/// Convert the gaps of a `GapList` into a `RangeSet`.
+///
+/// Conversion fails if a gap is unbounded or if its end is not
+/// representable as an excluded bound.
+///
+/// Gaps produced by an iterator can be converted in the same manner,
+/// by collecting their half-open ranges:
+///
+/// ```rust
+/// use std::convert::TryFrom as _;
+/// use std::ops::Range;
+/// # use maplit::btreeset;
+/// # use gaps::RangeGappable as _;
+/// use rangemap::RangeSet;
+///
+/// let set = btreeset!{2, 3, 7};
+/// let ranges = set
+///   .gaps(0..10)
+///   .map(Range::try_from)
+///   .collect::<Result<RangeSet<_>, _>>()
+///   .unwrap();
+/// assert_eq!(ranges.iter().cloned().collect::<Vec<_>>(), vec![0..2, 4..7, 8..10]);
+/// ```
+impl<T> TryFrom<GapList<T>> for RangeSet<T>
+where
+  T: Clone + Ord + Inc,
+{
+  type Error = GapError<T>;
+
+  fn try_from(list: GapList<T>) -> Result<Self, Self::Error> {
+    list.into_iter().map(Range::try_from).collect()
+  }
+}
+
+/// Convert the gaps of a `GapList` into a `RangeInclusiveSet`.
+///
+/// Conversion fails if a gap is unbounded or if its end is not
+/// representable as an included bound.
+impl<T> TryFrom<GapList<T>> for RangeInclusiveSet<T>
+where
+  T: Clone + Ord + Inc + Dec + StepLite,
+{
+  type Error = GapError<T>;
+
+  fn try_from(list: GapList<T>) -> Result<Self, Self::Error> {
+    list.into_iter().map(RangeInclusive::try_from).collect()
+  }
+}
@@ -0,0 +1,112 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::iter::FusedIterator;
+use std::ops::Bound;
+use std::ops::Bound::Excluded;
+
+use crate::bounds::start_le_end;
+use crate::Gap;
+use crate::Inc;
+
+
+/// An iterator over the gaps in a sequence represented by an iterator
+/// yielding elements in descending order, reporting gaps from high to
+/// low.
+///
+/// Objects of this type are created by
+/// [`RangeGappable::rgaps`][crate::RangeGappable::rgaps].
+#[derive(Clone, Debug)]
+pub struct RevGapIter<I, T> {
+  /// The iterator that we wrap.
+  iter: I,
+  /// The start of the range of interest.
+  start: Bound<T>,
+  /// The end of the range not yet covered by any gap, or `None` if
+  /// iteration has finished.
+  end: Option<Bound<T>>,
+}
+
+impl<I, T> RevGapIter<I, T> {
+  /// Create a new `RevGapIter` wrapping the provided iterator.
+  ///
+  /// The iterator is expected to only yield elements inside of the
+  /// range formed by `start` and `end`.
+  pub(crate) fn new(iter: I, start: Bound<T>, end: Bound<T>) -> Self {
+    Self {
+      iter,
+      start,
+      end: Some(end),
+    }
+  }
+}
+
+impl<I, T> Iterator for RevGapIter<I, T>
+where
+  I: Iterator<Item = T>,
+  T: Clone + Ord + Inc,
+{
+  type Item = Gap<T>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    loop {
+      let end = self.end.take()?;
+      let start = match self.iter.next() {
+        Some(value) => {
+          debug_assert!(
+            match &end {
+              Excluded(end) => value < *end,
+              _ => true,
+            },
+            "sequence is not descending"
+          );
+          self.end = Some(Excluded(value.clone()));
+          Excluded(value)
+        },
+        None => self.start.clone(),
+      };
+
+      if start_le_end(&start, &end) {
+        break Some(Gap::new(start, end))
+      }
+    }
+  }
+}
+
+impl<I, T> FusedIterator for RevGapIter<I, T>
+where
+  I: Iterator<Item = T>,
+  T: Clone + Ord + Inc,
+{
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use std::ops::Bound::Included;
+  use std::ops::Bound::Unbounded;
+
+
+  /// Check that gaps are reported in descending order.
+  #[test]
+  fn descending_gaps() {
+    let values = vec![7, 6, 3];
+    let gaps = RevGapIter::new(values.into_iter(), Unbounded, Included(9)).collect::<Vec<_>>();
+    let expected = vec![
+      Gap::new(Excluded(7), Included(9)),
+      Gap::new(Excluded(3), Excluded(6)),
+      Gap::new(Unbounded, Excluded(3)),
+    ];
+    assert_eq!(gaps, expected);
+  }
+
+  /// Check that no gaps are reported for a fully covered range.
+  #[test]
+  fn no_gaps() {
+    let values = vec![3, 2, 1];
+    let mut gaps = RevGapIter::new(values.into_iter(), Included(1), Included(3));
+    assert_eq!(gaps.next(), None);
+  }
+}
@@ -0,0 +1,208 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+use std::iter::FusedIterator;
+use std::ops::Bound;
+use std::ops::Bound::Excluded;
+use std::ops::Bound::Included;
+use std::ops::Bound::Unbounded;
+use std::ops::RangeBounds;
+
+use roaring::bitmap::Iter as BitmapIter;
+use roaring::treemap::BitmapIter as TreemapBitmapIter;
+use roaring::RoaringBitmap;
+use roaring::RoaringTreemap;
+
+use crate::bounds::bounds;
+use crate::GapIter;
+use crate::RangeGappable;
+use crate::RangeIter;
+use crate::Ranged;
+
+
+/// Split a value into the key of the bitmap it is stored in and its
+/// position inside that bitmap.
+fn split(value: u64) -> (u32, u32) {
+  ((value >> 32) as u32, value as u32)
+}
+
+/// Join a bitmap key and a position inside the bitmap into a value.
+fn join(key: u32, value: u32) -> u64 {
+  (u64::from(key) << 32) | u64::from(value)
+}
+
+
+/// An iterator over the values of a [`RoaringTreemap`] in a certain
+/// range.
+///
+/// The bitmaps making up the treemap are visited in order, with those
+/// outside of the range being skipped over wholesale.
+pub struct TreemapRange<'t> {
+  /// The bitmaps not yet visited.
+  bitmaps: TreemapBitmapIter<'t>,
+  /// The key of the bitmap currently visited along with an iterator
+  /// over its values in the range.
+  current: Option<(u32, BitmapIter<'t>)>,
+  /// The first and last value in the range, or `None` if the range is
+  /// empty or has been exhausted.
+  range: Option<(u64, u64)>,
+}
+
+impl<'t> TreemapRange<'t> {
+  /// Create a new `TreemapRange` over the values of `treemap` between
+  /// `start` and `end`.
+  fn new(treemap: &'t RoaringTreemap, start: Bound<u64>, end: Bound<u64>) -> Self {
+    let first = match start {
+      Included(start) => Some(start),
+      Excluded(start) => start.checked_add(1),
+      Unbounded => Some(0),
+    };
+    let last = match end {
+      Included(end) => Some(end),
+      Excluded(end) => end.checked_sub(1),
+      Unbounded => Some(u64::MAX),
+    };
+
+    Self {
+      bitmaps: treemap.bitmaps(),
+      current: None,
+      range: first.zip(last).filter(|(first, last)| first <= last),
+    }
+  }
+}
+
+impl Debug for TreemapRange<'_> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    f.debug_struct("TreemapRange")
+      .field("range", &self.range)
+      .finish()
+  }
+}
+
+impl Iterator for TreemapRange<'_> {
+  type Item = u64;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    loop {
+      if let Some((key, values)) = &mut self.current {
+        if let Some(value) = values.next() {
+          return Some(join(*key, value))
+        }
+        self.current = None;
+      }
+
+      let (first, last) = self.range?;
+      let (first_key, first_value) = split(first);
+      let (last_key, last_value) = split(last);
+
+      match self.bitmaps.next() {
+        Some((key, _)) if key < first_key => (),
+        Some((key, bitmap)) if key <= last_key => {
+          let start = if key == first_key { first_value } else { 0 };
+          let end = if key == last_key {
+            last_value
+          } else {
+            u32::MAX
+          };
+          self.current = Some((key, bitmap.range(start..=end)));
+        },
+        _ => self.range = None,
+      }
+    }
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    let lower = self
+      .current
+      .as_ref()
+      .map(|(_, values)| values.size_hint().0)
+      .unwrap_or(0);
+    let upper = if self.range.is_none() {
+      Some(lower)
+    } else {
+      None
+    };
+    (lower, upper)
+  }
+}
+
+impl FusedIterator for TreemapRange<'_> {}
+
+
+impl<'s> Ranged<'s, u32> for RoaringBitmap {
+  type Iter = BitmapIter<'s>;
+
+  fn range_iter(&'s self, start: Bound<u32>, end: Bound<u32>) -> Self::Iter {
+    self.range((start, end))
+  }
+}
+
+impl<'s> Ranged<'s, u64> for RoaringTreemap {
+  type Iter = TreemapRange<'s>;
+
+  fn range_iter(&'s self, start: Bound<u64>, end: Bound<u64>) -> Self::Iter {
+    TreemapRange::new(self, start, end)
+  }
+}
+
+
+/// Values are retrieved using the bitmap's range iteration, so that
+/// values outside of the range of interest are never visited.
+///
+/// ```rust
+/// use std::ops::Bound;
+/// # use gaps::Gap;
+/// # use gaps::RangeGappable as _;
+/// use roaring::RoaringBitmap;
+///
+/// let bitmap = vec![1, 3, 4].into_iter().collect::<RoaringBitmap>();
+/// let mut gaps = bitmap.gaps(0..=6);
+/// assert_eq!(gaps.next(), Some(Gap::new(Bound::Included(0), Bound::Excluded(1))));
+/// assert_eq!(gaps.next(), Some(Gap::new(Bound::Excluded(1), Bound::Excluded(3))));
+/// assert_eq!(gaps.next(), Some(Gap::new(Bound::Excluded(4), Bound::Included(6))));
+/// assert_eq!(gaps.next(), None);
+/// ```
+impl<'s> RangeGappable<'s, u32> for RoaringBitmap {
+  type Iter = RangeIter<'s, Self, u32>;
+
+  fn gaps<R>(&'s self, range: R) -> GapIter<Self::Iter, u32>
+  where
+    R: RangeBounds<u32>,
+  {
+    let (start, end) = bounds(&range);
+    let range = RangeIter::new(self, start, end);
+    GapIter::with_seek(range, start, end, RangeIter::seek)
+  }
+}
+
+
+/// Bitmaps of the treemap that lie outside of the range of interest
+/// are skipped over without visiting their values.
+///
+/// ```rust
+/// use std::ops::Bound;
+/// # use gaps::Gap;
+/// # use gaps::RangeGappable as _;
+/// use roaring::RoaringTreemap;
+///
+/// let treemap = vec![1, 1 << 40].into_iter().collect::<RoaringTreemap>();
+/// let mut gaps = treemap.gaps(1..);
+/// assert_eq!(gaps.next(), Some(Gap::new(Bound::Excluded(1), Bound::Excluded(1 << 40))));
+/// assert_eq!(gaps.next(), Some(Gap::new(Bound::Excluded(1 << 40), Bound::Unbounded)));
+/// assert_eq!(gaps.next(), None);
+/// ```
+impl<'s> RangeGappable<'s, u64> for RoaringTreemap {
+  type Iter = RangeIter<'s, Self, u64>;
+
+  fn gaps<R>(&'s self, range: R) -> GapIter<Self::Iter, u64>
+  where
+    R: RangeBounds<u64>,
+  {
+    let (start, end) = bounds(&range);
+    let range = RangeIter::new(self, start, end);
+    GapIter::with_seek(range, start, end, RangeIter::seek)
+  }
+}
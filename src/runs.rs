@@ -0,0 +1,248 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::iter::FusedIterator;
+use std::ops::Bound;
+use std::ops::Bound::Excluded;
+use std::ops::Bound::Included;
+use std::ops::Bound::Unbounded;
+
+use crate::bounds::start_le_end;
+use crate::Gap;
+use crate::Inc;
+
+
+/// An iterator over the runs of contiguous values in a sequence
+/// represented by an iterator.
+///
+/// Runs are reported as [`Gap`] objects, so that they can be
+/// inspected and processed just like gaps.
+///
+/// This is the counterpart to [`GapIter`][crate::GapIter]. Objects of
+/// this type are created by [`Gappable::runs`][crate::Gappable::runs]
+/// and [`RangeGappable::runs`][crate::RangeGappable::runs].
+#[derive(Clone, Debug)]
+pub struct RunIter<I, T> {
+  /// The iterator that we wrap.
+  iter: Option<I>,
+  /// The start of the range of interest.
+  start: Bound<T>,
+  /// The end of the range of interest.
+  end: Bound<T>,
+  /// An element that has been retrieved from the wrapped iterator but
+  /// not yet been accounted for.
+  pending: Option<T>,
+}
+
+impl<I, T> RunIter<I, T>
+where
+  I: Iterator<Item = T>,
+  T: Clone + Ord + Inc,
+{
+  /// Create a new `RunIter` wrapping the provided iterator and yielding
+  /// ranges identifying the runs of contiguous elements in the given
+  /// range.
+  ///
+  /// # Notes
+  /// - the provided iterator is assumed to yield elements in ascending
+  ///   order
+  pub fn new(iter: I, start: Bound<T>, end: Bound<T>) -> Self {
+    Self::from_parts(Some(iter), start, end)
+  }
+
+  /// Create a new `RunIter` from the parts of a
+  /// [`GapIter`][crate::GapIter], which may have finished already.
+  pub(crate) fn from_parts(iter: Option<I>, start: Bound<T>, end: Bound<T>) -> Self {
+    Self {
+      iter,
+      start,
+      end,
+      pending: None,
+    }
+  }
+
+  /// Retrieve the next element, either the pending one or one from the
+  /// wrapped iterator.
+  fn next_element(&mut self) -> Option<T> {
+    if let Some(element) = self.pending.take() {
+      return Some(element)
+    }
+
+    let element = self.iter.as_mut()?.next();
+    if element.is_none() {
+      self.iter = None;
+    }
+    element
+  }
+
+  /// Find the next run, reporting its first and last value.
+  fn next_run(&mut self) -> Option<(T, T)> {
+    let first = loop {
+      let element = self.next_element()?;
+      let before_start = match &self.start {
+        Included(start) => element < *start,
+        Excluded(start) => element <= *start,
+        Unbounded => false,
+      };
+
+      if before_start {
+        continue
+      }
+
+      if self.past_end(&element) {
+        self.iter = None;
+        return None
+      }
+      break element
+    };
+
+    let mut last = first.clone();
+    while let Some(element) = self.next_element() {
+      debug_assert!(element >= last, "sequence is not ascending");
+
+      if element != last && element != last.clone().inc() {
+        self.pending = Some(element);
+        break
+      }
+
+      if self.past_end(&element) {
+        self.iter = None;
+        break
+      }
+      last = element;
+    }
+
+    Some((first, last))
+  }
+
+  /// Check whether an element is past the end of the range of
+  /// interest.
+  fn past_end(&self, element: &T) -> bool {
+    match &self.end {
+      Included(end) => element > end,
+      Excluded(end) => element >= end,
+      Unbounded => false,
+    }
+  }
+}
+
+impl<I, T> Iterator for RunIter<I, T>
+where
+  I: Iterator<Item = T>,
+  T: Clone + Ord + Inc,
+{
+  type Item = Gap<T>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self
+      .next_run()
+      .map(|(first, last)| Gap::new(Included(first), Included(last)))
+  }
+}
+
+impl<I, T> FusedIterator for RunIter<I, T>
+where
+  I: Iterator<Item = T>,
+  T: Clone + Ord + Inc,
+{
+}
+
+
+/// A segment of a range, as produced by [`SegmentIter`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Segment<T> {
+  /// A range of values that are present.
+  Present(Gap<T>),
+  /// A range of values that are missing, i.e., a gap.
+  Missing(Gap<T>),
+}
+
+
+/// An iterator over the alternating runs and gaps in a sequence
+/// represented by an iterator.
+///
+/// Objects of this type are created by
+/// [`Gappable::segments`][crate::Gappable::segments] and
+/// [`RangeGappable::segments`][crate::RangeGappable::segments].
+#[derive(Clone, Debug)]
+pub struct SegmentIter<I, T> {
+  /// The iterator over runs that we wrap.
+  runs: RunIter<I, T>,
+  /// The start of the range not yet covered by any segment, or `None`
+  /// if iteration has finished.
+  start: Option<Bound<T>>,
+  /// The end of the range of interest.
+  end: Bound<T>,
+  /// A run that has been retrieved but not yet reported.
+  pending: Option<(T, T)>,
+}
+
+impl<I, T> SegmentIter<I, T>
+where
+  I: Iterator<Item = T>,
+  T: Clone + Ord + Inc,
+{
+  /// Create a new `SegmentIter` wrapping the provided iterator and
+  /// yielding the segments of the given range.
+  ///
+  /// # Notes
+  /// - the provided iterator is assumed to yield elements in ascending
+  ///   order
+  pub fn new(iter: I, start: Bound<T>, end: Bound<T>) -> Self {
+    Self::from_parts(Some(iter), start, end)
+  }
+
+  /// Create a new `SegmentIter` from the parts of a
+  /// [`GapIter`][crate::GapIter], which may have finished already. In
+  /// the latter case no segments are reported.
+  pub(crate) fn from_parts(iter: Option<I>, start: Bound<T>, end: Bound<T>) -> Self {
+    let finished = iter.is_none();
+    Self {
+      runs: RunIter::from_parts(iter, start.clone(), end.clone()),
+      start: if finished { None } else { Some(start) },
+      end,
+      pending: None,
+    }
+  }
+}
+
+impl<I, T> Iterator for SegmentIter<I, T>
+where
+  I: Iterator<Item = T>,
+  T: Clone + Ord + Inc,
+{
+  type Item = Segment<T>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let start = self.start.take()?;
+    let run = match self.pending.take() {
+      Some(run) => run,
+      None => match self.runs.next_run() {
+        Some((first, last)) => {
+          let gap_end = Excluded(first.clone());
+          if start_le_end(&start, &gap_end) {
+            self.start = Some(Included(first.clone()));
+            self.pending = Some((first, last));
+            return Some(Segment::Missing(Gap::new(start, gap_end)))
+          }
+          (first, last)
+        },
+        None => {
+          return start_le_end(&start, &self.end)
+            .then_some(Segment::Missing(Gap::new(start, self.end.clone())))
+        },
+      },
+    };
+
+    let (first, last) = run;
+    self.start = Some(Excluded(last.clone()));
+    Some(Segment::Present(Gap::new(Included(first), Included(last))))
+  }
+}
+
+impl<I, T> FusedIterator for SegmentIter<I, T>
+where
+  I: Iterator<Item = T>,
+  T: Clone + Ord + Inc,
+{
+}
@@ -0,0 +1,222 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::cmp::Ordering;
+use std::iter::FusedIterator;
+use std::ops::Bound::Excluded;
+use std::ops::Bound::Included;
+use std::ops::Bound::Unbounded;
+use std::ops::RangeBounds;
+
+use crate::bounds::bounds;
+use crate::Gap;
+use crate::GapIter;
+use crate::Inc;
+
+
+/// A sequence number following serial number arithmetic as per RFC
+/// 1982.
+///
+/// Serial numbers wrap around once they reach the maximum value of the
+/// underlying type. As a result, they are only partially ordered: a
+/// serial number is considered less than another one if the latter can
+/// be reached by incrementing the former less than half the range of
+/// the type times. Use [`SerialGappable::serial_gaps`] to find gaps
+/// in sequences of serial numbers that may wrap around.
+///
+/// ```rust
+/// # use gaps::Serial;
+/// assert!(Serial(250u8) < Serial(3));
+/// assert!(Serial(3u8) < Serial(100));
+/// assert_eq!(Serial(0u8).partial_cmp(&Serial(128)), None);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Serial<T>(pub T);
+
+/// An iterator mapping serial numbers to their offset relative to a
+/// base value.
+#[derive(Clone, Debug)]
+struct Offsets<I, T> {
+  /// The iterator over serial numbers that we wrap.
+  iter: I,
+  /// The base value that offsets are relative to.
+  base: T,
+}
+
+
+/// An iterator over the gaps in a sequence of serial numbers, as
+/// represented by an iterator.
+///
+/// Objects of this type are created by
+/// [`SerialGappable::serial_gaps`].
+#[derive(Clone, Debug)]
+pub struct SerialGapIter<I, T> {
+  /// The iterator over gaps between offsets that we wrap.
+  gaps: GapIter<Offsets<I, T>, T>,
+  /// The base value that offsets are relative to.
+  base: T,
+}
+
+
+/// An extension trait that provides access to the "gaps" in a sequence
+/// of serial numbers yielded by an iterator, taking wrap-around into
+/// account.
+///
+/// ```rust
+/// use std::ops::Bound;
+/// # use gaps::Gap;
+/// # use gaps::Serial;
+/// # use gaps::SerialGappable as _;
+///
+/// let vec = vec![Serial(254u8), Serial(255), Serial(1), Serial(2)];
+/// let mut gaps = vec.into_iter().serial_gaps(Serial(250)..Serial(5));
+/// assert_eq!(gaps.next(), Some(Gap::new(Bound::Included(Serial(250)), Bound::Excluded(Serial(254)))));
+/// assert_eq!(gaps.next(), Some(Gap::new(Bound::Excluded(Serial(255)), Bound::Excluded(Serial(1)))));
+/// assert_eq!(gaps.next(), Some(Gap::new(Bound::Excluded(Serial(2)), Bound::Excluded(Serial(5)))));
+/// assert_eq!(gaps.next(), None);
+/// ```
+pub trait SerialGappable<I, T> {
+  /// Create a new [`SerialGapIter`] that yields ranges identifying the
+  /// gaps between serial numbers in a certain range.
+  ///
+  /// The range extends from its start up to its end, wrapping around
+  /// if necessary. Serial numbers are expected to be yielded in
+  /// ascending order starting at the range's start.
+  ///
+  /// # Panics
+  /// This method panics if either of the range's bounds is unbounded,
+  /// as there is no notion of a first or last serial number.
+  fn serial_gaps<R>(self, range: R) -> SerialGapIter<I, T>
+  where
+    R: RangeBounds<Serial<T>>;
+}
+
+
+macro_rules! serial {
+  ( $t:ty ) => {
+    impl Inc for Serial<$t> {
+      fn inc(self) -> Self {
+        Self(self.0.wrapping_add(1))
+      }
+    }
+
+    impl PartialOrd for Serial<$t> {
+      fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        const HALF: $t = 1 << (<$t>::BITS - 1);
+
+        let distance = other.0.wrapping_sub(self.0);
+        match distance {
+          0 => Some(Ordering::Equal),
+          HALF => None,
+          distance if distance < HALF => Some(Ordering::Less),
+          _ => Some(Ordering::Greater),
+        }
+      }
+    }
+
+    impl<I> Iterator for Offsets<I, $t>
+    where
+      I: Iterator<Item = Serial<$t>>,
+    {
+      type Item = $t;
+
+      fn next(&mut self) -> Option<Self::Item> {
+        self
+          .iter
+          .next()
+          .map(|serial| serial.0.wrapping_sub(self.base))
+      }
+
+      fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+      }
+    }
+
+    impl<I> Iterator for SerialGapIter<I, $t>
+    where
+      I: Iterator<Item = Serial<$t>>,
+    {
+      type Item = Gap<Serial<$t>>;
+
+      fn next(&mut self) -> Option<Self::Item> {
+        let base = self.base;
+        let map = |bound| match bound {
+          Included(offset) => Included(Serial(base.wrapping_add(offset))),
+          Excluded(offset) => Excluded(Serial(base.wrapping_add(offset))),
+          Unbounded => Unbounded,
+        };
+
+        self.gaps.next().map(|gap| {
+          let (start, end) = gap.into_bounds();
+          Gap::new(map(start), map(end))
+        })
+      }
+    }
+
+    impl<I> FusedIterator for SerialGapIter<I, $t> where I: Iterator<Item = Serial<$t>> {}
+
+    impl<I> SerialGappable<I, $t> for I
+    where
+      I: Iterator<Item = Serial<$t>>,
+    {
+      fn serial_gaps<R>(self, range: R) -> SerialGapIter<I, $t>
+      where
+        R: RangeBounds<Serial<$t>>,
+      {
+        let (start, end) = bounds(&range);
+        let (base, start) = match start {
+          Included(Serial(base)) => (base, Included(0)),
+          Excluded(Serial(base)) => (base, Excluded(0)),
+          Unbounded => panic!("serial number range must have a start"),
+        };
+        let end = match end {
+          Included(Serial(end)) => Included(end.wrapping_sub(base)),
+          Excluded(Serial(end)) => Excluded(end.wrapping_sub(base)),
+          Unbounded => panic!("serial number range must have an end"),
+        };
+
+        let offsets = Offsets { iter: self, base };
+        SerialGapIter {
+          gaps: GapIter::new(offsets, start, end),
+          base,
+        }
+      }
+    }
+  };
+}
+
+serial!(u8);
+serial!(u16);
+serial!(u32);
+serial!(u64);
+serial!(u128);
+serial!(usize);
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+
+  /// Check that serial numbers are compared as per RFC 1982.
+  #[test]
+  fn comparison() {
+    assert!(Serial(0u8) < Serial(1));
+    assert!(Serial(0u8) < Serial(127));
+    assert!(Serial(255u8) < Serial(0));
+    assert!(Serial(200u8) < Serial(50));
+    assert!(Serial(50u8) > Serial(200));
+    assert_eq!(Serial(7u16).partial_cmp(&Serial(7)), Some(Ordering::Equal));
+    assert_eq!(Serial(0u8).partial_cmp(&Serial(128)), None);
+    assert_eq!(Serial(128u8).partial_cmp(&Serial(0)), None);
+    assert!(Serial(u32::MAX) < Serial(0));
+  }
+
+  /// Check that serial numbers wrap around when incremented.
+  #[test]
+  fn increment() {
+    assert_eq!(Serial(1u8).inc(), Serial(2));
+    assert_eq!(Serial(u8::MAX).inc(), Serial(0));
+    assert_eq!(Serial(u64::MAX).checked_inc(), Some(Serial(0)));
+  }
+}
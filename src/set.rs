@@ -0,0 +1,918 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::convert::TryFrom;
+use std::iter::empty;
+use std::iter::once;
+use std::iter::Cloned;
+use std::iter::FromIterator;
+use std::iter::Once;
+use std::ops::BitAnd;
+use std::ops::BitOr;
+use std::ops::BitXor;
+use std::ops::Bound;
+use std::ops::Bound::Excluded;
+use std::ops::Bound::Included;
+use std::ops::Bound::Unbounded;
+use std::ops::Not;
+use std::ops::Range;
+use std::ops::RangeBounds;
+use std::ops::Sub;
+use std::slice;
+use std::vec;
+
+#[cfg(feature = "serde")]
+use serde::de::Error as _;
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+#[cfg(feature = "serde")]
+use serde::Deserializer;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+#[cfg(feature = "serde")]
+use serde::Serializer;
+
+use crate::bounds::bounds;
+use crate::bounds::contains;
+use crate::bounds::lower_le_lower;
+use crate::bounds::overlap;
+use crate::bounds::resolve;
+use crate::bounds::start_le_end;
+use crate::complement;
+use crate::range;
+use crate::view::RANGE_SIZE;
+use crate::DecodeError;
+use crate::Difference;
+use crate::Domain;
+use crate::Gap;
+use crate::Inc;
+use crate::Intersection;
+use crate::SymmetricDifference;
+use crate::Union;
+
+
+/// Append `value` to `bytes` in LEB128 encoding.
+fn write_varint(bytes: &mut Vec<u8>, mut value: u64) {
+  while value >= 0x80 {
+    bytes.push((value as u8) | 0x80);
+    value >>= 7;
+  }
+  bytes.push(value as u8);
+}
+
+/// Read a LEB128 encoded value from the front of `bytes`, advancing
+/// past it.
+fn read_varint(bytes: &mut &[u8]) -> Result<u64, DecodeError> {
+  let mut value = 0u64;
+  for shift in (0..u64::BITS).step_by(7) {
+    let (byte, rest) = bytes.split_first().ok_or(DecodeError::Truncated)?;
+    *bytes = rest;
+
+    let bits = u64::from(byte & 0x7f);
+    if bits << shift >> shift != bits {
+      return Err(DecodeError::Overflow)
+    }
+    value |= bits << shift;
+
+    if byte & 0x80 == 0 {
+      return Ok(value)
+    }
+  }
+  Err(DecodeError::Overflow)
+}
+
+/// Bring `range` into its canonical form, i.e., an included start and
+/// an excluded end (unless the end is the type's maximum value).
+///
+/// Returns `None` if the range contains no values.
+fn normalize<T>(range: Gap<T>) -> Option<Gap<T>>
+where
+  T: Clone + Ord + Inc + Domain,
+{
+  let (start, end) = range::normalize(&resolve(range.into_bounds()));
+  start_le_end(&start, &end).then(|| Gap::new(start, end))
+}
+
+
+/// A set of values, stored as sorted, disjoint ranges.
+///
+/// Whereas a [`GapIter`][crate::GapIter] reports the gaps in a
+/// collection once, a `GapSet` maintains the covered ranges as values
+/// are added and removed, allowing for the gaps to be queried at any
+/// time. Overlapping and adjacent ranges are merged, so that the set
+/// always contains disjoint, maximal ranges. Ranges are stored in
+/// normalized form, so that sets containing the same values compare
+/// (and hash) equal, irrespective of how they were constructed.
+///
+/// ```rust
+/// use std::ops::Bound;
+/// # use gaps::Gap;
+/// # use gaps::GapSet;
+///
+/// let mut set = GapSet::new();
+/// set.insert_range(0..5);
+/// set.insert_range(8..=9);
+/// set.insert(5);
+/// assert!(set.contains(5));
+/// assert!(!set.contains(7));
+/// assert_eq!(set.range_count(), 2);
+///
+/// let mut gaps = set.gaps(0..12);
+/// assert_eq!(gaps.next(), Some(Gap::new(Bound::Included(6), Bound::Excluded(8))));
+/// assert_eq!(gaps.next(), Some(Gap::new(Bound::Included(10), Bound::Excluded(12))));
+/// assert_eq!(gaps.next(), None);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct GapSet<T> {
+  /// The sorted, disjoint, and non-adjacent ranges of values.
+  ranges: Vec<Gap<T>>,
+}
+
+impl<T> GapSet<T> {
+  /// Create a new, empty `GapSet`.
+  pub fn new() -> Self {
+    Self { ranges: Vec::new() }
+  }
+
+  /// Retrieve the number of disjoint ranges in the set.
+  ///
+  /// Note that this is not the number of values contained in the set.
+  pub fn range_count(&self) -> usize {
+    self.ranges.len()
+  }
+
+  /// Check whether the set contains no values.
+  pub fn is_empty(&self) -> bool {
+    self.ranges.is_empty()
+  }
+
+  /// Retrieve an iterator over the covered ranges in the set.
+  pub fn iter(&self) -> slice::Iter<'_, Gap<T>> {
+    self.ranges.iter()
+  }
+
+  /// Retrieve the covered ranges in the set as a slice.
+  pub fn as_slice(&self) -> &[Gap<T>] {
+    &self.ranges
+  }
+}
+
+impl<T> GapSet<T>
+where
+  T: Clone + Ord + Inc + Domain,
+{
+  /// Find the index of the first range that does not end before
+  /// `start`.
+  fn lower_bound(&self, start: &Bound<T>) -> usize {
+    self
+      .ranges
+      .partition_point(|range| !start_le_end(start, range.end()))
+  }
+
+  /// Check whether `value` is contained in the set.
+  ///
+  /// The covered range possibly containing `value` is located using
+  /// binary search, in `O(log n)`.
+  pub fn contains(&self, value: T) -> bool {
+    let value = Included(value);
+    self
+      .ranges
+      .get(self.lower_bound(&value))
+      .is_some_and(|range| lower_le_lower(range.start(), &value))
+  }
+
+  /// Check whether all values in `range` are contained in the set.
+  ///
+  /// An empty `range` is always contained. The query runs in
+  /// `O(log n)`.
+  ///
+  /// ```rust
+  /// # use gaps::GapSet;
+  ///
+  /// let set = vec![0..10, 20..30].into_iter().collect::<GapSet<_>>();
+  /// assert!(set.contains_range(2..=9));
+  /// assert!(!set.contains_range(8..21));
+  /// assert!(set.contains_range(15..15));
+  /// ```
+  pub fn contains_range<R>(&self, range: R) -> bool
+  where
+    R: RangeBounds<T>,
+  {
+    let (start, end) = match normalize(Gap::from(bounds(&range))) {
+      Some(range) => range.into_bounds(),
+      None => return true,
+    };
+
+    self
+      .ranges
+      .get(self.lower_bound(&start))
+      .is_some_and(|range| contains((range.start(), range.end()), (&start, &end)))
+  }
+
+  /// Check whether any value in `range` is contained in the set.
+  ///
+  /// The query runs in `O(log n)`.
+  ///
+  /// ```rust
+  /// # use gaps::GapSet;
+  ///
+  /// let set = vec![0..10, 20..30].into_iter().collect::<GapSet<_>>();
+  /// assert!(set.overlaps(8..21));
+  /// assert!(!set.overlaps(10..20));
+  /// ```
+  pub fn overlaps<R>(&self, range: R) -> bool
+  where
+    R: RangeBounds<T>,
+  {
+    let (start, end) = match normalize(Gap::from(bounds(&range))) {
+      Some(range) => range.into_bounds(),
+      None => return false,
+    };
+
+    self
+      .ranges
+      .get(self.lower_bound(&start))
+      .is_some_and(|range| overlap((range.start(), range.end()), (&start, &end)))
+  }
+
+  /// Add a value to the set.
+  ///
+  /// Returns whether the value was newly inserted.
+  pub fn insert(&mut self, value: T) -> bool {
+    if self.contains(value.clone()) {
+      return false
+    }
+    let _added = self.insert_range(value.clone()..=value);
+    true
+  }
+
+  /// Remove a value from the set.
+  ///
+  /// Returns whether the value was present.
+  pub fn remove(&mut self, value: T) -> bool {
+    if !self.contains(value.clone()) {
+      return false
+    }
+    let _removed = self.remove_range(value.clone()..=value);
+    true
+  }
+
+  /// Determine the window of ranges that overlap with or are adjacent
+  /// to the range formed by `start` and `end`.
+  ///
+  /// The window may include one additional range at either side.
+  fn window(&self, start: &Bound<T>, end: &Bound<T>) -> Range<usize> {
+    let lo = self.lower_bound(start).saturating_sub(1);
+    let hi = self
+      .ranges
+      .partition_point(|range| start_le_end(range.start(), end));
+    lo..(hi + 1).min(self.ranges.len())
+  }
+
+  /// Add all values in `range` to the set.
+  ///
+  /// The (normalized) ranges of values that were not previously part
+  /// of the set are returned.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use gaps::Gap;
+  /// # use gaps::GapSet;
+  ///
+  /// let mut set = GapSet::new();
+  /// set.insert_range(3..5);
+  /// let added = set.insert_range(0..=6);
+  /// assert_eq!(
+  ///   added,
+  ///   vec![
+  ///     Gap::new(Bound::Included(0), Bound::Excluded(3)),
+  ///     Gap::new(Bound::Included(5), Bound::Excluded(7)),
+  ///   ]
+  /// );
+  /// assert_eq!(set.as_slice(), &[Gap::new(Bound::Included(0), Bound::Excluded(7))]);
+  /// ```
+  pub fn insert_range<R>(&mut self, range: R) -> Vec<Gap<T>>
+  where
+    R: RangeBounds<T>,
+  {
+    let range = match normalize(Gap::from(bounds(&range))) {
+      Some(range) => range,
+      None => return Vec::new(),
+    };
+
+    let window = self.window(range.start(), range.end());
+    let ranges = &self.ranges[window.clone()];
+    let added = Difference::new(once(range.clone()), ranges.iter().cloned())
+      .filter_map(normalize)
+      .collect::<Vec<_>>();
+    if !added.is_empty() {
+      let merged = Union::new(ranges.iter().cloned(), once(range))
+        .filter_map(normalize)
+        .collect::<Vec<_>>();
+      let _ = self.ranges.splice(window, merged);
+    }
+    added
+  }
+
+  /// Remove all values in `range` from the set.
+  ///
+  /// Covered ranges only partly inside of `range` are split as
+  /// necessary. The (normalized) ranges of values that were actually
+  /// removed are returned.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use gaps::Gap;
+  /// # use gaps::GapSet;
+  ///
+  /// let mut set = GapSet::new();
+  /// set.insert_range(0..10);
+  /// let removed = set.remove_range(8..12);
+  /// assert_eq!(removed, vec![Gap::new(Bound::Included(8), Bound::Excluded(10))]);
+  /// set.remove_range(3..=4);
+  /// assert_eq!(
+  ///   set.as_slice(),
+  ///   &[
+  ///     Gap::new(Bound::Included(0), Bound::Excluded(3)),
+  ///     Gap::new(Bound::Included(5), Bound::Excluded(8)),
+  ///   ]
+  /// );
+  /// ```
+  pub fn remove_range<R>(&mut self, range: R) -> Vec<Gap<T>>
+  where
+    R: RangeBounds<T>,
+  {
+    let range = match normalize(Gap::from(bounds(&range))) {
+      Some(range) => range,
+      None => return Vec::new(),
+    };
+
+    let window = self.window(range.start(), range.end());
+    let ranges = &self.ranges[window.clone()];
+    let removed = Intersection::new(ranges.iter().cloned(), once(range.clone()))
+      .filter_map(normalize)
+      .collect::<Vec<_>>();
+    if !removed.is_empty() {
+      let remaining = Difference::new(ranges.iter().cloned(), once(range))
+        .filter_map(normalize)
+        .collect::<Vec<_>>();
+      let _ = self.ranges.splice(window, remaining);
+    }
+    removed
+  }
+
+  /// Retrieve an iterator over the ranges within `domain` that are not
+  /// covered by the set, i.e., its gaps.
+  ///
+  /// This method is equivalent to [`GapSet::gaps_in`].
+  #[allow(clippy::type_complexity)]
+  pub fn gaps<R>(&self, domain: R) -> Difference<Once<Gap<T>>, Cloned<slice::Iter<'_, Gap<T>>>, T>
+  where
+    R: RangeBounds<T>,
+  {
+    self.gaps_in(domain)
+  }
+
+  /// Retrieve an iterator over the gaps of the set that lie within
+  /// `range`, clipped to it.
+  ///
+  /// Just as with a [`GapIter`][crate::GapIter], gaps are bounded by
+  /// the covered ranges surrounding them and by `range` itself. Only
+  /// the covered ranges overlapping with `range` are visited, which are
+  /// located using binary search. Hence, the query runs in `O(log n +
+  /// k)`, with `k` being the number of gaps reported.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use gaps::Gap;
+  /// # use gaps::GapSet;
+  ///
+  /// let mut set = GapSet::new();
+  /// set.insert_range(0..10);
+  /// set.insert_range(20..30);
+  /// set.insert_range(40..50);
+  /// let mut gaps = set.gaps_in(25..=45);
+  /// assert_eq!(gaps.next(), Some(Gap::new(Bound::Included(30), Bound::Excluded(40))));
+  /// assert_eq!(gaps.next(), None);
+  /// ```
+  #[allow(clippy::type_complexity)]
+  pub fn gaps_in<R>(&self, range: R) -> Difference<Once<Gap<T>>, Cloned<slice::Iter<'_, Gap<T>>>, T>
+  where
+    R: RangeBounds<T>,
+  {
+    let (start, end) = bounds(&range);
+    let lo = self.lower_bound(&start);
+    let hi = self
+      .ranges
+      .partition_point(|range| start_le_end(range.start(), &end))
+      .max(lo);
+    complement(self.ranges[lo..hi].iter().cloned(), (start, end))
+  }
+
+  /// Create a new set from sorted and disjoint ranges, normalizing them
+  /// and dropping those that contain no values.
+  fn from_ranges<I>(ranges: I) -> Self
+  where
+    I: Iterator<Item = Gap<T>>,
+  {
+    let ranges = ranges.filter_map(normalize).collect();
+    Self { ranges }
+  }
+
+  /// Create a new set containing the values in `self`, `other`, or
+  /// both.
+  pub fn union(&self, other: &Self) -> Self {
+    Self::from_ranges(Union::new(self.iter().cloned(), other.iter().cloned()))
+  }
+
+  /// Create a new set containing the values in both `self` and
+  /// `other`.
+  pub fn intersection(&self, other: &Self) -> Self {
+    Self::from_ranges(Intersection::new(
+      self.iter().cloned(),
+      other.iter().cloned(),
+    ))
+  }
+
+  /// Create a new set containing the values in `self` but not in
+  /// `other`.
+  pub fn difference(&self, other: &Self) -> Self {
+    Self::from_ranges(Difference::new(self.iter().cloned(), other.iter().cloned()))
+  }
+
+  /// Create a new set containing the values in either `self` or
+  /// `other`, but not in both.
+  pub fn symmetric_difference(&self, other: &Self) -> Self {
+    Self::from_ranges(SymmetricDifference::new(
+      self.iter().cloned(),
+      other.iter().cloned(),
+    ))
+  }
+
+  /// Create a new set containing the values within `domain` that are
+  /// not part of `self`.
+  ///
+  /// Ranges not containing any values of the domain of `T` are not
+  /// part of the result. Hence, the complement of a set covering all
+  /// values is empty.
+  ///
+  /// ```rust
+  /// # use gaps::GapSet;
+  ///
+  /// let mut set = GapSet::new();
+  /// set.insert_range(0u8..);
+  /// assert!(set.complement(..).is_empty());
+  /// ```
+  pub fn complement<R>(&self, domain: R) -> Self
+  where
+    R: RangeBounds<T>,
+  {
+    Self::from_ranges(self.gaps(domain))
+  }
+}
+
+/// Binary encoding of sets of unsigned integers.
+///
+/// The encoding is stable and consists of a sequence of LEB128 encoded
+/// integers. The first is the number of covered ranges. Each range then
+/// is represented by two integers, with the range's first and last
+/// value being delta encoded:
+/// - the distance of the range's first value from the first value that
+///   could start a range: zero for the first range, and two past the
+///   last value of the preceding range otherwise
+/// - the number of values in the range minus one
+impl<T> GapSet<T>
+where
+  T: Clone + Ord + Inc + Domain + Into<u64> + TryFrom<u64>,
+{
+  /// Retrieve an iterator over the first and last value of each
+  /// covered range.
+  ///
+  /// Ranges not containing any values are skipped.
+  fn inclusive_ranges(&self) -> impl Iterator<Item = (u64, u64)> + '_ {
+    self.ranges.iter().filter_map(|range| {
+      let first = match range.start() {
+        Included(start) => start.clone().into(),
+        Excluded(start) => start.clone().checked_inc()?.into(),
+        Unbounded => T::MIN.into(),
+      };
+      let last = match range.end() {
+        Included(end) => end.clone().into(),
+        Excluded(end) => end.clone().into().checked_sub(1)?,
+        Unbounded => T::MAX.into(),
+      };
+      if first > last {
+        return None
+      }
+      Some((first, last))
+    })
+  }
+
+  /// Encode the set in its compact binary representation.
+  ///
+  /// ```rust
+  /// # use gaps::GapSet;
+  ///
+  /// let set = vec![1_000_000u64..1_000_005, 1_000_010..1_000_020]
+  ///   .into_iter()
+  ///   .collect::<GapSet<_>>();
+  /// let bytes = set.to_bytes();
+  /// assert_eq!(bytes.len(), 7);
+  ///
+  /// let decoded = GapSet::<u64>::from_bytes(&bytes).unwrap();
+  /// assert!(decoded.contains(1_000_004));
+  /// assert!(!decoded.contains(1_000_005));
+  /// assert_eq!(decoded.range_count(), 2);
+  /// ```
+  pub fn to_bytes(&self) -> Vec<u8> {
+    let ranges = self.inclusive_ranges().collect::<Vec<_>>();
+    let mut bytes = Vec::new();
+    write_varint(&mut bytes, ranges.len() as u64);
+
+    let mut base = 0u64;
+    for (first, last) in ranges {
+      write_varint(&mut bytes, first - base);
+      write_varint(&mut bytes, last - first);
+      // Ranges are non-adjacent and so the next range can start two
+      // values past the last one at the earliest. If there is no such
+      // value, this range is the last one.
+      base = last.wrapping_add(2);
+    }
+    bytes
+  }
+
+  /// Decode a set from its compact binary representation, as produced
+  /// by [`GapSet::to_bytes`].
+  ///
+  /// The decoded set compares equal to the one that was encoded.
+  pub fn from_bytes(mut bytes: &[u8]) -> Result<Self, DecodeError> {
+    let count = read_varint(&mut bytes)?;
+    let mut ranges = Vec::new();
+    let mut base = Some(0u64);
+
+    for _ in 0..count {
+      let delta = read_varint(&mut bytes)?;
+      let len = read_varint(&mut bytes)?;
+      let first = base
+        .and_then(|base| base.checked_add(delta))
+        .ok_or(DecodeError::Overflow)?;
+      let last = first.checked_add(len).ok_or(DecodeError::Overflow)?;
+      base = last.checked_add(2);
+
+      let first = T::try_from(first).map_err(|_| DecodeError::Overflow)?;
+      let last = T::try_from(last).map_err(|_| DecodeError::Overflow)?;
+      ranges.push(Gap::new(Included(first), Included(last)));
+    }
+
+    if !bytes.is_empty() {
+      return Err(DecodeError::TrailingData)
+    }
+    Ok(Self::from_ranges(ranges.into_iter()))
+  }
+
+  /// Encode the set in the index representation understood by
+  /// [`GapSetRef`][crate::GapSetRef].
+  ///
+  /// In contrast to the representation produced by
+  /// [`GapSet::to_bytes`], this one is not compact, but it supports
+  /// queries directly on the encoded data.
+  pub fn to_index_bytes(&self) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(self.ranges.len() * RANGE_SIZE);
+    for (first, last) in self.inclusive_ranges() {
+      bytes.extend_from_slice(&first.to_le_bytes());
+      bytes.extend_from_slice(&last.to_le_bytes());
+    }
+    bytes
+  }
+}
+
+impl<T> Default for GapSet<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+/// Collect a set from ranges, in any order.
+///
+/// ```rust
+/// use std::ops::Bound;
+/// # use gaps::Gap;
+/// # use gaps::GapSet;
+///
+/// let set = vec![
+///   Gap::new(Bound::Included(5), Bound::Excluded(8)),
+///   Gap::new(Bound::Included(0), Bound::Included(4)),
+/// ]
+/// .into_iter()
+/// .collect::<GapSet<_>>();
+/// assert_eq!(set.as_slice(), &[Gap::new(Bound::Included(0), Bound::Excluded(8))]);
+/// ```
+impl<T> FromIterator<Gap<T>> for GapSet<T>
+where
+  T: Clone + Ord + Inc + Domain,
+{
+  fn from_iter<I>(iter: I) -> Self
+  where
+    I: IntoIterator<Item = Gap<T>>,
+  {
+    let mut ranges = iter.into_iter().filter_map(normalize).collect::<Vec<_>>();
+    // The sort is stable and linear for already sorted input.
+    ranges.sort();
+
+    Self::from_ranges(Union::new(ranges.into_iter(), empty()))
+  }
+}
+
+/// Collect a set from `Range` objects, in any order.
+///
+/// ```rust
+/// use std::ops::Bound;
+/// # use gaps::Gap;
+/// # use gaps::GapSet;
+///
+/// let set = vec![4..6, 0..2, 1..3].into_iter().collect::<GapSet<_>>();
+/// assert_eq!(
+///   set.as_slice(),
+///   &[
+///     Gap::new(Bound::Included(0), Bound::Excluded(3)),
+///     Gap::new(Bound::Included(4), Bound::Excluded(6)),
+///   ]
+/// );
+/// ```
+impl<T> FromIterator<Range<T>> for GapSet<T>
+where
+  T: Clone + Ord + Inc + Domain,
+{
+  fn from_iter<I>(iter: I) -> Self
+  where
+    I: IntoIterator<Item = Range<T>>,
+  {
+    iter.into_iter().map(Gap::from).collect()
+  }
+}
+
+/// Collect a set from individual values, in any order.
+///
+/// ```rust
+/// use std::ops::Bound;
+/// # use maplit::btreeset;
+/// # use gaps::Gap;
+/// # use gaps::GapSet;
+///
+/// let set = btreeset!{1, 2, 3, 7}.into_iter().collect::<GapSet<_>>();
+/// assert_eq!(
+///   set.as_slice(),
+///   &[
+///     Gap::new(Bound::Included(1), Bound::Excluded(4)),
+///     Gap::new(Bound::Included(7), Bound::Excluded(8)),
+///   ]
+/// );
+/// ```
+impl<T> FromIterator<T> for GapSet<T>
+where
+  T: Clone + Ord + Inc + Domain,
+{
+  fn from_iter<I>(iter: I) -> Self
+  where
+    I: IntoIterator<Item = T>,
+  {
+    iter
+      .into_iter()
+      .map(|value| Gap::new(Included(value.clone()), Included(value)))
+      .collect()
+  }
+}
+
+impl<T> Extend<Gap<T>> for GapSet<T>
+where
+  T: Clone + Ord + Inc + Domain,
+{
+  fn extend<I>(&mut self, iter: I)
+  where
+    I: IntoIterator<Item = Gap<T>>,
+  {
+    *self = self.union(&Self::from_iter(iter));
+  }
+}
+
+impl<T> Extend<Range<T>> for GapSet<T>
+where
+  T: Clone + Ord + Inc + Domain,
+{
+  fn extend<I>(&mut self, iter: I)
+  where
+    I: IntoIterator<Item = Range<T>>,
+  {
+    self.extend(iter.into_iter().map(Gap::from))
+  }
+}
+
+impl<T> Extend<T> for GapSet<T>
+where
+  T: Clone + Ord + Inc + Domain,
+{
+  fn extend<I>(&mut self, iter: I)
+  where
+    I: IntoIterator<Item = T>,
+  {
+    self.extend(
+      iter
+        .into_iter()
+        .map(|value| Gap::new(Included(value.clone()), Included(value))),
+    )
+  }
+}
+
+/// A `GapSet` is serialized as a sorted sequence of `[start, end]`
+/// pairs of bounds, one for each covered range.
+#[cfg(feature = "serde")]
+impl<T> Serialize for GapSet<T>
+where
+  T: Serialize,
+{
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    serializer.collect_seq(self.ranges.iter().map(|range| (range.start(), range.end())))
+  }
+}
+
+/// A `GapSet` is deserialized from a sequence of `[start, end]` pairs
+/// of bounds. Pairs forming a range without any values are rejected.
+/// The ranges are sorted and merged, just as when collecting a
+/// `GapSet` from an iterator.
+#[cfg(feature = "serde")]
+impl<'de, T> Deserialize<'de> for GapSet<T>
+where
+  T: Clone + Ord + Inc + Domain + Deserialize<'de>,
+{
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    let ranges = Vec::<(Bound<T>, Bound<T>)>::deserialize(deserializer)?
+      .into_iter()
+      .map(|range| normalize(Gap::from(range)))
+      .collect::<Option<Vec<_>>>()
+      .ok_or_else(|| D::Error::custom("encountered empty range"))?;
+    Ok(Self::from_iter(ranges))
+  }
+}
+
+impl<T> BitOr<&GapSet<T>> for &GapSet<T>
+where
+  T: Clone + Ord + Inc + Domain,
+{
+  type Output = GapSet<T>;
+
+  /// Create the union of two sets.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use gaps::Gap;
+  /// # use gaps::GapSet;
+  ///
+  /// let mut a = GapSet::new();
+  /// a.insert_range(0..5);
+  /// let mut b = GapSet::new();
+  /// b.insert_range(5..8);
+  /// let union = &a | &b;
+  /// assert_eq!(union.as_slice(), &[Gap::new(Bound::Included(0), Bound::Excluded(8))]);
+  /// ```
+  fn bitor(self, other: &GapSet<T>) -> GapSet<T> {
+    self.union(other)
+  }
+}
+
+impl<T> BitAnd<&GapSet<T>> for &GapSet<T>
+where
+  T: Clone + Ord + Inc + Domain,
+{
+  type Output = GapSet<T>;
+
+  /// Create the intersection of two sets.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use gaps::Gap;
+  /// # use gaps::GapSet;
+  ///
+  /// let mut a = GapSet::new();
+  /// a.insert_range(0..5);
+  /// let mut b = GapSet::new();
+  /// b.insert_range(3..8);
+  /// let intersection = &a & &b;
+  /// assert_eq!(intersection.as_slice(), &[Gap::new(Bound::Included(3), Bound::Excluded(5))]);
+  /// ```
+  fn bitand(self, other: &GapSet<T>) -> GapSet<T> {
+    self.intersection(other)
+  }
+}
+
+impl<T> Sub<&GapSet<T>> for &GapSet<T>
+where
+  T: Clone + Ord + Inc + Domain,
+{
+  type Output = GapSet<T>;
+
+  /// Create the difference of two sets.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use gaps::Gap;
+  /// # use gaps::GapSet;
+  ///
+  /// let mut a = GapSet::new();
+  /// a.insert_range(0..5);
+  /// let mut b = GapSet::new();
+  /// b.insert_range(3..8);
+  /// let difference = &a - &b;
+  /// assert_eq!(difference.as_slice(), &[Gap::new(Bound::Included(0), Bound::Excluded(3))]);
+  /// ```
+  fn sub(self, other: &GapSet<T>) -> GapSet<T> {
+    self.difference(other)
+  }
+}
+
+impl<T> BitXor<&GapSet<T>> for &GapSet<T>
+where
+  T: Clone + Ord + Inc + Domain,
+{
+  type Output = GapSet<T>;
+
+  /// Create the symmetric difference of two sets.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use gaps::Gap;
+  /// # use gaps::GapSet;
+  ///
+  /// let mut a = GapSet::new();
+  /// a.insert_range(0..5);
+  /// let mut b = GapSet::new();
+  /// b.insert_range(3..8);
+  /// let symmetric_difference = &a ^ &b;
+  /// assert_eq!(
+  ///   symmetric_difference.as_slice(),
+  ///   &[
+  ///     Gap::new(Bound::Included(0), Bound::Excluded(3)),
+  ///     Gap::new(Bound::Included(5), Bound::Excluded(8)),
+  ///   ],
+  /// );
+  /// ```
+  fn bitxor(self, other: &GapSet<T>) -> GapSet<T> {
+    self.symmetric_difference(other)
+  }
+}
+
+impl<T> Not for &GapSet<T>
+where
+  T: Clone + Ord + Inc + Domain,
+{
+  type Output = GapSet<T>;
+
+  /// Create the complement of a set within the full domain of its
+  /// values.
+  ///
+  /// Use [`GapSet::complement`] to restrict the result to a narrower
+  /// domain.
+  ///
+  /// ```rust
+  /// use std::ops::Bound;
+  /// # use gaps::Gap;
+  /// # use gaps::GapSet;
+  ///
+  /// let mut set = GapSet::new();
+  /// set.insert_range(3u8..5);
+  /// let complement = !&set;
+  /// assert_eq!(
+  ///   complement.as_slice(),
+  ///   &[
+  ///     Gap::new(Bound::Included(0), Bound::Excluded(3)),
+  ///     Gap::new(Bound::Included(5), Bound::Included(u8::MAX)),
+  ///   ],
+  /// );
+  /// ```
+  fn not(self) -> GapSet<T> {
+    self.complement(..)
+  }
+}
+
+impl<T> IntoIterator for GapSet<T> {
+  type Item = Gap<T>;
+  type IntoIter = vec::IntoIter<Gap<T>>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.ranges.into_iter()
+  }
+}
+
+impl<'s, T> IntoIterator for &'s GapSet<T> {
+  type Item = &'s Gap<T>;
+  type IntoIter = slice::Iter<'s, Gap<T>>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.ranges.iter()
+  }
+}
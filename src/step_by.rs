@@ -0,0 +1,155 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::cmp::Ordering;
+use std::hash::Hash;
+use std::hash::Hasher;
+
+use crate::Inc;
+
+
+/// A value along with the step by which it advances when incremented.
+///
+/// Wrapping values in `StepBy` allows for finding gaps in sequences
+/// advancing by something other than one, while retaining the original
+/// values in the reported bounds. Comparison, equality, and hashing
+/// only consider the value itself. All values taking part in gap
+/// detection are expected to share the same step.
+///
+/// ```rust
+/// use std::ops::Bound;
+/// # use maplit::btreeset;
+/// # use gaps::RangeGappable as _;
+/// # use gaps::StepBy;
+///
+/// let page = |offset| StepBy::new(offset, 4096u64);
+/// let pages = btreeset!{page(0), page(4096), page(16384)};
+/// let missing = pages.missing(page(0)..page(20480)).map(StepBy::into_inner).collect::<Vec<_>>();
+/// assert_eq!(missing, vec![8192, 12288]);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct StepBy<T> {
+  /// The wrapped value.
+  value: T,
+  /// The amount by which the value advances when incremented.
+  step: T,
+}
+
+impl<T> StepBy<T> {
+  /// Create a new `StepBy` object wrapping the provided value.
+  pub fn new(value: T, step: T) -> Self {
+    Self { value, step }
+  }
+
+  /// Retrieve the wrapped value.
+  pub fn value(&self) -> &T {
+    &self.value
+  }
+
+  /// Retrieve the amount by which the value advances when incremented.
+  pub fn step(&self) -> &T {
+    &self.step
+  }
+
+  /// Destructure the object into the wrapped value.
+  pub fn into_inner(self) -> T {
+    self.value
+  }
+}
+
+impl<T> PartialEq for StepBy<T>
+where
+  T: PartialEq,
+{
+  fn eq(&self, other: &Self) -> bool {
+    self.value == other.value
+  }
+}
+
+impl<T> Eq for StepBy<T> where T: Eq {}
+
+impl<T> PartialOrd for StepBy<T>
+where
+  T: PartialOrd,
+{
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    self.value.partial_cmp(&other.value)
+  }
+}
+
+impl<T> Ord for StepBy<T>
+where
+  T: Ord,
+{
+  fn cmp(&self, other: &Self) -> Ordering {
+    self.value.cmp(&other.value)
+  }
+}
+
+impl<T> Hash for StepBy<T>
+where
+  T: Hash,
+{
+  fn hash<H>(&self, state: &mut H)
+  where
+    H: Hasher,
+  {
+    self.value.hash(state)
+  }
+}
+
+macro_rules! step_by {
+  ( $t:ty ) => {
+    impl Inc for StepBy<$t> {
+      fn inc(self) -> Self {
+        self
+          .checked_inc()
+          .expect("attempt to increment past maximum value")
+      }
+
+      fn checked_inc(self) -> Option<Self> {
+        let value = self.value.checked_add(self.step)?;
+        Some(Self::new(value, self.step))
+      }
+    }
+  };
+}
+
+step_by!(u8);
+step_by!(i8);
+step_by!(u16);
+step_by!(i16);
+step_by!(u32);
+step_by!(i32);
+step_by!(u64);
+step_by!(i64);
+step_by!(u128);
+step_by!(i128);
+step_by!(usize);
+step_by!(isize);
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+
+  /// Check that `StepBy` values advance by their step.
+  #[test]
+  fn increment() {
+    assert_eq!(StepBy::new(5u8, 5).inc().into_inner(), 10);
+    assert_eq!(StepBy::new(-10i32, 3).inc().into_inner(), -7);
+    assert_eq!(
+      StepBy::new(250u8, 5).checked_inc().map(StepBy::into_inner),
+      Some(255)
+    );
+    assert_eq!(StepBy::new(251u8, 5).checked_inc(), None);
+  }
+
+  /// Check that only the value is considered for comparison.
+  #[test]
+  fn comparison() {
+    assert_eq!(StepBy::new(5u8, 5), StepBy::new(5, 1));
+    assert!(StepBy::new(4u8, 5) < StepBy::new(5, 1));
+  }
+}
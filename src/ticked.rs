@@ -0,0 +1,207 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::cmp::Ordering;
+#[cfg(feature = "time")]
+use std::convert::TryFrom as _;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::time::Duration;
+use std::time::SystemTime;
+
+#[cfg(feature = "chrono")]
+use chrono::NaiveDateTime;
+#[cfg(feature = "time")]
+use time::OffsetDateTime;
+
+use crate::Inc;
+
+
+/// A point in time along with the granularity at which such points are
+/// expected to occur.
+///
+/// Incrementing a `Ticked` value advances it by its tick. Comparison,
+/// equality, and hashing only consider the value itself. All values
+/// taking part in gap detection are expected to share the same tick
+/// and to be multiples of it relative to some common origin.
+#[derive(Clone, Copy, Debug)]
+pub struct Ticked<T> {
+  /// The wrapped value.
+  value: T,
+  /// The granularity of values.
+  tick: Duration,
+}
+
+impl<T> Ticked<T> {
+  /// Create a new `Ticked` object wrapping the provided value.
+  pub fn new(value: T, tick: Duration) -> Self {
+    Self { value, tick }
+  }
+
+  /// Retrieve the wrapped value.
+  pub fn value(&self) -> &T {
+    &self.value
+  }
+
+  /// Retrieve the granularity of values.
+  pub fn tick(&self) -> Duration {
+    self.tick
+  }
+
+  /// Destructure the object into the wrapped value.
+  pub fn into_inner(self) -> T {
+    self.value
+  }
+}
+
+impl<T> PartialEq for Ticked<T>
+where
+  T: PartialEq,
+{
+  fn eq(&self, other: &Self) -> bool {
+    self.value == other.value
+  }
+}
+
+impl<T> Eq for Ticked<T> where T: Eq {}
+
+impl<T> PartialOrd for Ticked<T>
+where
+  T: PartialOrd,
+{
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    self.value.partial_cmp(&other.value)
+  }
+}
+
+impl<T> Ord for Ticked<T>
+where
+  T: Ord,
+{
+  fn cmp(&self, other: &Self) -> Ordering {
+    self.value.cmp(&other.value)
+  }
+}
+
+impl<T> Hash for Ticked<T>
+where
+  T: Hash,
+{
+  fn hash<H>(&self, state: &mut H)
+  where
+    H: Hasher,
+  {
+    self.value.hash(state)
+  }
+}
+
+/// `Ticked` durations, incrementing by a caller chosen tick.
+///
+/// ```rust
+/// use std::time::Duration;
+/// # use gaps::Gappable as _;
+/// # use gaps::Ticked;
+///
+/// let tick = Duration::from_millis(500);
+/// let at = |ms| Ticked::new(Duration::from_millis(ms), tick);
+/// let chunks = vec![at(0), at(500), at(2000)];
+/// let missing = chunks.into_iter().missing(at(0)..at(2500)).collect::<Vec<_>>();
+/// assert_eq!(missing, vec![at(1000), at(1500)]);
+/// ```
+impl Inc for Ticked<Duration> {
+  fn inc(self) -> Self {
+    self
+      .checked_inc()
+      .expect("attempt to increment past maximum value")
+  }
+
+  fn checked_inc(self) -> Option<Self> {
+    let value = self.value.checked_add(self.tick)?;
+    Some(Self::new(value, self.tick))
+  }
+}
+
+/// `Ticked` system times, incrementing by a caller chosen tick.
+///
+/// ```rust
+/// use std::time::Duration;
+/// use std::time::SystemTime;
+/// # use gaps::Gappable as _;
+/// # use gaps::Ticked;
+///
+/// let tick = Duration::from_secs(10);
+/// let at = |s| Ticked::new(SystemTime::UNIX_EPOCH + Duration::from_secs(s), tick);
+/// let samples = vec![at(0), at(10), at(40)];
+/// let missing = samples.into_iter().missing(at(0)..at(50)).collect::<Vec<_>>();
+/// assert_eq!(missing, vec![at(20), at(30)]);
+/// ```
+impl Inc for Ticked<SystemTime> {
+  fn inc(self) -> Self {
+    self
+      .checked_inc()
+      .expect("attempt to increment past maximum value")
+  }
+
+  fn checked_inc(self) -> Option<Self> {
+    let value = self.value.checked_add(self.tick)?;
+    Some(Self::new(value, self.tick))
+  }
+}
+
+/// `Ticked` date times, incrementing by a caller chosen tick.
+///
+/// ```rust
+/// use std::time::Duration;
+/// # use chrono::NaiveDate;
+/// # use gaps::Gappable as _;
+/// # use gaps::Ticked;
+///
+/// let tick = Duration::from_secs(15 * 60);
+/// let at = |h, m| Ticked::new(NaiveDate::from_ymd_opt(2026, 1, 1).unwrap().and_hms_opt(h, m, 0).unwrap(), tick);
+/// let samples = vec![at(10, 0), at(10, 15), at(11, 0)];
+/// let missing = samples.into_iter().missing(at(10, 0)..at(11, 15)).collect::<Vec<_>>();
+/// assert_eq!(missing, vec![at(10, 30), at(10, 45)]);
+/// ```
+#[cfg(feature = "chrono")]
+impl Inc for Ticked<NaiveDateTime> {
+  fn inc(self) -> Self {
+    self
+      .checked_inc()
+      .expect("attempt to increment past maximum value")
+  }
+
+  fn checked_inc(self) -> Option<Self> {
+    let tick = chrono::Duration::from_std(self.tick).ok()?;
+    let value = self.value.checked_add_signed(tick)?;
+    Some(Self::new(value, self.tick))
+  }
+}
+
+/// `Ticked` offset date times, incrementing by a caller chosen tick.
+///
+/// ```rust
+/// use std::time::Duration;
+/// # use gaps::Gappable as _;
+/// # use gaps::Ticked;
+/// # use time::OffsetDateTime;
+///
+/// let tick = Duration::from_secs(1);
+/// let at = |s| Ticked::new(OffsetDateTime::from_unix_timestamp(s).unwrap(), tick);
+/// let samples = vec![at(100), at(101), at(104)];
+/// let missing = samples.into_iter().missing(at(100)..=at(104)).collect::<Vec<_>>();
+/// assert_eq!(missing, vec![at(102), at(103)]);
+/// ```
+#[cfg(feature = "time")]
+impl Inc for Ticked<OffsetDateTime> {
+  fn inc(self) -> Self {
+    self
+      .checked_inc()
+      .expect("attempt to increment past maximum value")
+  }
+
+  fn checked_inc(self) -> Option<Self> {
+    let tick = time::Duration::try_from(self.tick).ok()?;
+    let value = self.value.checked_add(tick)?;
+    Some(Self::new(value, self.tick))
+  }
+}
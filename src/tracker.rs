@@ -0,0 +1,121 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::iter::Cloned;
+use std::iter::Once;
+use std::ops::Bound::Excluded;
+use std::ops::Bound::Included;
+use std::ops::Bound::Unbounded;
+use std::slice;
+
+use crate::Difference;
+use crate::Domain;
+use crate::Gap;
+use crate::GapSet;
+use crate::Inc;
+
+
+/// A tracker of gaps in a stream of values that arrive one at a time,
+/// possibly out of order.
+///
+/// In contrast to a [`GapIter`][crate::GapIter], which inspects a
+/// sequence once, a `GapTracker` is fed values as they arrive and can
+/// be queried for the current gaps at any moment. Observed values are
+/// stored as disjoint ranges in a [`GapSet`], so that memory usage is
+/// proportional to the number of gaps, not the number of values.
+///
+/// ```rust
+/// use std::ops::Bound;
+/// # use gaps::Gap;
+/// # use gaps::GapTracker;
+///
+/// let mut tracker = GapTracker::new(0);
+/// for seq in vec![0, 1, 4, 2, 7] {
+///   tracker.observe(seq);
+/// }
+/// assert_eq!(tracker.highest(), Some(7));
+/// assert_eq!(tracker.first_missing(), Some(3));
+///
+/// let mut gaps = tracker.gaps();
+/// assert_eq!(gaps.next(), Some(Gap::new(Bound::Included(3), Bound::Excluded(4))));
+/// assert_eq!(gaps.next(), Some(Gap::new(Bound::Included(5), Bound::Excluded(7))));
+/// assert_eq!(gaps.next(), None);
+/// ```
+#[derive(Clone, Debug)]
+pub struct GapTracker<T> {
+  /// The first value of interest.
+  start: T,
+  /// The values observed so far.
+  observed: GapSet<T>,
+  /// The highest value observed so far, if any.
+  highest: Option<T>,
+}
+
+impl<T> GapTracker<T>
+where
+  T: Clone + Ord + Inc + Domain,
+{
+  /// Create a new `GapTracker` for values starting at `start`.
+  ///
+  /// Values less than `start` are considered not of interest and are
+  /// ignored.
+  pub fn new(start: T) -> Self {
+    Self {
+      start,
+      observed: GapSet::new(),
+      highest: None,
+    }
+  }
+
+  /// Record the arrival of `value`.
+  ///
+  /// Returns whether the value was newly observed. Values observed
+  /// before and values less than the tracker's start are not.
+  pub fn observe(&mut self, value: T) -> bool {
+    if value < self.start || !self.observed.insert(value.clone()) {
+      return false
+    }
+
+    if self.highest.as_ref().map_or(true, |highest| value > *highest) {
+      self.highest = Some(value);
+    }
+    true
+  }
+
+  /// Check whether `value` has been observed.
+  pub fn is_observed(&self, value: T) -> bool {
+    self.observed.contains(value)
+  }
+
+  /// Retrieve the highest value observed so far, if any.
+  pub fn highest(&self) -> Option<T> {
+    self.highest.clone()
+  }
+
+  /// Retrieve the lowest value of interest that has not yet been
+  /// observed.
+  ///
+  /// `None` is returned if all values starting at the tracker's start
+  /// up to the maximum value of the type have been observed.
+  pub fn first_missing(&self) -> Option<T> {
+    match self.observed.iter().next() {
+      Some(range) if self.observed.contains(self.start.clone()) => match range.end() {
+        Included(end) => end.clone().checked_inc(),
+        Excluded(end) => Some(end.clone()),
+        Unbounded => None,
+      },
+      _ => Some(self.start.clone()),
+    }
+  }
+
+  /// Retrieve an iterator over the gaps between the tracker's start
+  /// and the highest value observed so far.
+  #[allow(clippy::type_complexity)]
+  pub fn gaps(&self) -> Difference<Once<Gap<T>>, Cloned<slice::Iter<'_, Gap<T>>>, T> {
+    let end = match &self.highest {
+      Some(highest) => Excluded(highest.clone()),
+      None => Excluded(self.start.clone()),
+    };
+    self.observed.gaps_in((Included(self.start.clone()), end))
+  }
+}
@@ -0,0 +1,181 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::convert::TryInto as _;
+use std::iter::FusedIterator;
+use std::iter::Once;
+use std::ops::Bound::Excluded;
+use std::ops::Bound::Included;
+use std::ops::RangeBounds;
+use std::slice::ChunksExact;
+
+use crate::bounds::bounds;
+use crate::bounds::start_le_end;
+use crate::complement;
+use crate::DecodeError;
+use crate::Difference;
+use crate::Gap;
+
+
+/// The size of a single encoded range, in bytes.
+pub(crate) const RANGE_SIZE: usize = 16;
+
+
+/// Decode the first and last value of an encoded range.
+fn decode(chunk: &[u8]) -> (u64, u64) {
+  // `chunk` is always exactly `RANGE_SIZE` bytes long.
+  let first = u64::from_le_bytes(chunk[..8].try_into().unwrap());
+  let last = u64::from_le_bytes(chunk[8..].try_into().unwrap());
+  (first, last)
+}
+
+
+/// An iterator over the covered ranges of a [`GapSetRef`].
+#[derive(Clone, Debug)]
+pub struct RangesRef<'b> {
+  /// The encoded ranges.
+  chunks: ChunksExact<'b, u8>,
+}
+
+impl Iterator for RangesRef<'_> {
+  type Item = Gap<u64>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let (first, last) = decode(self.chunks.next()?);
+    // Ranges are reported in the same normalized form as used by
+    // `GapSet`.
+    let end = match last.checked_add(1) {
+      Some(end) => Excluded(end),
+      None => Included(last),
+    };
+    Some(Gap::new(Included(first), end))
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.chunks.size_hint()
+  }
+}
+
+impl ExactSizeIterator for RangesRef<'_> {}
+
+impl FusedIterator for RangesRef<'_> {}
+
+
+/// A read-only view of a [`GapSet`][crate::GapSet] of `u64` values,
+/// operating directly on its index representation.
+///
+/// The index representation, as produced by
+/// [`GapSet::to_index_bytes`][crate::GapSet::to_index_bytes], consists
+/// of the set's covered ranges in ascending order. Each range is
+/// encoded as its first and its last value, as 8 byte little endian
+/// integers. Because all ranges have the same size, queries are
+/// answered using binary search directly over the encoded data, without
+/// decoding it first. That makes this type suitable for working with
+/// large, memory-mapped files.
+///
+/// ```rust
+/// use std::ops::Bound;
+/// # use gaps::Gap;
+/// # use gaps::GapSet;
+/// # use gaps::GapSetRef;
+///
+/// let set = vec![0u64..10, 20..30].into_iter().collect::<GapSet<_>>();
+/// let bytes = set.to_index_bytes();
+///
+/// let view = GapSetRef::new(&bytes).unwrap();
+/// assert!(view.contains(25));
+/// assert!(!view.contains(10));
+/// let mut gaps = view.gaps_in(5..25);
+/// assert_eq!(gaps.next(), Some(Gap::new(Bound::Included(10), Bound::Excluded(20))));
+/// assert_eq!(gaps.next(), None);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GapSetRef<'b> {
+  /// The encoded ranges.
+  bytes: &'b [u8],
+}
+
+impl<'b> GapSetRef<'b> {
+  /// Create a new `GapSetRef` operating on the given index
+  /// representation.
+  ///
+  /// Only the size of `bytes` is validated. If they otherwise do not
+  /// contain a valid index representation, query results are
+  /// unspecified.
+  pub fn new(bytes: &'b [u8]) -> Result<Self, DecodeError> {
+    if bytes.len() % RANGE_SIZE != 0 {
+      return Err(DecodeError::Truncated)
+    }
+    Ok(Self { bytes })
+  }
+
+  /// Retrieve the number of disjoint ranges in the set.
+  ///
+  /// Note that this is not the number of values contained in the set.
+  pub fn range_count(&self) -> usize {
+    self.bytes.len() / RANGE_SIZE
+  }
+
+  /// Check whether the set contains no values.
+  pub fn is_empty(&self) -> bool {
+    self.bytes.is_empty()
+  }
+
+  /// Retrieve the first and last value of the range at `index`.
+  fn get(&self, index: usize) -> (u64, u64) {
+    decode(&self.bytes[index * RANGE_SIZE..(index + 1) * RANGE_SIZE])
+  }
+
+  /// Find the index of the first range for which `pred` is false,
+  /// assuming that it is true for all ranges before it.
+  fn partition_point<P>(&self, mut pred: P) -> usize
+  where
+    P: FnMut(u64, u64) -> bool,
+  {
+    let (mut lo, mut hi) = (0, self.range_count());
+    while lo < hi {
+      let mid = lo + (hi - lo) / 2;
+      let (first, last) = self.get(mid);
+      if pred(first, last) {
+        lo = mid + 1;
+      } else {
+        hi = mid;
+      }
+    }
+    lo
+  }
+
+  /// Retrieve an iterator over the covered ranges of the set.
+  pub fn iter(&self) -> RangesRef<'b> {
+    RangesRef {
+      chunks: self.bytes.chunks_exact(RANGE_SIZE),
+    }
+  }
+
+  /// Check whether `value` is contained in the set.
+  pub fn contains(&self, value: u64) -> bool {
+    let index = self.partition_point(|_first, last| last < value);
+    index < self.range_count() && self.get(index).0 <= value
+  }
+
+  /// Retrieve an iterator over the gaps of the set that lie within
+  /// `range`, clipped to it.
+  ///
+  /// This method is the equivalent of
+  /// [`GapSet::gaps_in`][crate::GapSet::gaps_in].
+  pub fn gaps_in<R>(&self, range: R) -> Difference<Once<Gap<u64>>, RangesRef<'b>, u64>
+  where
+    R: RangeBounds<u64>,
+  {
+    let (start, end) = bounds(&range);
+    let lo = self.partition_point(|_first, last| !start_le_end(&start, &Included(last)));
+    let hi = self
+      .partition_point(|first, _last| start_le_end(&Included(first), &end))
+      .max(lo);
+
+    let ranges = RangesRef {
+      chunks: self.bytes[lo * RANGE_SIZE..hi * RANGE_SIZE].chunks_exact(RANGE_SIZE),
+    };
+    complement(ranges, (start, end))
+  }
+}
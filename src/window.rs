@@ -0,0 +1,186 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::ops::Bound;
+use std::ops::Bound::Excluded;
+use std::ops::Bound::Included;
+use std::ops::Bound::Unbounded;
+
+use crate::bounds::start_le_end;
+use crate::Domain;
+use crate::Gap;
+use crate::GapSet;
+use crate::Step;
+
+
+/// A tracker of received sequence numbers within a sliding window, as
+/// used by protocols acknowledging data selectively.
+///
+/// Sequence numbers less than the window's base are considered
+/// received and acknowledged cumulatively. Those inside of the window
+/// are tracked individually, while those beyond it are rejected. Hence,
+/// memory usage is bounded by the window size.
+///
+/// ```rust
+/// use std::ops::Bound;
+/// # use gaps::Gap;
+/// # use gaps::SeqWindow;
+///
+/// let mut window = SeqWindow::new(100u32, 64);
+/// for seq in vec![100, 101, 104, 105, 108] {
+///   window.observe(seq);
+/// }
+/// assert_eq!(window.advance_base(), Some(102));
+///
+/// let acks = window.ack_ranges(4);
+/// assert_eq!(
+///   acks,
+///   vec![
+///     Gap::new(Bound::Included(104), Bound::Excluded(106)),
+///     Gap::new(Bound::Included(108), Bound::Excluded(109)),
+///   ]
+/// );
+/// let missing = window.missing_ranges(1);
+/// assert_eq!(missing, vec![Gap::new(Bound::Included(102), Bound::Excluded(104))]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct SeqWindow<T> {
+  /// The lowest sequence number not yet received, or `None` if all
+  /// sequence numbers up to the maximum have been received.
+  base: Option<T>,
+  /// The number of sequence numbers covered by the window.
+  size: usize,
+  /// The sequence numbers received inside of the window.
+  received: GapSet<T>,
+}
+
+impl<T> SeqWindow<T>
+where
+  T: Clone + Ord + Step + Domain,
+{
+  /// Create a new `SeqWindow` starting at `base` and spanning `size`
+  /// sequence numbers.
+  pub fn new(base: T, size: usize) -> Self {
+    Self {
+      base: Some(base),
+      size,
+      received: GapSet::new(),
+    }
+  }
+
+  /// Retrieve the lowest sequence number not yet received, i.e., the
+  /// cumulative acknowledgment point, as of the last advancement.
+  ///
+  /// `None` is returned once all sequence numbers up to the maximum
+  /// have been received.
+  pub fn base(&self) -> Option<T> {
+    self.base.clone()
+  }
+
+  /// Retrieve the end of a window starting at `base`.
+  fn end(&self, base: &T) -> Bound<T> {
+    match base.clone().forward(self.size) {
+      Some(end) => Excluded(end),
+      None => Unbounded,
+    }
+  }
+
+  /// Check whether `seq` lies inside of the window.
+  pub fn in_window(&self, seq: T) -> bool {
+    match &self.base {
+      Some(base) => seq >= *base && start_le_end(&Included(seq), &self.end(base)),
+      None => false,
+    }
+  }
+
+  /// Record the receipt of `seq`.
+  ///
+  /// Returns whether the sequence number was newly recorded. Sequence
+  /// numbers received before or lying outside of the window are not.
+  pub fn observe(&mut self, seq: T) -> bool {
+    self.in_window(seq.clone()) && self.received.insert(seq)
+  }
+
+  /// Advance the window's base past all sequence numbers received
+  /// contiguously, returning the new base.
+  ///
+  /// `None` is returned once all sequence numbers up to the maximum
+  /// have been received.
+  pub fn advance_base(&mut self) -> Option<T> {
+    let base = self.base.clone()?;
+    if !self.received.contains(base.clone()) {
+      return Some(base)
+    }
+
+    let next = self
+      .received
+      .iter()
+      .next()
+      .and_then(|range| match range.end() {
+        Included(end) => end.clone().checked_inc(),
+        Excluded(end) => Some(end.clone()),
+        Unbounded => None,
+      });
+    match next {
+      Some(next) => {
+        let _removed = self.received.remove_range(..next.clone());
+        self.base = Some(next);
+      },
+      // All sequence numbers up to the maximum have been received. We
+      // keep the final range around to report it in acknowledgments.
+      None => {
+        let _removed = self.received.remove_range(..base);
+        self.base = None;
+      },
+    }
+    self.base.clone()
+  }
+
+  /// Retrieve up to `max_blocks` ranges of sequence numbers received
+  /// past the window's base, in ascending order.
+  ///
+  /// These are the ranges to report in a selective acknowledgment.
+  /// Once all sequence numbers up to the maximum have been received,
+  /// the final range extending to it is reported.
+  pub fn ack_ranges(&self, max_blocks: usize) -> Vec<Gap<T>> {
+    self
+      .received
+      .iter()
+      .filter(|range| {
+        self
+          .base
+          .as_ref()
+          .map_or(true, |base| !range.contains(base))
+      })
+      .take(max_blocks)
+      .cloned()
+      .collect()
+  }
+
+  /// Retrieve up to `max_blocks` ranges of sequence numbers missing
+  /// between the window's base and the highest sequence number
+  /// received, in ascending order.
+  ///
+  /// These are the ranges to report in a negative acknowledgment.
+  pub fn missing_ranges(&self, max_blocks: usize) -> Vec<Gap<T>> {
+    let base = match &self.base {
+      Some(base) => base.clone(),
+      None => return Vec::new(),
+    };
+    let end = match self.received.iter().next_back() {
+      Some(range) => range.start().clone(),
+      None => return Vec::new(),
+    };
+    let end = match end {
+      Included(end) => Excluded(end),
+      Excluded(end) => Included(end),
+      Unbounded => return Vec::new(),
+    };
+
+    self
+      .received
+      .gaps_in((Included(base), end))
+      .take(max_blocks)
+      .collect()
+  }
+}
@@ -3,13 +3,42 @@
 
 use std::collections::BTreeMap;
 use std::collections::BTreeSet;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::convert::TryFrom as _;
+use std::iter::from_fn;
+use std::num::NonZeroI8;
+use std::num::NonZeroU32;
 use std::ops::Bound::Excluded;
 use std::ops::Bound::Included;
 use std::ops::Bound::Unbounded;
+use std::ops::Range;
+use std::time::Duration;
+use std::time::SystemTime;
 
+use gaps::complement;
 use gaps::range::bounds;
+use gaps::range::resolve;
+use gaps::DecodeError;
+use gaps::Fit;
+use gaps::Gap;
+use gaps::GapList;
+use gaps::GapSet;
+use gaps::GapSetRef;
+use gaps::GapTracker;
 use gaps::Gappable as _;
+use gaps::GappableByKey as _;
+use gaps::HoleGappable as _;
+use gaps::Inc as _;
+use gaps::IntervalGappable as _;
+use gaps::MapGappable as _;
 use gaps::RangeGappable as _;
+use gaps::Segment;
+use gaps::SeqWindow;
+use gaps::Serial;
+use gaps::SerialGappable as _;
+use gaps::StepBy;
+use gaps::Ticked;
 
 use maplit::btreemap;
 use maplit::btreeset;
@@ -20,33 +49,36 @@ fn set_gap_iteration_empty() {
   let r = BTreeSet::<usize>::new();
   assert_eq!(
     r.gaps(0..=0).collect::<Vec<_>>(),
-    vec![(Included(0), Included(0))]
+    vec![Gap::new(Included(0), Included(0))]
   );
   assert_eq!(
     r.gaps(0..=1).collect::<Vec<_>>(),
-    vec![(Included(0), Included(1))]
+    vec![Gap::new(Included(0), Included(1))]
   );
   assert_eq!(
     r.gaps(0..=2).collect::<Vec<_>>(),
-    vec![(Included(0), Included(2))]
+    vec![Gap::new(Included(0), Included(2))]
   );
   assert_eq!(
     r.gaps(1..=3).collect::<Vec<_>>(),
-    vec![(Included(1), Included(3))]
+    vec![Gap::new(Included(1), Included(3))]
   );
   assert_eq!(
     r.gaps(0..).collect::<Vec<_>>(),
-    vec![(Included(0), Unbounded)]
+    vec![Gap::new(Included(0), Unbounded)]
   );
   assert_eq!(
     r.gaps(..0).collect::<Vec<_>>(),
-    vec![(Unbounded, Excluded(0))]
+    vec![Gap::new(Unbounded, Excluded(0))]
   );
   assert_eq!(
     r.gaps(..=0).collect::<Vec<_>>(),
-    vec![(Unbounded, Included(0))]
+    vec![Gap::new(Unbounded, Included(0))]
+  );
+  assert_eq!(
+    r.gaps(..).collect::<Vec<_>>(),
+    vec![Gap::new(Unbounded, Unbounded)]
   );
-  assert_eq!(r.gaps(..).collect::<Vec<_>>(), vec![(Unbounded, Unbounded)]);
 }
 
 #[test]
@@ -58,35 +90,47 @@ fn set_gap_iteration_1() {
 
   assert_eq!(
     r.gaps(0..=0).collect::<Vec<_>>(),
-    vec![(Included(0), Included(0))]
+    vec![Gap::new(Included(0), Included(0))]
   );
   assert_eq!(
     r.gaps(0..=1).collect::<Vec<_>>(),
-    vec![(Included(0), Included(1))]
+    vec![Gap::new(Included(0), Included(1))]
   );
   assert_eq!(
     r.gaps(0..=2).collect::<Vec<_>>(),
-    vec![(Included(0), Excluded(2))]
+    vec![Gap::new(Included(0), Excluded(2))]
   );
   assert_eq!(
     r.gaps(1..=2).collect::<Vec<_>>(),
-    vec![(Included(1), Excluded(2))]
+    vec![Gap::new(Included(1), Excluded(2))]
   );
   assert_eq!(
     r.gaps(0..=3).collect::<Vec<_>>(),
-    vec![(Included(0), Excluded(2)), (Excluded(2), Included(3))]
+    vec![
+      Gap::new(Included(0), Excluded(2)),
+      Gap::new(Excluded(2), Included(3))
+    ]
   );
   assert_eq!(
     r.gaps(0..).collect::<Vec<_>>(),
-    vec![(Included(0), Excluded(2)), (Excluded(2), Unbounded)]
+    vec![
+      Gap::new(Included(0), Excluded(2)),
+      Gap::new(Excluded(2), Unbounded)
+    ]
   );
   assert_eq!(
     r.gaps(..9).collect::<Vec<_>>(),
-    vec![(Unbounded, Excluded(2)), (Excluded(2), Excluded(9))]
+    vec![
+      Gap::new(Unbounded, Excluded(2)),
+      Gap::new(Excluded(2), Excluded(9))
+    ]
   );
   assert_eq!(
     r.gaps(..).collect::<Vec<_>>(),
-    vec![(Unbounded, Excluded(2)), (Excluded(2), Unbounded)]
+    vec![
+      Gap::new(Unbounded, Excluded(2)),
+      Gap::new(Excluded(2), Unbounded)
+    ]
   );
 }
 
@@ -100,36 +144,51 @@ fn set_gap_iteration_2() {
 
   assert_eq!(
     r.gaps(0..=0).collect::<Vec<_>>(),
-    vec![(Included(0), Included(0))]
+    vec![Gap::new(Included(0), Included(0))]
   );
   assert_eq!(
     r.gaps(0..=1).collect::<Vec<_>>(),
-    vec![(Included(0), Excluded(1))]
+    vec![Gap::new(Included(0), Excluded(1))]
   );
   assert_eq!(
     r.gaps(0..=2).collect::<Vec<_>>(),
-    vec![(Included(0), Excluded(1))]
+    vec![Gap::new(Included(0), Excluded(1))]
   );
   assert_eq!(r.gaps(1..=2).collect::<Vec<_>>(), vec![]);
   assert_eq!(
     r.gaps(0..=3).collect::<Vec<_>>(),
-    vec![(Included(0), Excluded(1)), (Excluded(2), Included(3))]
+    vec![
+      Gap::new(Included(0), Excluded(1)),
+      Gap::new(Excluded(2), Included(3))
+    ]
   );
   assert_eq!(
     r.gaps(0..=6).collect::<Vec<_>>(),
-    vec![(Included(0), Excluded(1)), (Excluded(2), Included(6))]
+    vec![
+      Gap::new(Included(0), Excluded(1)),
+      Gap::new(Excluded(2), Included(6))
+    ]
   );
   assert_eq!(
     r.gaps(0..).collect::<Vec<_>>(),
-    vec![(Included(0), Excluded(1)), (Excluded(2), Unbounded)]
+    vec![
+      Gap::new(Included(0), Excluded(1)),
+      Gap::new(Excluded(2), Unbounded)
+    ]
   );
   assert_eq!(
     r.gaps(..9).collect::<Vec<_>>(),
-    vec![(Unbounded, Excluded(1)), (Excluded(2), Excluded(9))]
+    vec![
+      Gap::new(Unbounded, Excluded(1)),
+      Gap::new(Excluded(2), Excluded(9))
+    ]
   );
   assert_eq!(
     r.gaps(..).collect::<Vec<_>>(),
-    vec![(Unbounded, Excluded(1)), (Excluded(2), Unbounded)]
+    vec![
+      Gap::new(Unbounded, Excluded(1)),
+      Gap::new(Excluded(2), Unbounded)
+    ]
   );
 }
 
@@ -144,64 +203,70 @@ fn set_gap_iteration_3() {
 
   assert_eq!(
     r.gaps(0..=0).collect::<Vec<_>>(),
-    vec![(Included(0), Included(0))]
+    vec![Gap::new(Included(0), Included(0))]
   );
   assert_eq!(
     r.gaps(0..=1).collect::<Vec<_>>(),
-    vec![(Included(0), Excluded(1))]
+    vec![Gap::new(Included(0), Excluded(1))]
   );
   assert_eq!(
     r.gaps(0..=2).collect::<Vec<_>>(),
-    vec![(Included(0), Excluded(1))]
+    vec![Gap::new(Included(0), Excluded(1))]
   );
   assert_eq!(r.gaps(1..=2).collect::<Vec<_>>(), vec![]);
   assert_eq!(
     r.gaps(0..=3).collect::<Vec<_>>(),
-    vec![(Included(0), Excluded(1)), (Excluded(2), Included(3))]
+    vec![
+      Gap::new(Included(0), Excluded(1)),
+      Gap::new(Excluded(2), Included(3))
+    ]
   );
   assert_eq!(
     r.gaps(0..=4).collect::<Vec<_>>(),
-    vec![(Included(0), Excluded(1)), (Excluded(2), Excluded(4))]
+    vec![
+      Gap::new(Included(0), Excluded(1)),
+      Gap::new(Excluded(2), Excluded(4))
+    ]
   );
   assert_eq!(
     r.gaps(0..=5).collect::<Vec<_>>(),
     vec![
-      (Included(0), Excluded(1)),
-      (Excluded(2), Excluded(4)),
-      (Excluded(4), Included(5))
+      Gap::new(Included(0), Excluded(1)),
+      Gap::new(Excluded(2), Excluded(4)),
+      Gap::new(Excluded(4), Included(5))
     ]
   );
   assert_eq!(
     r.gaps(0..=6).collect::<Vec<_>>(),
     vec![
-      (Included(0), Excluded(1)),
-      (Excluded(2), Excluded(4)),
-      (Excluded(4), Included(6))
+      Gap::new(Included(0), Excluded(1)),
+      Gap::new(Excluded(2), Excluded(4)),
+      Gap::new(Excluded(4), Included(6))
     ]
   );
 
   assert_eq!(
     r.gaps(0..).collect::<Vec<_>>(),
     vec![
-      (Included(0), Excluded(1)),
-      (Excluded(2), Excluded(4)),
-      (Excluded(4), Unbounded)
+      Gap::new(Included(0), Excluded(1)),
+      Gap::new(Excluded(2), Excluded(4)),
+      Gap::new(Excluded(4), Unbounded)
     ]
   );
   assert_eq!(
     r.gaps(..9).collect::<Vec<_>>(),
     vec![
-      (Unbounded, Excluded(1)),
-      (Excluded(2), Excluded(4)),
-      (Excluded(4), Excluded(9))
+      Gap::new(Unbounded, Excluded(1)),
+      Gap::new(Excluded(2), Excluded(4)),
+      Gap::new(Excluded(4), Excluded(9))
     ]
   );
   assert_eq!(
     r.gaps(..).collect::<Vec<_>>(),
     vec![
-      (Unbounded, Excluded(1)),
-      (Excluded(2), Excluded(4)),
-      (Excluded(4), Unbounded)
+      Gap::new(Unbounded, Excluded(1)),
+      Gap::new(Excluded(2), Excluded(4)),
+      Gap::new(Excluded(4), Unbounded)
     ]
   );
 }
@@ -212,20 +277,20 @@ fn iterator_gap_iteration() {
   let mut r = BTreeMap::<usize, &str>::new();
   assert_eq!(
     r.keys().copied().gaps(0..=0).collect::<Vec<_>>(),
-    vec![(Included(0), Included(0))]
+    vec![Gap::new(Included(0), Included(0))]
   );
 
   r.extend(btreemap! { 1 => "foo", 99 => "bar" });
   assert_eq!(
     r.keys().copied().gaps(0..2).collect::<Vec<_>>(),
-    vec![(Included(0), Excluded(1))]
+    vec![Gap::new(Included(0), Excluded(1))]
   );
   assert_eq!(
     r.keys().copied().gaps(0..).collect::<Vec<_>>(),
     vec![
-      (Included(0), Excluded(1)),
-      (Excluded(1), Excluded(99)),
-      (Excluded(99), Unbounded),
+      Gap::new(Included(0), Excluded(1)),
+      Gap::new(Excluded(1), Excluded(99)),
+      Gap::new(Excluded(99), Unbounded),
     ]
   );
 }
@@ -242,11 +307,11 @@ fn gap_iterator_cloning() {
   };
 
   let mut it1 = set.gaps(1..=7);
-  assert_eq!(it1.next().unwrap(), (Excluded(2), Excluded(4)));
+  assert_eq!(it1.next().unwrap(), Gap::new(Excluded(2), Excluded(4)));
 
   let mut it2 = it1.clone();
-  assert_eq!(it1.next().unwrap(), (Excluded(4), Excluded(7)));
-  assert_eq!(it2.next().unwrap(), (Excluded(4), Excluded(7)));
+  assert_eq!(it1.next().unwrap(), Gap::new(Excluded(4), Excluded(7)));
+  assert_eq!(it2.next().unwrap(), Gap::new(Excluded(4), Excluded(7)));
 
   assert_eq!(it1.next(), None);
   assert_eq!(it2.next(), None);
@@ -273,3 +338,2780 @@ fn extract_bounds() {
   assert_eq!(bounds(&(..4)), (Unbounded, Excluded(4)));
   assert_eq!(bounds::<_, u8>(&(..)), (Unbounded, Unbounded));
 }
+
+/// Check that the size hint reported by a [`GapIter`] is sensible.
+#[test]
+fn gap_iterator_size_hint() {
+  let set = btreeset! {1usize, 3, 4};
+  let mut it = set.iter().copied().gaps(0..=6);
+  assert_eq!(it.size_hint(), (0, Some(4)));
+
+  let _ = it.by_ref().count();
+  assert_eq!(it.size_hint(), (0, Some(0)));
+
+  let it = [0usize; 0].iter().copied().gaps(0usize..=6);
+  assert_eq!(it.size_hint(), (1, Some(1)));
+  assert_eq!(it.count(), 1);
+
+  let it = [0usize; 0].iter().copied().gaps(1usize..1);
+  assert_eq!(it.size_hint(), (0, Some(0)));
+  assert_eq!(it.count(), 0);
+}
+
+/// Check that resuming iteration from a [`GapIter`]'s remainder
+/// produces the outstanding gaps.
+#[test]
+fn gap_iterator_remainder() {
+  let set = btreeset! {1usize, 2, 4, 7, 9};
+  let expected = set.gaps(0..=8).collect::<Vec<_>>();
+
+  for i in 0..=expected.len() {
+    let mut it = set.gaps(0..=8);
+    let mut gaps = it.by_ref().take(i).collect::<Vec<_>>();
+    match it.remainder() {
+      Some(remainder) => gaps.extend(set.gaps(remainder)),
+      None => assert_eq!(it.next(), None),
+    }
+    assert_eq!(gaps, expected);
+  }
+}
+
+/// Check that we can advance a [`GapIter`] to a certain value.
+#[test]
+fn gap_iterator_advancing() {
+  let set = btreeset! {1usize, 3, 4, 8, 9};
+
+  let mut it = set.gaps(..);
+  it.advance_to(2);
+  assert_eq!(it.next(), Some(Gap::new(Included(2), Excluded(3))));
+  assert_eq!(it.next(), Some(Gap::new(Excluded(4), Excluded(8))));
+  // Advancing to a value we have already passed has no effect.
+  it.advance_to(0);
+  assert_eq!(it.next(), Some(Gap::new(Excluded(9), Unbounded)));
+  assert_eq!(it.next(), None);
+
+  let mut it = set.gaps(0..10);
+  it.advance_to(4);
+  assert_eq!(it.next(), Some(Gap::new(Excluded(4), Excluded(8))));
+  it.advance_to(10);
+  assert_eq!(it.next(), None);
+
+  let mut it = set.iter().copied().gaps(0..=12);
+  it.advance_to(5);
+  assert_eq!(it.next(), Some(Gap::new(Included(5), Excluded(8))));
+  assert_eq!(it.next(), Some(Gap::new(Excluded(9), Included(12))));
+  assert_eq!(it.next(), None);
+}
+
+/// Check that folding a [`GapIter`] produces the same gaps as
+/// iterating it externally.
+#[test]
+fn gap_iterator_folding() {
+  let set = btreeset! {1usize, 2, 4, 7, 9};
+  let ranges = [
+    bounds(&(0..=8)),
+    bounds(&(2..9)),
+    bounds(&(3..)),
+    bounds(&(..)),
+    bounds(&(5..5)),
+    (Excluded(1), Excluded(2)),
+  ];
+
+  for range in ranges.iter() {
+    let mut it = set.gaps(*range);
+    let expected = from_fn(|| it.next()).collect::<Vec<_>>();
+
+    let mut it = set.gaps(*range);
+    // Consume an element via `next` to make sure the iterator's state
+    // is honored.
+    let first = it.next();
+    let folded = it.fold(first.into_iter().collect::<Vec<_>>(), |mut acc, gap| {
+      acc.push(gap);
+      acc
+    });
+    assert_eq!(folded, expected);
+  }
+}
+
+/// Check that peeking at gaps interacts properly with other
+/// [`GapIter`] functionality.
+#[test]
+fn gap_iterator_peeking() {
+  let set = btreeset! {1usize, 3, 4, 8};
+
+  let mut it = set.gaps(0..=10);
+  assert_eq!(it.peek_gap(), Some(&Gap::new(Included(0), Excluded(1))));
+  assert_eq!(it.peek_gap(), Some(&Gap::new(Included(0), Excluded(1))));
+  assert_eq!(it.size_hint().0, 1);
+  assert_eq!(it.remainder(), Some((Included(0), Included(10))));
+  assert_eq!(it.next(), Some(Gap::new(Included(0), Excluded(1))));
+
+  assert_eq!(it.peek_gap(), Some(&Gap::new(Excluded(1), Excluded(3))));
+  // Advancing to the start of the gap has no effect.
+  it.advance_to(2);
+  assert_eq!(it.peek_gap(), Some(&Gap::new(Excluded(1), Excluded(3))));
+  it.advance_to(6);
+  assert_eq!(it.peek_gap(), Some(&Gap::new(Included(6), Excluded(8))));
+
+  let gaps = it.fold(Vec::new(), |mut acc, gap| {
+    acc.push(gap);
+    acc
+  });
+  assert_eq!(
+    gaps,
+    vec![
+      Gap::new(Included(6), Excluded(8)),
+      Gap::new(Excluded(8), Included(10))
+    ]
+  );
+
+  let mut it = set.gaps(0..=10);
+  let _ = it.by_ref().take(2).count();
+  assert_eq!(it.peek_gap(), Some(&Gap::new(Excluded(4), Excluded(8))));
+  assert_eq!(it.peek_gap(), Some(&Gap::new(Excluded(4), Excluded(8))));
+  assert_eq!(it.next(), Some(Gap::new(Excluded(4), Excluded(8))));
+  assert_eq!(it.peek_gap(), Some(&Gap::new(Excluded(8), Included(10))));
+  assert_eq!(it.remainder(), Some((Excluded(8), Included(10))));
+  it.advance_to(11);
+  assert_eq!(it.peek_gap(), None);
+  assert_eq!(it.next(), None);
+}
+
+/// Check that we can split gaps into chunks of a maximum length.
+#[test]
+fn gap_chunking() {
+  let set = btreeset! {3usize, 4, 10};
+
+  let chunks = set.gaps(0..=12).chunks(1).collect::<Vec<_>>();
+  assert_eq!(
+    chunks,
+    vec![
+      Gap::new(Included(0), Included(0)),
+      Gap::new(Excluded(0), Included(1)),
+      Gap::new(Excluded(1), Excluded(3)),
+      Gap::new(Excluded(4), Included(5)),
+      Gap::new(Excluded(5), Included(6)),
+      Gap::new(Excluded(6), Included(7)),
+      Gap::new(Excluded(7), Included(8)),
+      Gap::new(Excluded(8), Excluded(10)),
+      Gap::new(Excluded(10), Included(11)),
+      Gap::new(Excluded(11), Included(12)),
+    ]
+  );
+
+  let chunks = set.gaps(0..=12).chunks(5).collect::<Vec<_>>();
+  assert_eq!(
+    chunks,
+    vec![
+      Gap::new(Included(0), Excluded(3)),
+      Gap::new(Excluded(4), Excluded(10)),
+      Gap::new(Excluded(10), Included(12)),
+    ]
+  );
+
+  let chunks = set.gaps(..).chunks(4).take(4).collect::<Vec<_>>();
+  assert_eq!(
+    chunks,
+    vec![
+      Gap::new(Unbounded, Excluded(3)),
+      Gap::new(Excluded(4), Included(8)),
+      Gap::new(Excluded(8), Excluded(10)),
+      Gap::new(Excluded(10), Included(14)),
+    ]
+  );
+
+  for max_len in 1..=13 {
+    let linear = set.gaps(0..=12).chunks(max_len).collect::<Vec<_>>();
+    let striding = set
+      .gaps(0..=12)
+      .chunks(max_len)
+      .striding()
+      .collect::<Vec<_>>();
+    assert_eq!(linear, striding);
+  }
+
+  let set = btreeset! {0u8};
+  let chunks = set.gaps(..).chunks(200).striding().collect::<Vec<_>>();
+  assert_eq!(
+    chunks,
+    vec![
+      Gap::new(Unbounded, Excluded(0)),
+      Gap::new(Excluded(0), Included(200)),
+      Gap::new(Excluded(200), Unbounded),
+    ]
+  );
+  assert_eq!(set.gaps(..).chunks(200).collect::<Vec<_>>(), chunks);
+}
+
+/// Check that gaps are snapped to multiples of a step properly.
+#[test]
+fn stepped_gap_iteration() {
+  let set = btreeset! {10i32, 20, 50};
+  assert_eq!(
+    set.gaps_with_step(-20..=60, 10).collect::<Vec<_>>(),
+    vec![
+      Gap::new(Included(-20), Included(0)),
+      Gap::new(Included(30), Included(40)),
+      Gap::new(Included(60), Included(60)),
+    ]
+  );
+  assert_eq!(
+    set.gaps_with_step(.., 10).collect::<Vec<_>>(),
+    vec![
+      Gap::new(Unbounded, Included(0)),
+      Gap::new(Included(30), Included(40)),
+      Gap::new(Included(60), Unbounded),
+    ]
+  );
+  assert_eq!(set.gaps_with_step(11..19, 10).collect::<Vec<_>>(), vec![]);
+
+  // Elements not being multiples of the step should not cause
+  // additional gaps to be reported.
+  let set = btreeset! {10u8, 25, 37};
+  assert_eq!(
+    set
+      .iter()
+      .copied()
+      .gaps_with_step(0..=60, 10)
+      .collect::<Vec<_>>(),
+    vec![
+      Gap::new(Included(0), Included(0)),
+      Gap::new(Included(20), Included(60))
+    ]
+  );
+}
+
+/// Check that gaps are aligned as expected.
+#[test]
+fn aligned_gap_iteration() {
+  let set = btreeset! {2u8, 9, 15};
+  assert_eq!(
+    set.gaps(..).align(4).collect::<Vec<_>>(),
+    vec![
+      Gap::new(Unbounded, Excluded(0)),
+      Gap::new(Included(4), Excluded(8)),
+      Gap::new(Included(16), Unbounded),
+    ]
+  );
+  assert_eq!(
+    set.gaps(0..=14).align(2).collect::<Vec<_>>(),
+    vec![
+      Gap::new(Included(0), Excluded(2)),
+      Gap::new(Included(4), Excluded(8)),
+      Gap::new(Included(10), Excluded(14)),
+    ]
+  );
+  assert_eq!(
+    set.gaps(16..=255).align(16).collect::<Vec<_>>(),
+    vec![Gap::new(Included(16), Excluded(240))]
+  );
+  assert_eq!(set.gaps(3..9).align(8).collect::<Vec<_>>(), vec![]);
+}
+
+/// Check that gaps separated by few values get coalesced.
+#[test]
+fn coalesced_gap_iteration() {
+  let set = btreeset! {3usize, 10, 11, 20, 21, 22};
+  assert_eq!(
+    set.gaps(..).coalesce(0).collect::<Vec<_>>(),
+    set.gaps(..).collect::<Vec<_>>()
+  );
+  assert_eq!(
+    set.gaps(..).coalesce(1).collect::<Vec<_>>(),
+    set.gaps(..).collect::<Vec<_>>()
+  );
+  assert_eq!(
+    set.gaps(..).coalesce(2).collect::<Vec<_>>(),
+    vec![
+      Gap::new(Unbounded, Excluded(10)),
+      Gap::new(Excluded(11), Excluded(20)),
+      Gap::new(Excluded(22), Unbounded),
+    ]
+  );
+  assert_eq!(
+    set.gaps(..).coalesce(4).collect::<Vec<_>>(),
+    vec![Gap::new(Unbounded, Unbounded)]
+  );
+  assert_eq!(
+    set.gaps(4..=21).coalesce(4).collect::<Vec<_>>(),
+    vec![Gap::new(Included(4), Excluded(20))]
+  );
+}
+
+/// Check that we can iterate over the individual missing values.
+#[test]
+fn missing_value_iteration() {
+  let set = btreeset! {1u8, 3, 4, 254};
+  assert_eq!(set.missing(0..=6).collect::<Vec<_>>(), vec![0, 2, 5, 6]);
+  assert_eq!(
+    set.missing(250..=255).collect::<Vec<_>>(),
+    vec![250, 251, 252, 253, 255]
+  );
+  assert_eq!(set.missing(..3).collect::<Vec<_>>(), vec![2]);
+  assert_eq!(set.missing(3..=4).collect::<Vec<_>>(), vec![]);
+  assert_eq!(
+    set.iter().copied().missing(2..).take(3).collect::<Vec<_>>(),
+    vec![2, 5, 6]
+  );
+}
+
+/// Check that we can iterate over runs of contiguous values.
+#[test]
+fn run_iteration() {
+  let set = btreeset! {1usize, 3, 4, 5, 8, 9};
+  assert_eq!(
+    set.runs(..).collect::<Vec<_>>(),
+    vec![
+      Gap::new(Included(1), Included(1)),
+      Gap::new(Included(3), Included(5)),
+      Gap::new(Included(8), Included(9)),
+    ]
+  );
+  assert_eq!(
+    set.runs(4..9).collect::<Vec<_>>(),
+    vec![
+      Gap::new(Included(4), Included(5)),
+      Gap::new(Included(8), Included(8))
+    ]
+  );
+  assert_eq!(
+    set.runs((Excluded(3), Included(4))).collect::<Vec<_>>(),
+    vec![Gap::new(Included(4), Included(4))]
+  );
+  assert_eq!(set.runs(6..8).collect::<Vec<_>>(), vec![]);
+
+  let values = [2usize, 2, 3, 5, 5, 11];
+  assert_eq!(
+    values.iter().copied().runs(0..=10).collect::<Vec<_>>(),
+    vec![
+      Gap::new(Included(2), Included(3)),
+      Gap::new(Included(5), Included(5))
+    ]
+  );
+}
+
+/// Check that segments are reported in alternating order and agree
+/// with the gaps and runs reported separately.
+#[test]
+fn segment_iteration() {
+  let set = btreeset! {1usize, 3, 4, 5, 8, 9};
+  let ranges = [
+    bounds(&(..)),
+    bounds(&(0..=12)),
+    bounds(&(1..=9)),
+    bounds(&(4..9)),
+    bounds(&(6..8)),
+    (Excluded(3), Included(4)),
+  ];
+
+  for range in ranges.iter() {
+    let mut gaps = Vec::new();
+    let mut runs = Vec::new();
+    for segment in set.segments(*range) {
+      match segment {
+        Segment::Missing(gap) => gaps.push(gap),
+        Segment::Present(run) => runs.push(run),
+      }
+    }
+    assert_eq!(gaps, set.gaps(*range).collect::<Vec<_>>());
+    assert_eq!(runs, set.runs(*range).collect::<Vec<_>>());
+  }
+
+  assert_eq!(
+    set.segments(2..=4).collect::<Vec<_>>(),
+    vec![
+      Segment::Missing(Gap::new(Included(2), Excluded(3))),
+      Segment::Present(Gap::new(Included(3), Included(4))),
+    ]
+  );
+}
+
+
+/// Check that iterators derived from a `RangeGappable` implementation
+/// handing out an already exhausted `GapIter` are empty.
+#[test]
+fn exhausted_gap_iter_derivation() {
+  struct Exhausted;
+
+  impl<'s> gaps::RangeGappable<'s, u8> for Exhausted {
+    type Iter = std::vec::IntoIter<u8>;
+
+    fn gaps<R>(&'s self, _range: R) -> gaps::GapIter<Self::Iter, u8>
+    where
+      R: std::ops::RangeBounds<u8>,
+    {
+      let mut gaps = vec![].into_iter().gaps(0..4);
+      assert_eq!(gaps.next(), Some(Gap::new(Included(0), Excluded(4))));
+      assert_eq!(gaps.next(), None);
+      gaps
+    }
+  }
+
+  assert_eq!(Exhausted.gaps(..).count(), 0);
+  assert_eq!(Exhausted.runs(..).count(), 0);
+  assert_eq!(Exhausted.segments(..).count(), 0);
+}
+
+/// Check that we can iterate over the gaps between intervals.
+#[test]
+fn interval_gap_iteration() {
+  let intervals = vec![
+    (Included(1u32), Excluded(3)),
+    (Included(2), Included(4)),
+    (Excluded(4), Excluded(6)),
+    (Included(8), Excluded(9)),
+    (Included(8), Included(8)),
+    (Excluded(12), Unbounded),
+  ];
+
+  assert_eq!(
+    intervals
+      .iter()
+      .copied()
+      .interval_gaps(..)
+      .collect::<Vec<_>>(),
+    vec![
+      Gap::new(Unbounded, Excluded(1)),
+      Gap::new(Included(6), Excluded(8)),
+      Gap::new(Included(9), Included(12)),
+    ]
+  );
+  assert_eq!(
+    intervals
+      .iter()
+      .copied()
+      .interval_gaps(2..=10)
+      .collect::<Vec<_>>(),
+    vec![
+      Gap::new(Included(6), Excluded(8)),
+      Gap::new(Included(9), Included(10))
+    ]
+  );
+  assert_eq!(
+    intervals
+      .iter()
+      .copied()
+      .interval_gaps(2..6)
+      .collect::<Vec<_>>(),
+    vec![]
+  );
+  assert_eq!(
+    intervals
+      .into_iter()
+      .interval_gaps(20..30)
+      .collect::<Vec<_>>(),
+    vec![]
+  );
+
+  let intervals = [(Unbounded, Excluded(5u32)), (Excluded(5), Unbounded)];
+  assert_eq!(
+    intervals
+      .iter()
+      .copied()
+      .interval_gaps(..)
+      .collect::<Vec<_>>(),
+    vec![Gap::new(Included(5), Included(5))]
+  );
+
+  let intervals = [1u32..3, 3..5, 7..9];
+  assert_eq!(
+    intervals
+      .iter()
+      .cloned()
+      .interval_gaps(0..)
+      .collect::<Vec<_>>(),
+    vec![
+      Gap::new(Included(0), Excluded(1)),
+      Gap::new(Included(5), Excluded(7)),
+      Gap::new(Included(9), Unbounded),
+    ]
+  );
+}
+
+/// Check that we can iterate over the gaps between keys of structured
+/// items.
+#[test]
+fn keyed_gap_iteration() {
+  #[derive(Debug)]
+  struct Packet {
+    seq: u16,
+    payload: &'static str,
+  }
+
+  let packets = [
+    Packet {
+      seq: 3,
+      payload: "foo",
+    },
+    Packet {
+      seq: 4,
+      payload: "bar",
+    },
+    Packet {
+      seq: 7,
+      payload: "baz",
+    },
+  ];
+  assert!(packets.iter().all(|packet| !packet.payload.is_empty()));
+
+  assert_eq!(
+    packets
+      .iter()
+      .gaps_by_key(0..10, |packet| packet.seq)
+      .collect::<Vec<_>>(),
+    vec![
+      Gap::new(Included(0), Excluded(3)),
+      Gap::new(Excluded(4), Excluded(7)),
+      Gap::new(Excluded(7), Excluded(10)),
+    ]
+  );
+}
+
+/// Check that we can find the first gap in a range.
+#[test]
+fn first_gap_retrieval() {
+  let map = btreemap! {1usize => "foo", 2 => "bar", 5 => "baz"};
+  assert_eq!(map.first_gap(..), Some(Gap::new(Unbounded, Excluded(1))));
+  assert_eq!(map.first_gap(1..), Some(Gap::new(Excluded(2), Excluded(5))));
+  assert_eq!(map.first_gap(1..=2), None);
+  assert_eq!(
+    map.keys().copied().first_gap(3..),
+    Some(Gap::new(Included(3), Excluded(5)))
+  );
+  assert_eq!(map.keys().copied().first_gap(5..6), None);
+}
+
+/// Check that we can find the smallest missing value in a range.
+#[test]
+fn next_free_value() {
+  let set = btreeset! {0u32, 1, 2, 4, 5};
+  assert_eq!(set.next_free(0..), Some(3));
+  assert_eq!(set.next_free(4..), Some(6));
+  assert_eq!(set.next_free((Excluded(4), Unbounded)), Some(6));
+  assert_eq!(set.next_free(0..3), None);
+  assert_eq!(set.next_free(..), None);
+  assert_eq!(set.iter().copied().next_free(1..), Some(3));
+  assert_eq!(set.iter().copied().next_free(0..=2), None);
+}
+
+/// Check that we can find the largest gap in a range.
+#[test]
+fn largest_gap_retrieval() {
+  let set = btreeset! {2i32, 3, 7, 9, 13};
+  assert_eq!(
+    set.largest_gap(0..=15),
+    Some(Gap::new(Excluded(3), Excluded(7)))
+  );
+  assert_eq!(
+    set.largest_gap(4..=15),
+    Some(Gap::new(Included(4), Excluded(7)))
+  );
+  assert_eq!(set.largest_gap(..), Some(Gap::new(Unbounded, Excluded(2))));
+  assert_eq!(set.largest_gap(2..=3), None);
+  assert_eq!(
+    set.iter().copied().largest_gap(7..),
+    Some(Gap::new(Excluded(13), Unbounded))
+  );
+}
+
+/// Check that we can find the k largest gaps in a range.
+#[test]
+fn k_largest_gap_retrieval() {
+  let set = btreeset! {2u64, 3, 7, 9, 13, 14, 20};
+  assert_eq!(set.k_largest_gaps(0..=20, 0), vec![]);
+  assert_eq!(
+    set.k_largest_gaps(0..=20, 1),
+    vec![Gap::new(Excluded(14), Excluded(20))]
+  );
+  assert_eq!(
+    set.k_largest_gaps(0..=20, 3),
+    vec![
+      Gap::new(Excluded(14), Excluded(20)),
+      Gap::new(Excluded(3), Excluded(7)),
+      Gap::new(Excluded(9), Excluded(13)),
+    ]
+  );
+  assert_eq!(
+    set.k_largest_gaps(0..=20, 10),
+    vec![
+      Gap::new(Excluded(14), Excluded(20)),
+      Gap::new(Excluded(3), Excluded(7)),
+      Gap::new(Excluded(9), Excluded(13)),
+      Gap::new(Included(0), Excluded(2)),
+      Gap::new(Excluded(7), Excluded(9)),
+    ]
+  );
+  assert_eq!(
+    set.iter().copied().k_largest_gaps(10.., 2),
+    vec![
+      Gap::new(Excluded(20), Unbounded),
+      Gap::new(Excluded(14), Excluded(20))
+    ]
+  );
+}
+
+/// Check that we can count the number of missing values in a range.
+#[test]
+fn missing_value_counting() {
+  let set = btreeset! {2u8, 3, 7, 9};
+  assert_eq!(set.missing_count(0..=10), Some(7));
+  assert_eq!(set.missing_count(2..=3), Some(0));
+  assert_eq!(set.missing_count((Excluded(3), Excluded(7))), Some(3));
+  assert_eq!(set.missing_count(0..=255), Some(252));
+  assert_eq!(set.missing_count(..5), None);
+  assert_eq!(set.iter().copied().missing_count(4..), None);
+  assert_eq!(set.iter().copied().missing_count(4..9), Some(4));
+}
+
+/// Check that we can check for the presence of gaps.
+#[test]
+fn gap_presence_check() {
+  let set = btreeset! {1usize, 2, 3, 5};
+  assert!(set.has_gaps(..));
+  assert!(set.has_gaps(0..=3));
+  assert!(!set.has_gaps(1..=3));
+  assert!(!set.has_gaps((Excluded(1), Excluded(4))));
+  assert!(set.has_gaps(3..=5));
+  assert!(!set.iter().copied().has_gaps(5..=5));
+  assert!(set.iter().copied().has_gaps(5..));
+
+  let set = (1..=100).collect::<BTreeSet<usize>>();
+  assert!(!set.has_gaps_by_count(1..=100));
+  assert!(!set.has_gaps_by_count(1..101));
+  assert!(!set.has_gaps_by_count(10..=20));
+  assert!(set.has_gaps_by_count(0..=100));
+  assert!(set.has_gaps_by_count(1..=101));
+  assert!(!set.has_gaps_by_count(5..5));
+
+  let map = set.iter().map(|x| (*x, ())).collect::<BTreeMap<_, _>>();
+  assert!(!map.has_gaps_by_count(1..=100));
+  assert!(!map.has_gaps_by_count(50..=100));
+  assert!(map.has_gaps_by_count(50..));
+  assert!(map.has_gaps_by_count(0..=100));
+
+  let set = BTreeSet::<usize>::new();
+  assert!(set.has_gaps_by_count(0..=1));
+  assert!(!set.has_gaps_by_count(1..1));
+
+  let nz = |x| NonZeroU32::new(x).unwrap();
+  let set = (1..=3).map(nz).collect::<BTreeSet<_>>();
+  assert!(!set.has_gaps(nz(1)..=nz(3)));
+  assert!(set.has_gaps(nz(1)..=nz(4)));
+}
+
+/// Check that gap statistics are gathered correctly.
+#[test]
+fn gap_statistics() {
+  let set = btreeset! {2u32, 3, 7, 9};
+
+  let stats = set.stats(2..=3);
+  assert_eq!(stats.count, 0);
+  assert_eq!(stats.missing, Some(0));
+  assert_eq!(stats.min_len, None);
+  assert_eq!(stats.max_len, None);
+  assert_eq!(stats.mean_len, None);
+  assert_eq!(stats.largest, None);
+
+  let stats = set.stats(1..=12);
+  assert_eq!(stats.count, 4);
+  assert_eq!(stats.missing, Some(8));
+  assert_eq!(stats.min_len, Some(1));
+  assert_eq!(stats.max_len, Some(3));
+  assert_eq!(stats.mean_len, Some(2.0));
+  assert_eq!(stats.largest, Some(Gap::new(Excluded(3), Excluded(7))));
+
+  let stats = set.stats(3..);
+  assert_eq!(stats.count, 3);
+  assert_eq!(stats.missing, None);
+  assert_eq!(stats.min_len, Some(1));
+  assert_eq!(stats.max_len, None);
+  assert_eq!(stats.mean_len, None);
+  assert_eq!(stats.largest, Some(Gap::new(Excluded(9), Unbounded)));
+}
+
+/// Check that we can calculate the coverage of a range.
+#[test]
+fn range_coverage() {
+  let set = btreeset! {1i16, 2, 3, 8};
+  assert_eq!(set.coverage(1..=3), Some(1.0));
+  assert_eq!(set.coverage(0..4), Some(0.75));
+  assert_eq!(set.coverage(0..=9), Some(0.4));
+  assert_eq!(set.coverage(4..8), Some(0.0));
+  assert_eq!(set.coverage(5..5), Some(1.0));
+  assert_eq!(set.coverage(..5), None);
+  assert_eq!(set.iter().copied().coverage(-2..2), Some(0.25));
+}
+
+/// Check that we can search for gaps of a certain length.
+#[test]
+fn gap_search() {
+  let set = btreeset! {3usize, 5, 10, 13};
+  assert_eq!(
+    set.find_gap(0..=20, 3, Fit::First),
+    Some(Gap::new(Included(0), Excluded(3)))
+  );
+  assert_eq!(
+    set.find_gap(0..=20, 3, Fit::Best),
+    Some(Gap::new(Included(0), Excluded(3)))
+  );
+  assert_eq!(
+    set.find_gap(0..=20, 4, Fit::Best),
+    Some(Gap::new(Excluded(5), Excluded(10)))
+  );
+  assert_eq!(
+    set.find_gap(0..=20, 4, Fit::Worst),
+    Some(Gap::new(Excluded(13), Included(17)))
+  );
+  assert_eq!(set.find_gap(0..=20, 8, Fit::First), None);
+  assert_eq!(
+    set.find_gap(1.., 8, Fit::First),
+    Some(Gap::new(Excluded(13), Included(21)))
+  );
+  assert_eq!(
+    set.iter().copied().find_gap(.., 1, Fit::Worst),
+    Some(Gap::new(Unbounded, Excluded(3)))
+  );
+
+  // Searching for a huge gap should not require visiting each of its
+  // values.
+  let set = btreeset! {0u64};
+  assert_eq!(
+    set.find_gap(0.., 1 << 62, Fit::First),
+    Some(Gap::new(Excluded(0), Included(1 << 62)))
+  );
+}
+
+/// Check that we can intersect the gaps of two sources.
+#[test]
+fn gap_intersection() {
+  let set1 = btreeset! {3, 5, 10};
+  let set2 = btreeset! {4, 5, 12};
+  let gaps = set1
+    .gaps(0..=15)
+    .intersect(set2.gaps(0..=15))
+    .collect::<Vec<_>>();
+  let expected = vec![
+    Gap::new(Included(0), Excluded(3)),
+    Gap::new(Excluded(5), Excluded(10)),
+    Gap::new(Excluded(10), Excluded(12)),
+    Gap::new(Excluded(12), Included(15)),
+  ];
+  assert_eq!(gaps, expected);
+
+  // Intersecting with ranges using differing bound kinds works as
+  // well.
+  let other = vec![
+    Gap::new(Unbounded, Included(6)),
+    Gap::new(Included(11), Unbounded),
+  ];
+  let gaps = set1.gaps(0..=15).intersect(other).collect::<Vec<_>>();
+  let expected = vec![
+    Gap::new(Included(0), Excluded(3)),
+    Gap::new(Excluded(3), Excluded(5)),
+    Gap::new(Excluded(5), Included(6)),
+    Gap::new(Excluded(10), Included(15)),
+  ];
+  assert_eq!(gaps, expected);
+
+  let empty = Vec::new();
+  let gaps = set1.gaps(..).intersect(empty).collect::<Vec<_>>();
+  assert_eq!(gaps, Vec::new());
+
+  let gaps = set1
+    .gaps(..)
+    .intersect(vec![Gap::new(Unbounded, Unbounded)])
+    .collect::<Vec<_>>();
+  assert_eq!(gaps, set1.gaps(..).collect::<Vec<_>>());
+}
+
+/// Check that we can unite the gaps of two sources.
+#[test]
+fn gap_union() {
+  let set1 = btreeset! {3, 5, 10};
+  let set2 = btreeset! {4, 5, 12};
+  let gaps = set1
+    .gaps(0..=15)
+    .union(set2.gaps(0..=15))
+    .collect::<Vec<_>>();
+  let expected = vec![
+    Gap::new(Included(0), Excluded(5)),
+    Gap::new(Excluded(5), Included(15)),
+  ];
+  assert_eq!(gaps, expected);
+
+  // Adjacent ranges get merged, irrespective of their bound kinds.
+  let other = vec![
+    Gap::new(Included(16), Excluded(18)),
+    Gap::new(Included(18), Included(20)),
+  ];
+  let gaps = set1.gaps(11..=15).union(other).collect::<Vec<_>>();
+  assert_eq!(gaps, vec![Gap::new(Included(11), Included(20))]);
+
+  let gaps = set1.gaps(..).union(Vec::new()).collect::<Vec<_>>();
+  assert_eq!(gaps, set1.gaps(..).collect::<Vec<_>>());
+
+  let gaps = set1
+    .gaps(..)
+    .union(vec![Gap::new(Excluded(2), Unbounded)])
+    .collect::<Vec<_>>();
+  assert_eq!(gaps, vec![Gap::new(Unbounded, Unbounded)]);
+}
+
+/// Check that we can determine the difference between the gaps of two
+/// sources.
+#[test]
+fn gap_difference() {
+  let set1 = btreeset! {3, 5, 10};
+  let set2 = btreeset! {4, 5, 12};
+  let gaps = set1
+    .gaps(0..=15)
+    .difference(set2.gaps(0..=15))
+    .collect::<Vec<_>>();
+  let expected = vec![
+    Gap::new(Excluded(3), Excluded(5)),
+    Gap::new(Included(12), Included(12)),
+  ];
+  assert_eq!(gaps, expected);
+
+  let gaps = set2
+    .gaps(0..=15)
+    .difference(set1.gaps(0..=15))
+    .collect::<Vec<_>>();
+  let expected = vec![
+    Gap::new(Included(3), Excluded(4)),
+    Gap::new(Included(10), Included(10)),
+  ];
+  assert_eq!(gaps, expected);
+
+  let gaps = set1.gaps(..).difference(Vec::new()).collect::<Vec<_>>();
+  assert_eq!(gaps, set1.gaps(..).collect::<Vec<_>>());
+
+  let gaps = set1
+    .gaps(..)
+    .difference(vec![Gap::new(Unbounded, Unbounded)])
+    .collect::<Vec<_>>();
+  assert_eq!(gaps, Vec::new());
+}
+
+/// Check that we can determine the symmetric difference between the
+/// gaps of two sources.
+#[test]
+fn gap_symmetric_difference() {
+  let set1 = btreeset! {3, 5, 10};
+  let set2 = btreeset! {4, 5, 12};
+  let gaps = set1
+    .gaps(0..=15)
+    .symmetric_difference(set2.gaps(0..=15))
+    .collect::<Vec<_>>();
+  let expected = vec![
+    Gap::new(Included(3), Excluded(5)),
+    Gap::new(Included(10), Included(10)),
+    Gap::new(Included(12), Included(12)),
+  ];
+  assert_eq!(gaps, expected);
+
+  let gaps = set1
+    .gaps(0..=15)
+    .symmetric_difference(set1.gaps(0..=15))
+    .collect::<Vec<_>>();
+  assert_eq!(gaps, Vec::new());
+
+  let gaps = set1
+    .gaps(..)
+    .symmetric_difference(vec![Gap::new(Unbounded, Unbounded)])
+    .collect::<Vec<_>>();
+  let expected = vec![
+    Gap::new(Included(3), Included(3)),
+    Gap::new(Included(5), Included(5)),
+    Gap::new(Included(10), Included(10)),
+  ];
+  assert_eq!(gaps, expected);
+}
+
+/// Check that we can collect gaps into a `GapList` and query it.
+#[test]
+fn gap_list_queries() {
+  let set = btreeset! {3, 5, 10};
+  let list = set.gaps(0..=15).collect::<GapList<_>>();
+  assert_eq!(list.len(), 4);
+  assert!(!list.is_empty());
+  assert_eq!(list.as_slice(), set.gaps(0..=15).collect::<Vec<_>>());
+
+  assert!(list.contains(0));
+  assert!(!list.contains(3));
+  assert!(list.contains(4));
+  assert!(!list.contains(10));
+  assert!(list.contains(15));
+  assert!(!list.contains(16));
+  assert_eq!(list.get(7), Some(&Gap::new(Excluded(5), Excluded(10))));
+  assert_eq!(list.get(5), None);
+
+  assert!(list.overlaps(4..6));
+  assert!(list.overlaps(..));
+  assert!(list.overlaps(15..));
+  assert!(!list.overlaps(5..=5));
+  assert!(!list.overlaps(16..));
+  assert!(!list.overlaps((Excluded(4), Excluded(5))));
+  assert_eq!(list.total_missing(), Some(13));
+
+  // Gaps are sorted and merged when collected.
+  let list = vec![
+    Gap::new(Included(10), Excluded(12)),
+    Gap::new(Included(0), Included(2)),
+    Gap::new(Included(12), Included(14)),
+    Gap::new(Included(1), Included(4)),
+  ]
+  .into_iter()
+  .collect::<GapList<_>>();
+  let expected = vec![
+    Gap::new(Included(0), Included(4)),
+    Gap::new(Included(10), Included(14)),
+  ];
+  assert_eq!(list.into_iter().collect::<Vec<_>>(), expected);
+
+  let list = set.gaps(..).collect::<GapList<_>>();
+  assert!(list.contains(u32::MIN));
+  assert!(list.contains(u32::MAX));
+  assert_eq!(list.total_missing(), None);
+
+  let list = GapList::<u8>::new();
+  assert!(list.is_empty());
+  assert!(!list.contains(1));
+  assert!(!list.overlaps(..));
+  assert_eq!(list.total_missing(), Some(0));
+}
+
+/// Check that `GapList` objects covering the same values compare and
+/// hash equal, irrespective of the bounds of their gaps.
+#[test]
+fn gap_list_equality() {
+  let set = btreeset! {3u8, 5};
+  let list1 = set.gaps(0..10).collect::<GapList<_>>();
+  let list2 = set.gaps(0..=9).collect::<GapList<_>>();
+  assert_ne!(list1.as_slice(), list2.as_slice());
+  assert_eq!(list1, list2);
+
+  let list3 = vec![
+    Gap::new(Included(0), Excluded(3)),
+    Gap::new(Excluded(3), Included(4)),
+    Gap::new(Included(6), Excluded(10)),
+  ]
+  .into_iter()
+  .collect::<GapList<_>>();
+  assert_eq!(list1, list3);
+
+  let lists = vec![list1.clone(), list2, list3]
+    .into_iter()
+    .collect::<HashSet<_>>();
+  assert_eq!(lists.len(), 1);
+
+  assert_ne!(list1, set.gaps(0..=10).collect::<GapList<_>>());
+  assert_ne!(list1, set.gaps(1..10).collect::<GapList<_>>());
+
+  let list1 = set.gaps(6..).collect::<GapList<_>>();
+  let list2 = set.gaps(6..=u8::MAX).collect::<GapList<_>>();
+  assert_eq!(list1, list2);
+  assert_ne!(
+    set.gaps(..3).collect::<GapList<_>>(),
+    set.gaps(0..3).collect::<GapList<_>>()
+  );
+}
+
+/// Check that we can combine `GapList` objects using set operations.
+#[test]
+fn gap_list_set_operations() {
+  let set1 = btreeset! {3, 5, 10};
+  let set2 = btreeset! {4, 5, 12};
+  let list1 = set1.gaps(0..=15).collect::<GapList<_>>();
+  let list2 = set2.gaps(0..=15).collect::<GapList<_>>();
+
+  let expected = set1
+    .gaps(0..=15)
+    .union(set2.gaps(0..=15))
+    .collect::<Vec<_>>();
+  assert_eq!((&list1 | &list2).as_slice(), expected);
+  assert_eq!(list1.union(&list2), &list1 | &list2);
+
+  let expected = set1
+    .gaps(0..=15)
+    .intersect(set2.gaps(0..=15))
+    .collect::<Vec<_>>();
+  assert_eq!((&list1 & &list2).as_slice(), expected);
+  assert_eq!(list1.intersection(&list2), &list1 & &list2);
+
+  let expected = set1
+    .gaps(0..=15)
+    .difference(set2.gaps(0..=15))
+    .collect::<Vec<_>>();
+  assert_eq!((&list1 - &list2).as_slice(), expected);
+  assert_eq!(list1.difference(&list2), &list1 - &list2);
+
+  let expected = set1
+    .gaps(0..=15)
+    .symmetric_difference(set2.gaps(0..=15))
+    .collect::<Vec<_>>();
+  assert_eq!((&list1 ^ &list2).as_slice(), expected);
+  assert_eq!(list1.symmetric_difference(&list2), &list1 ^ &list2);
+
+  let empty = GapList::new();
+  assert_eq!(&list1 | &empty, list1);
+  assert_eq!(&list1 & &empty, empty);
+  assert_eq!(&list1 - &empty, list1);
+  assert_eq!(&list1 ^ &list1, empty);
+}
+
+/// Check that we can determine the changes between two gap snapshots.
+#[test]
+fn gap_list_diffing() {
+  let earlier = btreeset! {3, 10, 20, 25};
+  let later = btreeset! {3, 4, 5, 9, 10, 15, 25, 28};
+  let earlier = earlier.gaps(0..30).collect::<GapList<_>>();
+  let later = later.gaps(0..30).collect::<GapList<_>>();
+
+  let delta = GapList::diff(&earlier, &later);
+  assert!(!delta.is_empty());
+  assert_eq!(delta.closed, vec![]);
+  assert_eq!(delta.opened, vec![]);
+  assert_eq!(
+    delta.shrank,
+    vec![
+      (
+        Gap::new(Excluded(3), Excluded(10)),
+        Gap::new(Excluded(5), Excluded(9))
+      ),
+      (
+        Gap::new(Excluded(10), Excluded(20)),
+        Gap::new(Excluded(10), Excluded(15))
+      ),
+      (
+        Gap::new(Excluded(10), Excluded(20)),
+        Gap::new(Excluded(15), Excluded(25))
+      ),
+      (
+        Gap::new(Excluded(25), Excluded(30)),
+        Gap::new(Excluded(25), Excluded(28))
+      ),
+      (
+        Gap::new(Excluded(25), Excluded(30)),
+        Gap::new(Excluded(28), Excluded(30))
+      ),
+    ]
+  );
+  assert_eq!(
+    delta.grew,
+    vec![
+      (
+        Gap::new(Excluded(10), Excluded(20)),
+        Gap::new(Excluded(15), Excluded(25))
+      ),
+      (
+        Gap::new(Excluded(20), Excluded(25)),
+        Gap::new(Excluded(15), Excluded(25))
+      ),
+    ]
+  );
+
+  let earlier = btreeset! {5}.gaps(0..10).collect::<GapList<_>>();
+  let later = btreeset! {0, 1, 2, 3, 4, 5, 8}
+    .gaps(0..10)
+    .collect::<GapList<_>>();
+  let delta = GapList::diff(&earlier, &later);
+  assert_eq!(delta.closed, vec![Gap::new(Included(0), Excluded(5))]);
+  assert_eq!(delta.opened, vec![]);
+  assert_eq!(
+    delta.shrank,
+    vec![
+      (
+        Gap::new(Excluded(5), Excluded(10)),
+        Gap::new(Excluded(5), Excluded(8))
+      ),
+      (
+        Gap::new(Excluded(5), Excluded(10)),
+        Gap::new(Excluded(8), Excluded(10))
+      ),
+    ]
+  );
+  assert_eq!(delta.grew, vec![]);
+
+  let delta = GapList::diff(&later, &earlier);
+  assert_eq!(delta.closed, vec![]);
+  assert_eq!(delta.opened, vec![Gap::new(Included(0), Excluded(5))]);
+  assert_eq!(delta.shrank, vec![]);
+  assert_eq!(delta.grew.len(), 2);
+
+  let delta = GapList::diff(&earlier, &earlier);
+  assert!(delta.is_empty());
+}
+
+/// Check that we can invert gaps into the ranges covered by values.
+#[test]
+fn gap_complement() {
+  let set = btreeset! {3, 4, 5, 10};
+  let present = complement(set.gaps(0..=15), 0..=15).collect::<Vec<_>>();
+  let expected = vec![
+    Gap::new(Included(3), Included(5)),
+    Gap::new(Included(10), Included(10)),
+  ];
+  assert_eq!(present, expected);
+
+  let present = complement(set.gaps(0..=15), ..).collect::<Vec<_>>();
+  let expected = vec![
+    Gap::new(Unbounded, Excluded(0)),
+    Gap::new(Included(3), Included(5)),
+    Gap::new(Included(10), Included(10)),
+    Gap::new(Excluded(15), Unbounded),
+  ];
+  assert_eq!(present, expected);
+
+  let present = complement(Vec::new(), 1..3).collect::<Vec<_>>();
+  assert_eq!(present, vec![Gap::new(Included(1), Excluded(3))]);
+
+  let list = set.gaps(..).collect::<GapList<_>>();
+  assert_eq!(list.complement(..).complement(..), list);
+  let expected = vec![
+    Gap::new(Included(4), Included(5)),
+    Gap::new(Included(10), Included(10)),
+  ];
+  assert_eq!(list.complement(4..=10).as_slice(), expected);
+}
+
+/// Check that we can restrict gaps to a range.
+#[test]
+fn gap_clipping() {
+  let set = btreeset! {3, 8, 12};
+  let gaps = set.gaps(..).clip(..).collect::<Vec<_>>();
+  assert_eq!(gaps, set.gaps(..).collect::<Vec<_>>());
+
+  let gaps = set.gaps(..).clip(2..=8).collect::<Vec<_>>();
+  let expected = vec![
+    Gap::new(Included(2), Excluded(3)),
+    Gap::new(Excluded(3), Excluded(8)),
+  ];
+  assert_eq!(gaps, expected);
+
+  let gaps = set
+    .gaps(..)
+    .clip((Excluded(8), Unbounded))
+    .collect::<Vec<_>>();
+  let expected = vec![
+    Gap::new(Excluded(8), Excluded(12)),
+    Gap::new(Excluded(12), Unbounded),
+  ];
+  assert_eq!(gaps, expected);
+
+  let gaps = set.gaps(..).clip(8..=8).collect::<Vec<_>>();
+  assert_eq!(gaps, vec![]);
+
+  let gaps = set.gaps(0..5).clip(10..).collect::<Vec<_>>();
+  assert_eq!(gaps, vec![]);
+
+  // Clipping should stop as soon as a gap starts past the end of the
+  // range, instead of consuming all remaining gaps.
+  let set = (0..1000).step_by(2).collect::<BTreeSet<_>>();
+  let mut gaps = set.gaps(0..).clip(..=4);
+  assert_eq!(gaps.next(), Some(Gap::new(Excluded(0), Excluded(2))));
+  assert_eq!(gaps.next(), Some(Gap::new(Excluded(2), Excluded(4))));
+  assert_eq!(gaps.next(), None);
+  assert_eq!(gaps.size_hint(), (0, Some(0)));
+  assert_eq!(gaps.next(), None);
+}
+
+/// Check that gaps touching the maximum value of a type are handled
+/// correctly.
+#[test]
+fn gap_iteration_at_type_maximum() {
+  let set = btreeset! {250u8, 254};
+  let gaps = set.gaps(250..).collect::<Vec<_>>();
+  let expected = vec![
+    Gap::new(Excluded(250), Excluded(254)),
+    Gap::new(Excluded(254), Unbounded),
+  ];
+  assert_eq!(gaps, expected);
+
+  let set = btreeset! {250u8, 255};
+  let gaps = set.gaps(250..).collect::<Vec<_>>();
+  assert_eq!(gaps, vec![Gap::new(Excluded(250), Excluded(255))]);
+  let gaps = set.gaps((Excluded(255), Unbounded)).collect::<Vec<_>>();
+  assert_eq!(gaps, vec![]);
+
+  assert_eq!(
+    set.missing(250..).collect::<Vec<_>>(),
+    vec![251, 252, 253, 254]
+  );
+  let set = btreeset! {250u8};
+  assert_eq!(
+    set.missing(250..).collect::<Vec<_>>(),
+    vec![251, 252, 253, 254, 255]
+  );
+  assert_eq!(set.missing_count(250..=255), Some(5));
+  assert_eq!(
+    set.gaps(250..).chunks(3).collect::<Vec<_>>(),
+    vec![
+      Gap::new(Excluded(250), Included(253)),
+      Gap::new(Excluded(253), Unbounded)
+    ]
+  );
+  assert_eq!(
+    set.gaps(..=255).coalesce(2).collect::<Vec<_>>(),
+    vec![Gap::new(Unbounded, Included(255))]
+  );
+}
+
+/// Check that we can find gaps in collections of non-zero values.
+#[test]
+fn non_zero_gap_iteration() {
+  let id = |x| NonZeroU32::new(x).unwrap();
+  let set = btreeset! {id(1), id(2), id(5)};
+  let gaps = set.gaps(id(1)..=id(6)).collect::<Vec<_>>();
+  let expected = vec![
+    Gap::new(Excluded(id(2)), Excluded(id(5))),
+    Gap::new(Excluded(id(5)), Included(id(6))),
+  ];
+  assert_eq!(gaps, expected);
+
+  let id = |x| NonZeroI8::new(x).unwrap();
+  let set = btreeset! {id(-2), id(1)};
+  let values = set.missing(id(-4)..=id(3)).collect::<Vec<_>>();
+  assert_eq!(values, vec![id(-4), id(-3), id(-1), id(2), id(3)]);
+}
+
+/// Check that we can find gaps in sequences of serial numbers wrapping
+/// around.
+#[test]
+fn serial_gap_iteration() {
+  let serials = vec![Serial(65534u16), Serial(65535), Serial(0), Serial(3)];
+  let gaps = serials
+    .clone()
+    .into_iter()
+    .serial_gaps(Serial(65530)..=Serial(5))
+    .collect::<Vec<_>>();
+  let expected = vec![
+    Gap::new(Included(Serial(65530)), Excluded(Serial(65534))),
+    Gap::new(Excluded(Serial(0)), Excluded(Serial(3))),
+    Gap::new(Excluded(Serial(3)), Included(Serial(5))),
+  ];
+  assert_eq!(gaps, expected);
+
+  let gaps = serials
+    .into_iter()
+    .serial_gaps(Serial(65534)..Serial(1))
+    .collect::<Vec<_>>();
+  assert_eq!(gaps, vec![]);
+
+  let gaps = Vec::new()
+    .into_iter()
+    .serial_gaps(Serial(10u32)..Serial(20))
+    .collect::<Vec<_>>();
+  assert_eq!(
+    gaps,
+    vec![Gap::new(Included(Serial(10)), Excluded(Serial(20)))]
+  );
+}
+
+/// Check that we can find missing days in a date keyed map.
+#[cfg(feature = "chrono")]
+#[test]
+fn chrono_date_gap_iteration() {
+  use chrono::NaiveDate;
+
+  let day = |d| NaiveDate::from_ymd_opt(2026, 2, d).unwrap();
+  let reports = btreemap! {
+    day(1) => "report 1",
+    day(2) => "report 2",
+    day(5) => "report 3",
+    day(28) => "report 4",
+  };
+
+  let gaps = reports.gaps(day(1)..).collect::<Vec<_>>();
+  let expected = vec![
+    Gap::new(Excluded(day(2)), Excluded(day(5))),
+    Gap::new(Excluded(day(5)), Excluded(day(28))),
+    Gap::new(Excluded(day(28)), Unbounded),
+  ];
+  assert_eq!(gaps, expected);
+
+  let march = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+  let missing = reports.missing(day(25)..=march).collect::<Vec<_>>();
+  assert_eq!(missing, vec![day(25), day(26), day(27), march]);
+  assert_eq!(NaiveDate::MAX.checked_inc(), None);
+}
+
+/// Check that we can find missing samples among date times.
+#[cfg(feature = "chrono")]
+#[test]
+fn chrono_date_time_gap_iteration() {
+  use chrono::NaiveDate;
+
+  let tick = Duration::from_secs(60);
+  let at = |m| {
+    let time = NaiveDate::from_ymd_opt(2026, 1, 1)
+      .unwrap()
+      .and_hms_opt(23, m, 0)
+      .unwrap();
+    Ticked::new(time, tick)
+  };
+
+  let samples = btreeset! {at(0), at(1), at(2), at(4), at(58)};
+  let missing = samples.missing(at(0)..).take(3).collect::<Vec<_>>();
+  assert_eq!(missing, vec![at(3), at(5), at(6)]);
+
+  let midnight = NaiveDate::from_ymd_opt(2026, 1, 2)
+    .unwrap()
+    .and_hms_opt(0, 0, 0)
+    .unwrap();
+  assert_eq!(at(59).inc(), Ticked::new(midnight, tick));
+}
+
+/// Check that we can find missing days among `time` dates.
+#[cfg(feature = "time")]
+#[test]
+fn time_date_gap_iteration() {
+  use time::Date;
+  use time::Month;
+
+  let day = |d| Date::from_calendar_date(2024, Month::February, d).unwrap();
+  let days = btreeset! {day(27), day(29)};
+  let march = Date::from_calendar_date(2024, Month::March, 1).unwrap();
+  let missing = days.missing(day(26)..=march).collect::<Vec<_>>();
+  assert_eq!(missing, vec![day(26), day(28), march]);
+  assert_eq!(Date::MAX.checked_inc(), None);
+}
+
+/// Check that we can find missing samples among offset date times.
+#[cfg(feature = "time")]
+#[test]
+fn time_offset_date_time_gap_iteration() {
+  use time::OffsetDateTime;
+
+  let tick = Duration::from_millis(500);
+  let at = |ms| {
+    let time = OffsetDateTime::from_unix_timestamp_nanos(ms * 1_000_000i128).unwrap();
+    Ticked::new(time, tick)
+  };
+
+  let samples = btreeset! {at(0), at(500), at(2000)};
+  let gaps = samples.gaps(at(0)..=at(2500)).collect::<Vec<_>>();
+  let expected = vec![
+    Gap::new(Excluded(at(500)), Excluded(at(2000))),
+    Gap::new(Excluded(at(2000)), Included(at(2500))),
+  ];
+  assert_eq!(gaps, expected);
+
+  let missing = samples.missing(at(0)..=at(2500)).collect::<Vec<_>>();
+  assert_eq!(missing, vec![at(1000), at(1500), at(2500)]);
+}
+
+/// Check that we can find missing samples among system times.
+#[test]
+fn system_time_gap_iteration() {
+  let tick = Duration::from_millis(250);
+  let start = SystemTime::now();
+  let at = |n| Ticked::new(start + tick * n, tick);
+
+  let samples = btreeset! {at(0), at(1), at(3), at(4), at(8)};
+  let gaps = samples.gaps(at(0)..at(10)).collect::<Vec<_>>();
+  let expected = vec![
+    Gap::new(Excluded(at(1)), Excluded(at(3))),
+    Gap::new(Excluded(at(4)), Excluded(at(8))),
+    Gap::new(Excluded(at(8)), Excluded(at(10))),
+  ];
+  assert_eq!(gaps, expected);
+
+  let missing = samples.missing(at(0)..at(10)).collect::<Vec<_>>();
+  assert_eq!(missing, vec![at(2), at(5), at(6), at(7), at(9)]);
+  assert_eq!(at(2).inc().value(), at(3).value());
+  assert_eq!(at(2).tick(), tick);
+}
+
+/// Check that we can find gaps in a set of durations.
+#[test]
+fn duration_gap_iteration() {
+  let nanos = btreeset! {Duration::from_nanos(1), Duration::from_nanos(3)};
+  let missing = nanos
+    .missing(Duration::ZERO..=Duration::from_nanos(4))
+    .collect::<Vec<_>>();
+  let expected = vec![
+    Duration::ZERO,
+    Duration::from_nanos(2),
+    Duration::from_nanos(4),
+  ];
+  assert_eq!(missing, expected);
+
+  let tick = Duration::from_secs(10);
+  let at = |s| Ticked::new(Duration::from_secs(s), tick);
+  let chunks = btreeset! {at(0), at(10), at(40)};
+  let gaps = chunks.gaps(at(0)..at(60)).collect::<Vec<_>>();
+  let expected = vec![
+    Gap::new(Excluded(at(10)), Excluded(at(40))),
+    Gap::new(Excluded(at(40)), Excluded(at(60))),
+  ];
+  assert_eq!(gaps, expected);
+}
+
+/// Check that we can derive `Inc` and `Dec` for newtypes.
+#[cfg(feature = "derive")]
+#[test]
+fn derived_inc_and_dec() {
+  use gaps::Dec as _;
+
+  #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, gaps::Inc, gaps::Dec)]
+  struct OrderId(u64);
+
+  #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, gaps::Inc)]
+  struct Handle<T> {
+    id: T,
+  }
+
+  assert_eq!(OrderId(41).inc(), OrderId(42));
+  assert_eq!(OrderId(41).dec(), OrderId(40));
+  assert_eq!(OrderId(u64::MAX).checked_inc(), None);
+  assert_eq!(Handle { id: 1u8 }.inc(), Handle { id: 2 });
+
+  let orders = btreeset! {OrderId(2), OrderId(5)};
+  let missing = orders.missing(OrderId(1)..=OrderId(6)).collect::<Vec<_>>();
+  assert_eq!(
+    missing,
+    vec![OrderId(1), OrderId(3), OrderId(4), OrderId(6)]
+  );
+}
+
+/// Check that we can find gaps among generic numeric types.
+#[cfg(feature = "num")]
+#[test]
+fn numeric_gap_iteration() {
+  use std::ops::Add;
+  use std::ops::Mul;
+
+  use gaps::Numeric;
+  use num_traits::CheckedAdd;
+  use num_traits::One;
+
+  /// A numeric type restricted to values below 1000.
+  #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+  struct Small(u16);
+
+  impl Add for Small {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+      self.checked_add(&other).unwrap()
+    }
+  }
+
+  impl Mul for Small {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+      Self(self.0 * other.0)
+    }
+  }
+
+  impl CheckedAdd for Small {
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+      Some(self.0 + other.0).filter(|x| *x < 1000).map(Self)
+    }
+  }
+
+  impl One for Small {
+    fn one() -> Self {
+      Self(1)
+    }
+  }
+
+  let n = |x| Numeric(Small(x));
+  let set = btreeset! {n(997), n(998)};
+  let gaps = set.gaps(n(995)..).collect::<Vec<_>>();
+  let expected = vec![
+    Gap::new(Included(n(995)), Excluded(n(997))),
+    Gap::new(Excluded(n(998)), Unbounded),
+  ];
+  assert_eq!(gaps, expected);
+  assert_eq!(
+    set.missing(n(995)..).collect::<Vec<_>>(),
+    vec![n(995), n(996), n(999)]
+  );
+}
+
+
+/// Check that gaps can be found between elements of a type that is
+/// `Clone` but not `Copy`.
+#[test]
+fn non_copy_gap_iteration() {
+  #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+  struct Boxed(Box<u32>);
+
+  impl gaps::Inc for Boxed {
+    fn inc(self) -> Self {
+      Self(Box::new(*self.0 + 1))
+    }
+  }
+
+  impl gaps::Distance for Boxed {
+    fn distance(self, other: Self) -> Option<usize> {
+      other
+        .0
+        .checked_sub(*self.0)
+        .map(|distance| distance as usize)
+    }
+  }
+
+  impl gaps::Step for Boxed {
+    fn forward(self, n: usize) -> Option<Self> {
+      let n = u32::try_from(n).ok()?;
+      self.0.checked_add(n).map(|value| Self(Box::new(value)))
+    }
+  }
+
+  impl gaps::Multiple for Boxed {
+    fn round_up(self, step: Self) -> Option<Self> {
+      self.0.round_up(*step.0).map(|value| Self(Box::new(value)))
+    }
+
+    fn round_down(self, step: Self) -> Option<Self> {
+      self.0.round_down(*step.0).map(|value| Self(Box::new(value)))
+    }
+
+    fn next_multiple(self, step: Self) -> Option<Self> {
+      self.0.next_multiple(*step.0).map(|value| Self(Box::new(value)))
+    }
+
+    fn prev_multiple(self, step: Self) -> Option<Self> {
+      self.0.prev_multiple(*step.0).map(|value| Self(Box::new(value)))
+    }
+  }
+
+  let b = |x| Boxed(Box::new(x));
+  let expected = vec![
+    Gap::new(Included(b(0)), Excluded(b(1))),
+    Gap::new(Excluded(b(1)), Excluded(b(4))),
+    Gap::new(Excluded(b(4)), Included(b(6))),
+  ];
+
+  let vec = vec![b(1), b(4)];
+  let gaps = vec.iter().cloned().gaps(b(0)..=b(6)).collect::<Vec<_>>();
+  assert_eq!(gaps, expected);
+  let gaps = vec.iter().gaps(b(0)..=b(6)).collect::<Vec<_>>();
+  assert_eq!(gaps, expected);
+
+  let set = btreeset! {b(1), b(4)};
+  assert_eq!(set.gaps(b(0)..=b(6)).collect::<Vec<_>>(), expected);
+  assert_eq!(
+    set.first_gap(b(1)..),
+    Some(Gap::new(Excluded(b(1)), Excluded(b(4))))
+  );
+  assert_eq!(set.next_free(b(1)..), Some(b(2)));
+
+  let map = btreemap! {b(1) => "a", b(4) => "b"};
+  assert_eq!(map.gaps(b(0)..=b(6)).collect::<Vec<_>>(), expected);
+
+  assert!(set.has_gaps(b(0)..=b(6)));
+  assert!(!set.has_gaps(b(1)..=b(1)));
+  assert_eq!(
+    set.largest_gap(b(0)..=b(6)),
+    Some(Gap::new(Excluded(b(1)), Excluded(b(4))))
+  );
+  assert_eq!(set.missing_count(b(0)..=b(6)), Some(5));
+  assert_eq!(set.stats(b(0)..=b(6)).count, 3);
+  assert_eq!(set.coverage(b(0)..b(10)), Some(0.2));
+  assert_eq!(
+    set.find_gap(b(0)..=b(6), 2, Fit::Best),
+    Some(Gap::new(Excluded(b(1)), Excluded(b(4))))
+  );
+  assert_eq!(
+    set.missing(b(0)..=b(6)).collect::<Vec<_>>(),
+    vec![b(0), b(2), b(3), b(5), b(6)]
+  );
+
+  let gaps = set.gaps(b(0)..=b(6)).align(b(2)).collect::<Vec<_>>();
+  assert_eq!(gaps, vec![Gap::new(Included(b(2)), Excluded(b(4)))]);
+  let gaps = set.gaps(b(0)..=b(6)).coalesce(2).collect::<Vec<_>>();
+  assert_eq!(gaps, vec![Gap::new(Included(b(0)), Included(b(6)))]);
+  let gaps = set.gaps(b(0)..=b(6)).clip(b(2)..=b(5)).collect::<Vec<_>>();
+  assert_eq!(
+    gaps,
+    vec![
+      Gap::new(Included(b(2)), Excluded(b(4))),
+      Gap::new(Excluded(b(4)), Included(b(5))),
+    ]
+  );
+
+  let other = btreeset! {b(2), b(5)};
+  let gaps = set
+    .gaps(b(0)..=b(6))
+    .intersect(other.gaps(b(0)..=b(6)))
+    .collect::<Vec<_>>();
+  assert_eq!(
+    gaps,
+    vec![
+      Gap::new(Included(b(0)), Excluded(b(1))),
+      Gap::new(Excluded(b(2)), Excluded(b(4))),
+      Gap::new(Excluded(b(5)), Included(b(6))),
+    ]
+  );
+  let gaps = set
+    .gaps(b(0)..=b(6))
+    .union(other.gaps(b(0)..=b(6)))
+    .collect::<Vec<_>>();
+  assert_eq!(gaps, vec![Gap::new(Included(b(0)), Included(b(6)))]);
+
+  let present = vec![
+    Gap::new(Included(b(1)), Included(b(1))),
+    Gap::new(Included(b(4)), Included(b(4))),
+  ];
+  let gaps = complement(set.gaps(b(0)..=b(6)), b(0)..=b(6)).collect::<Vec<_>>();
+  assert_eq!(gaps, present);
+  assert_eq!(set.runs(b(0)..=b(6)).collect::<Vec<_>>(), present);
+  assert_eq!(
+    set.segments(b(0)..=b(6)).collect::<Vec<_>>(),
+    vec![
+      Segment::Missing(Gap::new(Included(b(0)), Excluded(b(1)))),
+      Segment::Present(Gap::new(Included(b(1)), Included(b(1)))),
+      Segment::Missing(Gap::new(Excluded(b(1)), Excluded(b(4)))),
+      Segment::Present(Gap::new(Included(b(4)), Included(b(4)))),
+      Segment::Missing(Gap::new(Excluded(b(4)), Included(b(6)))),
+    ]
+  );
+
+  let list = set.gaps(b(0)..=b(6)).collect::<GapList<_>>();
+  assert_eq!(list.get(b(2)), Some(&Gap::new(Excluded(b(1)), Excluded(b(4)))));
+  assert!(!list.overlaps(b(4)..=b(4)));
+  assert_eq!(list.complement(b(0)..=b(6)).as_slice(), present.as_slice());
+
+  let intervals = vec![(Included(b(1)), Included(b(2)))];
+  let gaps = intervals
+    .into_iter()
+    .interval_gaps(b(0)..=b(6))
+    .collect::<Vec<_>>();
+  assert_eq!(
+    gaps,
+    vec![
+      Gap::new(Included(b(0)), Excluded(b(1))),
+      Gap::new(Excluded(b(2)), Included(b(6))),
+    ]
+  );
+
+  let gaps = vec
+    .into_iter()
+    .try_gaps(b(0)..=b(6))
+    .collect::<Result<Vec<_>, _>>()
+    .unwrap();
+  assert_eq!(gaps, expected);
+}
+
+
+/// Check that gaps can be found in the full domain of a type and that
+/// unbounded gaps can be resolved into concrete ones.
+#[test]
+fn gap_iteration_in_full_domain() {
+  let set = btreeset! {0u16, 1, 10};
+  let gaps = set.gaps_full().collect::<Vec<_>>();
+  let expected = vec![
+    Gap::new(Excluded(1), Excluded(10)),
+    Gap::new(Excluded(10), Included(u16::MAX)),
+  ];
+  assert_eq!(gaps, expected);
+  assert_eq!(
+    set.iter().copied().gaps_full().collect::<Vec<_>>(),
+    expected
+  );
+  assert_eq!(
+    BTreeSet::<i8>::new().gaps_full().collect::<Vec<_>>(),
+    vec![Gap::new(Included(i8::MIN), Included(i8::MAX))]
+  );
+
+  let gap = set.gaps(5..).last().map(Gap::into_bounds).map(resolve);
+  assert_eq!(gap, Some((Excluded(10), Included(u16::MAX))));
+  assert_eq!(
+    set.missing_count(resolve::<u16>((Unbounded, Unbounded))),
+    Some(65533)
+  );
+}
+
+
+/// Check that gaps are reported correctly for values advancing by a
+/// custom step.
+#[test]
+fn step_by_gap_iteration() {
+  let s = |x| StepBy::new(x, 5u32);
+  let vec = vec![s(10), s(15), s(30)];
+  let gaps = vec.iter().copied().gaps(s(0)..=s(40)).collect::<Vec<_>>();
+  let expected = vec![
+    Gap::new(Included(s(0)), Excluded(s(10))),
+    Gap::new(Excluded(s(15)), Excluded(s(30))),
+    Gap::new(Excluded(s(30)), Included(s(40))),
+  ];
+  assert_eq!(gaps, expected);
+
+  let missing = vec
+    .into_iter()
+    .missing(s(0)..=s(40))
+    .map(StepBy::into_inner)
+    .collect::<Vec<_>>();
+  assert_eq!(missing, vec![0, 5, 20, 25, 35, 40]);
+}
+
+
+/// Check that gaps are reported correctly for quantized floating
+/// point values.
+#[cfg(feature = "float")]
+#[test]
+fn quantized_float_gap_iteration() {
+  use gaps::Quantized;
+
+  let at = |x| Quantized::new(x, 0.1f64);
+  let set = [0.0, 0.1, 0.20000001, 0.5, 0.61]
+    .iter()
+    .copied()
+    .map(at)
+    .collect::<BTreeSet<_>>();
+  let gaps = set.gaps(at(0.0)..at(1.0)).collect::<Vec<_>>();
+  let expected = vec![
+    Gap::new(Excluded(at(0.2)), Excluded(at(0.5))),
+    Gap::new(Excluded(at(0.6)), Excluded(at(1.0))),
+  ];
+  assert_eq!(gaps, expected);
+
+  let buckets = set
+    .missing(at(0.0)..at(1.0))
+    .map(|q| q.bucket())
+    .collect::<Vec<_>>();
+  assert_eq!(buckets, vec![3, 4, 7, 8, 9]);
+}
+
+
+/// Check that we can find gaps among composite keys.
+#[test]
+fn tuple_gap_iteration() {
+  let set = btreeset! {(1u16, 0u16), (1, 1), (1, u16::MAX), (2, 1)};
+  let gaps = set.gaps((1, 0)..(2, 3)).collect::<Vec<_>>();
+  let expected = vec![
+    Gap::new(Excluded((1, 1)), Excluded((1, u16::MAX))),
+    Gap::new(Excluded((1, u16::MAX)), Excluded((2, 1))),
+    Gap::new(Excluded((2, 1)), Excluded((2, 3))),
+  ];
+  assert_eq!(gaps, expected);
+
+  let missing = set.missing((1, u16::MAX)..(2, 3)).collect::<Vec<_>>();
+  assert_eq!(missing, vec![(2, 0), (2, 2)]);
+}
+
+
+/// Check that we can find gaps among fixed-width binary keys.
+#[test]
+fn byte_array_gap_iteration() {
+  let set = btreeset! {[0x00u8, 0x00, 0xff], [0x00, 0x01, 0x01], [0xff, 0xff, 0xff]};
+  let gaps = set.gaps_full().collect::<Vec<_>>();
+  let expected = vec![
+    Gap::new(Included([0x00, 0x00, 0x00]), Excluded([0x00, 0x00, 0xff])),
+    Gap::new(Excluded([0x00, 0x00, 0xff]), Excluded([0x00, 0x01, 0x01])),
+    Gap::new(Excluded([0x00, 0x01, 0x01]), Excluded([0xff, 0xff, 0xff])),
+  ];
+  assert_eq!(gaps, expected);
+
+  let missing = set.missing([0x00, 0x00, 0xfe]..[0x00, 0x01, 0x02]);
+  let expected = vec![[0x00, 0x00, 0xfe], [0x00, 0x01, 0x00]];
+  assert_eq!(missing.collect::<Vec<_>>(), expected);
+}
+
+
+/// Check that a `Gap` can be converted from and into a tuple of bounds.
+#[test]
+fn gap_tuple_conversion() {
+  let set = btreeset! {2u32, 5};
+  let gaps = set.gaps(0..=6).map(<(_, _)>::from).collect::<Vec<_>>();
+  let expected = vec![
+    (Included(0), Excluded(2)),
+    (Excluded(2), Excluded(5)),
+    (Excluded(5), Included(6)),
+  ];
+  assert_eq!(gaps, expected);
+
+  let gaps = expected.into_iter().map(Gap::from).collect::<Vec<_>>();
+  assert_eq!(gaps, set.gaps(0..=6).collect::<Vec<_>>());
+  assert_eq!(gaps[1].start(), &Excluded(2));
+  assert_eq!(gaps[1].end(), &Excluded(5));
+}
+
+
+/// Check that the values inside of gaps can be enumerated in batches.
+#[test]
+fn gap_value_batching() {
+  let ids = btreeset! {1u64, 2, 9};
+  let batches = ids
+    .gaps(1..=12)
+    .map(|gap| gap.iter().take(4).collect::<Vec<_>>())
+    .collect::<Vec<_>>();
+  assert_eq!(batches, vec![vec![3, 4, 5, 6], vec![10, 11, 12]]);
+}
+
+
+/// Check that generated fixtures and gaps are consistent with gap
+/// iteration.
+#[cfg(feature = "proptest")]
+#[test]
+fn proptest_generation() {
+  use proptest::arbitrary::any;
+  use proptest::test_runner::TestRunner;
+
+  use gaps::GapFixture;
+
+  let mut runner = TestRunner::default();
+  runner
+    .run(&any::<GapFixture<u8>>(), |fixture| {
+      let gaps = fixture.values.iter().copied().gaps(fixture.range).collect::<Vec<_>>();
+      assert_eq!(gaps, fixture.gaps);
+      Ok(())
+    })
+    .unwrap();
+
+  runner
+    .run(&any::<Gap<i16>>(), |gap| {
+      assert!(!gap.is_empty());
+      Ok(())
+    })
+    .unwrap();
+
+  runner
+    .run(&any::<GapList<u16>>(), |list| {
+      let slice = list.as_slice();
+      assert!(slice
+        .windows(2)
+        .all(|pair| pair[0].end() != &Unbounded && pair[0] < pair[1]));
+      Ok(())
+    })
+    .unwrap();
+}
+
+
+/// Check that fixtures and gaps generated and shrunk by `quickcheck`
+/// are consistent with gap iteration.
+#[cfg(feature = "quickcheck")]
+#[test]
+fn quickcheck_generation() {
+  use quickcheck::Arbitrary as _;
+  use quickcheck::QuickCheck;
+
+  use gaps::GapFixture;
+
+  fn consistent(fixture: &GapFixture<u8>) -> bool {
+    fixture.values.windows(2).all(|pair| pair[0] < pair[1])
+      && fixture.values.iter().copied().gaps(fixture.range.clone()).collect::<Vec<_>>() == fixture.gaps
+  }
+
+  fn check_fixture(fixture: GapFixture<u8>) -> bool {
+    consistent(&fixture) && fixture.shrink().all(|fixture| consistent(&fixture))
+  }
+
+  fn check_gap(gap: Gap<i16>) -> bool {
+    !gap.is_empty() && gap.shrink().all(|gap| !gap.is_empty())
+  }
+
+  fn check_list(list: GapList<u16>) -> bool {
+    list.shrink().chain(Some(list)).all(|list| {
+      list
+        .as_slice()
+        .windows(2)
+        .all(|pair| pair[0].end() != &Unbounded && pair[0] < pair[1])
+    })
+  }
+
+  QuickCheck::new().quickcheck(check_fixture as fn(_) -> bool);
+  QuickCheck::new().quickcheck(check_gap as fn(_) -> bool);
+  QuickCheck::new().quickcheck(check_list as fn(_) -> bool);
+}
+
+
+/// Check that gaps can be found in a sorted `VecDeque`, even if its
+/// contents wrap around the internal buffer.
+#[test]
+fn vec_deque_gap_iteration() {
+  let mut deque = VecDeque::from(vec![9, 12, 13, 15, 16, 20]);
+  deque.push_front(2);
+  deque.push_front(1);
+  assert!(!deque.as_slices().1.is_empty());
+
+  let gaps = deque.gaps(0..=20).collect::<Vec<_>>();
+  let expected = vec![
+    Gap::new(Included(0), Excluded(1)),
+    Gap::new(Excluded(2), Excluded(9)),
+    Gap::new(Excluded(9), Excluded(12)),
+    Gap::new(Excluded(13), Excluded(15)),
+    Gap::new(Excluded(16), Excluded(20)),
+  ];
+  assert_eq!(gaps, expected);
+
+  let gaps = deque.gaps((Excluded(9), Excluded(15))).collect::<Vec<_>>();
+  let expected = vec![
+    Gap::new(Excluded(9), Excluded(12)),
+    Gap::new(Excluded(13), Excluded(15)),
+  ];
+  assert_eq!(gaps, expected);
+
+  assert_eq!(deque.next_free(12..), Some(14));
+  assert_eq!(
+    VecDeque::<u8>::new().first_gap(..),
+    Some(Gap::new(Unbounded, Unbounded))
+  );
+}
+
+
+/// Check that keys whose value does not satisfy a predicate are
+/// treated as absent.
+#[test]
+fn map_gap_iteration_with_predicate() {
+  let map = btreemap! {1 => true, 2 => false, 3 => true, 6 => false};
+
+  let gaps = map.gaps_where(.., |present| *present).collect::<Vec<_>>();
+  let expected = vec![
+    Gap::new(Unbounded, Excluded(1)),
+    Gap::new(Excluded(1), Excluded(3)),
+    Gap::new(Excluded(3), Unbounded),
+  ];
+  assert_eq!(gaps, expected);
+
+  let gaps = map.gaps_where(2..=6, |_| true).collect::<Vec<_>>();
+  let expected = vec![Gap::new(Excluded(3), Excluded(6))];
+  assert_eq!(gaps, expected);
+
+  let gaps = map.gaps_where(2..=6, |_| false).collect::<Vec<_>>();
+  let expected = vec![Gap::new(Included(2), Included(6))];
+  assert_eq!(gaps, expected);
+}
+
+
+/// Check that gaps among map keys are reported together with their
+/// neighboring entries.
+#[test]
+fn map_gap_iteration_with_neighbors() {
+  let map = btreemap! {1 => 'a', 2 => 'b', 5 => 'c', 8 => 'd'};
+
+  let gaps = map
+    .gaps_with_neighbors(..)
+    .map(|gap| (gap.before, gap.gap, gap.after))
+    .collect::<Vec<_>>();
+  let expected = vec![
+    (None, Gap::new(Unbounded, Excluded(1)), Some((&1, &'a'))),
+    (
+      Some((&2, &'b')),
+      Gap::new(Excluded(2), Excluded(5)),
+      Some((&5, &'c')),
+    ),
+    (
+      Some((&5, &'c')),
+      Gap::new(Excluded(5), Excluded(8)),
+      Some((&8, &'d')),
+    ),
+    (Some((&8, &'d')), Gap::new(Excluded(8), Unbounded), None),
+  ];
+  assert_eq!(gaps, expected);
+
+  let gaps = map
+    .gaps_with_neighbors((Excluded(1), Included(6)))
+    .map(|gap| (gap.before, gap.gap, gap.after))
+    .collect::<Vec<_>>();
+  let expected = vec![
+    (
+      Some((&2, &'b')),
+      Gap::new(Excluded(2), Excluded(5)),
+      Some((&5, &'c')),
+    ),
+    (Some((&5, &'c')), Gap::new(Excluded(5), Included(6)), None),
+  ];
+  assert_eq!(gaps, expected);
+}
+
+
+/// Check that gaps in `roaring` bitmaps match those of an equivalent
+/// `BTreeSet`.
+#[cfg(feature = "roaring")]
+#[test]
+fn roaring_gap_iteration() {
+  use roaring::RoaringBitmap;
+  use roaring::RoaringTreemap;
+
+  let values = [0, 1, 2, 3, 10, 11, 70_000, 70_001, 70_005, u32::MAX - 1];
+  let set = values.iter().copied().collect::<BTreeSet<u32>>();
+  let bitmap = values.iter().copied().collect::<RoaringBitmap>();
+
+  for (start, end) in [
+    (Unbounded, Unbounded),
+    (Included(0), Excluded(0)),
+    (Excluded(1), Included(70_003)),
+    (Included(5), Excluded(70_005)),
+    (Excluded(70_001), Unbounded),
+    (Excluded(u32::MAX), Unbounded),
+  ] {
+    assert_eq!(
+      bitmap.gaps((start, end)).collect::<Vec<_>>(),
+      set.gaps((start, end)).collect::<Vec<_>>()
+    );
+    assert_eq!(
+      bitmap.rgaps((start, end)).collect::<Vec<_>>(),
+      set.rgaps((start, end)).collect::<Vec<_>>()
+    );
+  }
+  assert_eq!(bitmap.gaps(..).nth(2), set.gaps(..).nth(2));
+
+  let mut gaps = bitmap.gaps(..);
+  gaps.advance_to(70_003);
+  assert_eq!(
+    gaps.collect::<Vec<_>>(),
+    vec![
+      Gap::new(Included(70_003), Excluded(70_005)),
+      Gap::new(Excluded(70_005), Excluded(u32::MAX - 1)),
+      Gap::new(Excluded(u32::MAX - 1), Unbounded),
+    ]
+  );
+
+  let values = [1, 2, 5, 1 << 32, (1 << 32) + 3, 1 << 40, u64::MAX];
+  let set = values.iter().copied().collect::<BTreeSet<u64>>();
+  let treemap = values.iter().copied().collect::<RoaringTreemap>();
+
+  for (start, end) in [
+    (Unbounded, Unbounded),
+    (Included(0), Excluded(0)),
+    (Excluded(1), Included(1 << 32)),
+    (Included(3), Excluded((1 << 32) + 3)),
+    (Included(1 << 33), Excluded(1 << 40)),
+    (Excluded((1 << 32) + 1), Unbounded),
+    (Excluded(u64::MAX), Unbounded),
+  ] {
+    assert_eq!(
+      treemap.gaps((start, end)).collect::<Vec<_>>(),
+      set.gaps((start, end)).collect::<Vec<_>>()
+    );
+  }
+  assert_eq!(treemap.gaps(..).nth(3), set.gaps(..).nth(3));
+}
+
+
+/// Check that gaps in `im` collections match those of equivalent
+/// standard library collections.
+#[cfg(feature = "im")]
+#[test]
+fn im_gap_iteration() {
+  use im::OrdMap;
+  use im::OrdSet;
+
+  let values = (0..100).chain(105..200).chain([300, 302]);
+  let set = values.clone().collect::<BTreeSet<u32>>();
+  let ord_set = values.clone().collect::<OrdSet<u32>>();
+  let map = values.clone().map(|v| (v, ())).collect::<BTreeMap<_, _>>();
+  let ord_map = values.map(|v| (v, ())).collect::<OrdMap<_, ()>>();
+
+  for (start, end) in [
+    (Unbounded, Unbounded),
+    (Included(0), Excluded(0)),
+    (Excluded(50), Included(301)),
+    (Included(150), Excluded(302)),
+  ] {
+    let expected = set.gaps((start, end)).collect::<Vec<_>>();
+    assert_eq!(ord_set.gaps((start, end)).collect::<Vec<_>>(), expected);
+    assert_eq!(ord_map.gaps((start, end)).collect::<Vec<_>>(), expected);
+    assert_eq!(
+      ord_set.rgaps((start, end)).collect::<Vec<_>>(),
+      set.rgaps((start, end)).collect::<Vec<_>>()
+    );
+  }
+
+  let mut gaps = ord_map.gaps(..);
+  gaps.advance_to(150);
+  assert_eq!(
+    gaps.collect::<Vec<_>>(),
+    map.gaps(150..).collect::<Vec<_>>()
+  );
+}
+
+/// Check that we can convert between `GapList` and `rangemap` sets and
+/// find the gaps between the ranges of the latter.
+#[cfg(feature = "rangemap")]
+#[test]
+fn rangemap_interop() {
+  use std::convert::TryFrom as _;
+
+  use gaps::GapError;
+  use gaps::RangeSetGappable as _;
+  use rangemap::RangeInclusiveSet;
+  use rangemap::RangeSet;
+
+  let set = btreeset! {2u8, 3, 7, 255};
+  let list = set.gaps(0..).collect::<GapList<_>>();
+  let ranges = RangeSet::try_from(list.clone()).unwrap();
+  assert_eq!(
+    ranges.iter().cloned().collect::<Vec<_>>(),
+    vec![0..2, 4..7, 8..255]
+  );
+  let list = GapList::from(ranges.clone());
+  assert_eq!(list.get(5), Some(&Gap::new(Included(4), Excluded(7))));
+  assert_eq!(RangeSet::try_from(list.clone()), Ok(ranges));
+
+  let ranges = RangeInclusiveSet::try_from(list.clone()).unwrap();
+  assert_eq!(
+    ranges.iter().cloned().collect::<Vec<_>>(),
+    vec![0..=1, 4..=6, 8..=254]
+  );
+  assert_eq!(GapList::from(ranges.clone()).len(), 3);
+  assert_eq!(
+    ranges.interval_gaps(1..).collect::<Vec<_>>(),
+    vec![
+      Gap::new(Excluded(1), Excluded(4)),
+      Gap::new(Excluded(6), Excluded(8)),
+      Gap::new(Excluded(254), Unbounded),
+    ]
+  );
+
+  let list = btreeset! {2u8}.gaps(0..).collect::<GapList<_>>();
+  assert_eq!(RangeSet::try_from(list), Err(GapError::Unbounded));
+}
+
+
+/// Check that gaps can be found among the values referenced by the
+/// items of an iterator.
+#[test]
+fn reference_gap_iteration() {
+  let set = btreeset! {2u16, 3, 7};
+  let gaps = set.iter().gaps(0..10).collect::<Vec<_>>();
+  let expected = set.iter().copied().gaps(0..10).collect::<Vec<_>>();
+  assert_eq!(gaps, expected);
+
+  assert_eq!(set.iter().next_free(2..), Some(4));
+  assert_eq!(set.iter().missing_count(0..=7), Some(5));
+
+  let values = [3, 1];
+  let result = values.iter().try_gaps(..).collect::<Result<Vec<_>, _>>();
+  assert!(result.is_err());
+}
+
+
+/// Check that gaps can be found among unordered elements.
+#[test]
+fn unordered_gap_iteration() {
+  let values = [9u32, 2, 5, 2, 3, 12];
+  let gaps = values.iter().gaps_sorted(1..=10).collect::<Vec<_>>();
+  let expected = vec![
+    Gap::new(Included(1), Excluded(2)),
+    Gap::new(Excluded(3), Excluded(5)),
+    Gap::new(Excluded(5), Excluded(9)),
+    Gap::new(Excluded(9), Included(10)),
+  ];
+  assert_eq!(gaps, expected);
+
+  let set = values.iter().copied().collect::<HashSet<_>>();
+  let gaps = set.gaps(1..=10).collect::<Vec<_>>();
+  assert_eq!(gaps, expected);
+
+  let gaps = set.gaps((Excluded(2), Excluded(9))).collect::<Vec<_>>();
+  let expected = vec![
+    Gap::new(Excluded(3), Excluded(5)),
+    Gap::new(Excluded(5), Excluded(9)),
+  ];
+  assert_eq!(gaps, expected);
+}
+
+
+/// Check that gaps between overlapping intervals not ordered by their
+/// start are reported correctly.
+#[test]
+fn unordered_interval_gap_iteration() {
+  let busy = vec![
+    (Included(10), Excluded(12)),
+    (Included(2), Included(4)),
+    (Included(3), Included(8)),
+    (Excluded(1), Included(2)),
+    (Included(20), Unbounded),
+    (Included(15), Included(16)),
+  ];
+  let gaps = busy
+    .clone()
+    .into_iter()
+    .interval_gaps_unordered(0..30, 4)
+    .collect::<Vec<_>>();
+  let expected = vec![
+    Gap::new(Included(0), Included(1)),
+    Gap::new(Excluded(8), Excluded(10)),
+    Gap::new(Included(12), Excluded(15)),
+    Gap::new(Excluded(16), Excluded(20)),
+  ];
+  assert_eq!(gaps, expected);
+
+  // With a window too small, the gap before the displaced interval has
+  // already been reported, but later gaps are still correct.
+  let gaps = busy
+    .into_iter()
+    .interval_gaps_unordered(0..30, 1)
+    .collect::<Vec<_>>();
+  let expected = vec![
+    Gap::new(Included(0), Excluded(10)),
+    Gap::new(Included(12), Excluded(20)),
+  ];
+  assert_eq!(gaps, expected);
+}
+
+
+/// Check that holes in a slice of options are reported correctly.
+#[test]
+fn option_slice_holes() {
+  let buffer = [Some("a"), None, Some("c"), None, None, Some("f")];
+  let holes = buffer.iter().holes().collect::<Vec<_>>();
+  let expected = vec![
+    Gap::new(Included(1), Excluded(2)),
+    Gap::new(Included(3), Excluded(5)),
+  ];
+  assert_eq!(holes, expected);
+}
+
+
+/// Check that gaps in a `BTreeMap` can be iterated in descending order.
+#[test]
+fn reverse_map_gap_iteration() {
+  let map = btreemap! {2 => "b", 3 => "c", 6 => "f"};
+  let gaps = map.rgaps(..).collect::<Vec<_>>();
+  let expected = vec![
+    Gap::new(Excluded(6), Unbounded),
+    Gap::new(Excluded(3), Excluded(6)),
+    Gap::new(Unbounded, Excluded(2)),
+  ];
+  assert_eq!(gaps, expected);
+
+  let gaps = map.rgaps(3..=5).collect::<Vec<_>>();
+  let expected = vec![Gap::new(Excluded(3), Included(5))];
+  assert_eq!(gaps, expected);
+}
+
+
+/// Check that values and ranges can be added to and removed from a
+/// `GapSet`.
+#[test]
+fn gap_set_insertion_removal() {
+  let mut set = GapSet::new();
+  assert!(set.is_empty());
+  assert!(set.insert(3));
+  assert!(!set.insert(3));
+  assert!(set.insert(4));
+  assert_eq!(set.as_slice(), &[Gap::new(Included(3), Excluded(5))]);
+
+  set.insert_range(6..10);
+  set.insert_range(5..5);
+  assert_eq!(set.range_count(), 2);
+  set.insert_range(5..6);
+  assert_eq!(set.range_count(), 1);
+  assert!((3..10).all(|value| set.contains(value)));
+  assert!(!set.contains(2));
+  assert!(!set.contains(10));
+
+  assert!(set.remove(5));
+  assert!(!set.remove(5));
+  set.remove_range(8..);
+  let gaps = set.gaps(0..=10).collect::<Vec<_>>();
+  let expected = vec![
+    Gap::new(Included(0), Excluded(3)),
+    Gap::new(Included(5), Excluded(6)),
+    Gap::new(Included(8), Included(10)),
+  ];
+  assert_eq!(gaps, expected);
+
+  set.remove_range(..);
+  assert!(set.is_empty());
+}
+
+
+/// Check that `GapSet` range insertion and removal handles all kinds
+/// of bounds and reports the actual changes.
+#[test]
+fn gap_set_range_deltas() {
+  let mut set = GapSet::new();
+  let added = set.insert_range((Excluded(2), Excluded(5)));
+  assert_eq!(added, vec![Gap::new(Included(3), Excluded(5))]);
+  // Adjacent ranges are merged, even if not overlapping.
+  let added = set.insert_range((Excluded(4), Included(6)));
+  assert_eq!(added, vec![Gap::new(Included(5), Excluded(7))]);
+  let added = set.insert_range(..=1u8);
+  assert_eq!(added, vec![Gap::new(Included(0), Excluded(2))]);
+  assert_eq!(set.range_count(), 2);
+  let added = set.insert_range(2..=2);
+  assert_eq!(added, vec![Gap::new(Included(2), Excluded(3))]);
+  assert_eq!(set.as_slice(), &[Gap::new(Included(0), Excluded(7))]);
+  assert_eq!(set.insert_range(3..5), Vec::new());
+
+  let added = set.insert_range(10..);
+  assert_eq!(added, vec![Gap::new(Included(10), Included(u8::MAX))]);
+  let removed = set.remove_range((Excluded(0), Excluded(3)));
+  assert_eq!(removed, vec![Gap::new(Included(1), Excluded(3))]);
+  let removed = set.remove_range(5..=12);
+  assert_eq!(
+    removed,
+    vec![
+      Gap::new(Included(5), Excluded(7)),
+      Gap::new(Included(10), Excluded(13)),
+    ]
+  );
+  assert_eq!(set.remove_range(7..10), Vec::new());
+  assert_eq!(
+    set.as_slice(),
+    &[
+      Gap::new(Included(0), Excluded(1)),
+      Gap::new(Included(3), Excluded(5)),
+      Gap::new(Included(13), Included(u8::MAX)),
+    ]
+  );
+}
+
+
+/// Check that `GapSet` range insertion and removal ignore ranges not
+/// containing any values of the type's domain.
+#[test]
+fn gap_set_empty_domain_ranges() {
+  let mut set = GapSet::new();
+  assert_eq!(set.insert_range(..0u8), Vec::new());
+  assert_eq!(set.insert_range((Excluded(u8::MAX), Unbounded)), Vec::new());
+  assert!(set.is_empty());
+
+  let _added = set.insert_range(..=5);
+  let removed = set.remove_range(0..=2);
+  assert_eq!(removed, vec![Gap::new(Included(0), Excluded(3))]);
+  assert_eq!(set.as_slice(), &[Gap::new(Included(3), Excluded(6))]);
+
+  let _added = set.insert_range(..3);
+  assert_eq!(set.remove_range(..0), Vec::new());
+  assert_eq!(
+    set.remove_range(0..),
+    vec![Gap::new(Included(0), Excluded(6))]
+  );
+  assert!(set.is_empty());
+}
+
+
+/// Check that `GapSet` objects containing the same values compare and
+/// hash equal, irrespective of the ranges they were constructed from.
+#[test]
+fn gap_set_normalization() {
+  let mut set1 = GapSet::new();
+  set1.insert_range(0..5);
+  set1.insert_range((Excluded(7), Unbounded));
+
+  let mut set2 = GapSet::new();
+  set2.insert_range(0..=4);
+  set2.insert_range(8..=u8::MAX);
+  assert_eq!(set1, set2);
+
+  let mut set3 = GapSet::new();
+  set3.insert_range(0..10);
+  set3.remove_range(5..=7);
+  set3.insert_range(10..);
+  assert_eq!(set1, set3);
+
+  let sets = vec![set1, set2, set3].into_iter().collect::<HashSet<_>>();
+  assert_eq!(sets.len(), 1);
+
+  let mut set = GapSet::new();
+  set.insert_range(..0u8);
+  set.insert_range((Excluded(u8::MAX), Unbounded));
+  assert!(set.is_empty());
+}
+
+
+/// Check that the gaps of a `GapSet` within a range are reported
+/// correctly.
+#[test]
+fn gap_set_gaps_in_range() {
+  let mut set = GapSet::new();
+  set.insert_range(2..4);
+  set.insert_range(6..=8);
+  set.insert_range(12..);
+
+  let gaps = set.gaps_in(3..13).collect::<Vec<_>>();
+  let expected = vec![
+    Gap::new(Included(4), Excluded(6)),
+    Gap::new(Included(9), Excluded(12)),
+  ];
+  assert_eq!(gaps, expected);
+
+  let gaps = set.gaps_in(..).collect::<Vec<_>>();
+  let expected = vec![
+    Gap::new(Unbounded, Excluded(2)),
+    Gap::new(Included(4), Excluded(6)),
+    Gap::new(Included(9), Excluded(12)),
+  ];
+  assert_eq!(gaps, expected);
+
+  assert_eq!(set.gaps_in(6..8).next(), None);
+  assert_eq!(set.gaps_in(20..30).next(), None);
+  assert_eq!(
+    set.gaps_in(9..=10).collect::<Vec<_>>(),
+    vec![Gap::new(Included(9), Included(10))]
+  );
+  assert_eq!(
+    GapSet::<u8>::new().gaps_in(1..2).collect::<Vec<_>>(),
+    vec![Gap::new(Included(1), Excluded(2))]
+  );
+}
+
+
+/// Check that we can combine `GapSet` objects using set operations.
+#[test]
+fn gap_set_operations() {
+  let mut set1 = GapSet::new();
+  set1.insert_range(0..10);
+  set1.insert_range(20..30);
+  let mut set2 = GapSet::new();
+  set2.insert_range(5..25);
+
+  assert_eq!(
+    (&set1 | &set2).as_slice(),
+    &[Gap::new(Included(0), Excluded(30))]
+  );
+  assert_eq!(
+    (&set1 & &set2).as_slice(),
+    &[
+      Gap::new(Included(5), Excluded(10)),
+      Gap::new(Included(20), Excluded(25)),
+    ]
+  );
+  assert_eq!(
+    (&set1 - &set2).as_slice(),
+    &[
+      Gap::new(Included(0), Excluded(5)),
+      Gap::new(Included(25), Excluded(30)),
+    ]
+  );
+  assert_eq!(
+    (&set1 ^ &set2).as_slice(),
+    &[
+      Gap::new(Included(0), Excluded(5)),
+      Gap::new(Included(10), Excluded(20)),
+      Gap::new(Included(25), Excluded(30)),
+    ]
+  );
+  assert_eq!(
+    (!&set2).as_slice(),
+    &[
+      Gap::new(Included(i32::MIN), Excluded(5)),
+      Gap::new(Included(25), Included(i32::MAX)),
+    ]
+  );
+  assert_eq!(
+    set2.complement(0..=30).as_slice(),
+    &[
+      Gap::new(Included(0), Excluded(5)),
+      Gap::new(Included(25), Excluded(31)),
+    ]
+  );
+  assert_eq!(!&!&set2, set2);
+
+  let mut full = GapSet::new();
+  let _added = full.insert_range(0u8..);
+  assert!((!&full).is_empty());
+  assert_eq!((!&full).range_count(), 0);
+  let mut set = GapSet::new();
+  let _added = set.insert_range(0u8..5);
+  assert_eq!(
+    (!&set).as_slice(),
+    &[Gap::new(Included(5), Included(u8::MAX))]
+  );
+  assert_eq!((!&!&set).as_slice(), &[Gap::new(Included(0), Excluded(5))]);
+
+  let empty = GapSet::new();
+  assert_eq!(&set1 | &empty, set1);
+  assert_eq!(&set1 & &empty, empty);
+}
+
+
+/// Check that a `GapSet` can be collected from and extended with
+/// values and ranges.
+#[test]
+fn gap_set_collection() {
+  let values = btreeset! {2, 3, 7};
+  let set = values.gaps(0..10).collect::<GapSet<_>>();
+  assert_eq!(
+    set.as_slice(),
+    &[
+      Gap::new(Included(0), Excluded(2)),
+      Gap::new(Included(4), Excluded(7)),
+      Gap::new(Included(8), Excluded(10)),
+    ]
+  );
+
+  let mut set = vec![9, 1, 2, 2, 5].into_iter().collect::<GapSet<_>>();
+  set.extend(vec![3, 4]);
+  assert_eq!(
+    set.as_slice(),
+    &[
+      Gap::new(Included(1), Excluded(6)),
+      Gap::new(Included(9), Excluded(10)),
+    ]
+  );
+
+  set.extend(vec![6..9, 12..12]);
+  assert_eq!(set.as_slice(), &[Gap::new(Included(1), Excluded(10))]);
+
+  let set = Vec::<Range<u8>>::new().into_iter().collect::<GapSet<_>>();
+  assert!(set.is_empty());
+
+  let set = vec![Gap::new(Unbounded, Excluded(0u8))]
+    .into_iter()
+    .collect::<GapSet<_>>();
+  assert!(set.is_empty());
+}
+
+
+/// Check that a `GapSet` can be round-tripped through its binary
+/// encoding.
+#[test]
+fn gap_set_binary_encoding() {
+  let empty = GapSet::<u32>::new();
+  assert_eq!(empty.to_bytes(), vec![0]);
+  assert_eq!(GapSet::from_bytes(&empty.to_bytes()), Ok(empty));
+
+  let mut set = GapSet::<u8>::new();
+  set.insert_range(..3);
+  set.insert_range((Excluded(7), Excluded(9)));
+  set.insert_range(200..);
+  assert_eq!(set.to_bytes(), vec![3, 0, 2, 4, 0, 190, 1, 55]);
+
+  let decoded = GapSet::<u8>::from_bytes(&set.to_bytes()).unwrap();
+  assert_eq!(
+    decoded.as_slice(),
+    &[
+      Gap::new(Included(0), Excluded(3)),
+      Gap::new(Included(8), Excluded(9)),
+      Gap::new(Included(200), Included(255)),
+    ]
+  );
+  assert_eq!(decoded, set);
+
+  let mut set = GapSet::<u64>::new();
+  set.insert_range(u64::MAX - 1..);
+  let decoded = GapSet::<u64>::from_bytes(&set.to_bytes()).unwrap();
+  assert_eq!(decoded, set);
+
+  // Ranges touching the minimum value of the type are encoded
+  // correctly.
+  let mut set = GapSet::<u64>::new();
+  set.insert_range(0..5);
+  let complement = !&set;
+  let decoded = GapSet::<u64>::from_bytes(&complement.to_bytes()).unwrap();
+  assert_eq!(decoded, complement);
+  assert_eq!(complement.to_index_bytes().len(), 16);
+
+  let mut set = GapSet::<u8>::new();
+  set.insert_range(..1);
+  assert_eq!(set.to_bytes(), vec![1, 0, 0]);
+  assert_eq!(GapSet::<u8>::from_bytes(&set.to_bytes()), Ok(set));
+
+  assert_eq!(
+    GapSet::<u8>::from_bytes(&[1, 0]),
+    Err(DecodeError::Truncated)
+  );
+  assert_eq!(
+    GapSet::<u8>::from_bytes(&[1, 0, 0, 0]),
+    Err(DecodeError::TrailingData)
+  );
+  assert_eq!(
+    GapSet::<u8>::from_bytes(&[1, 0, 0x80, 0x02]),
+    Err(DecodeError::Overflow)
+  );
+  assert_eq!(
+    GapSet::<u8>::from_bytes(&[1, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f, 0]),
+    Err(DecodeError::Overflow)
+  );
+}
+
+
+/// Check that a `GapSetRef` answers queries just like the `GapSet` it
+/// was created from.
+#[test]
+fn gap_set_ref_queries() {
+  let mut set = GapSet::<u64>::new();
+  set.insert_range(3..=5);
+  set.insert_range(10..20);
+  set.insert_range(u64::MAX - 4..);
+  let bytes = set.to_index_bytes();
+  assert_eq!(bytes.len(), 48);
+
+  let view = GapSetRef::new(&bytes).unwrap();
+  assert_eq!(view.range_count(), 3);
+  assert_eq!(view.iter().collect::<Vec<_>>(), set.as_slice());
+  for value in (0..25).chain(u64::MAX - 10..=u64::MAX) {
+    assert_eq!(view.contains(value), set.contains(value), "{}", value);
+  }
+
+  for range in [
+    (Included(0), Unbounded),
+    (Excluded(4), Included(12)),
+    (Included(6), Excluded(10)),
+  ] {
+    let expected = set.gaps_in(range).collect::<Vec<_>>();
+    assert_eq!(view.gaps_in(range).collect::<Vec<_>>(), expected);
+  }
+
+  let empty = GapSetRef::new(&[]).unwrap();
+  assert!(empty.is_empty());
+  assert!(!empty.contains(0));
+  assert_eq!(
+    empty.gaps_in(..).collect::<Vec<_>>(),
+    vec![Gap::new(Unbounded, Unbounded)]
+  );
+  assert_eq!(GapSetRef::new(&bytes[1..]), Err(DecodeError::Truncated));
+}
+
+
+/// Check that `GapSet` range queries work as expected.
+#[test]
+fn gap_set_range_queries() {
+  let mut set = GapSet::new();
+  set.insert_range(..=3);
+  set.insert_range((Excluded(5), Excluded(9)));
+  set.insert_range(12..);
+
+  assert!(set.contains_range(..2));
+  assert!(set.contains_range(6..=8));
+  assert!(set.contains_range((Excluded(5), Included(8))));
+  assert!(!set.contains_range(5..=8));
+  assert!(!set.contains_range(6..=9));
+  assert!(set.contains_range(20..));
+  assert!(!set.contains_range(..));
+  assert!(set.contains_range(4..4));
+
+  assert!(set.overlaps(3..=4));
+  assert!(!set.overlaps(4..=5));
+  assert!(!set.overlaps(9..12));
+  assert!(set.overlaps(9..=12));
+  assert!(set.overlaps(..));
+  assert!(!set.overlaps(4..4));
+
+  let empty = GapSet::<i32>::new();
+  assert!(!empty.overlaps(..));
+  assert!(!empty.contains_range(0..1));
+}
+
+
+/// Check that the covered ranges and the gaps of a `GapSet` can be
+/// iterated over.
+#[test]
+fn gap_set_iteration() {
+  let set = vec![1..3, 5..8].into_iter().collect::<GapSet<_>>();
+  let expected = vec![
+    Gap::new(Included(1), Excluded(3)),
+    Gap::new(Included(5), Excluded(8)),
+  ];
+  assert_eq!(set.iter().copied().collect::<Vec<_>>(), expected);
+
+  let mut covered = Vec::new();
+  for range in &set {
+    covered.push(*range);
+  }
+  assert_eq!(covered, expected);
+
+  let gaps = set.gaps(0..10).collect::<Vec<_>>();
+  let expected_gaps = vec![
+    Gap::new(Included(0), Excluded(1)),
+    Gap::new(Included(3), Excluded(5)),
+    Gap::new(Included(8), Excluded(10)),
+  ];
+  assert_eq!(gaps, expected_gaps);
+
+  assert_eq!(set.into_iter().collect::<Vec<_>>(), expected);
+}
+
+
+/// Check that a `GapTracker` reports gaps correctly as values arrive.
+#[test]
+fn gap_tracker_observation() {
+  let mut tracker = GapTracker::new(10u8);
+  assert_eq!(tracker.highest(), None);
+  assert_eq!(tracker.first_missing(), Some(10));
+  assert_eq!(tracker.gaps().next(), None);
+
+  assert!(tracker.observe(12));
+  assert!(!tracker.observe(12));
+  assert!(!tracker.observe(9));
+  assert_eq!(tracker.first_missing(), Some(10));
+  assert_eq!(
+    tracker.gaps().collect::<Vec<_>>(),
+    vec![Gap::new(Included(10), Excluded(12))]
+  );
+
+  assert!(tracker.observe(10));
+  assert!(tracker.observe(11));
+  assert_eq!(tracker.first_missing(), Some(13));
+  assert_eq!(tracker.gaps().next(), None);
+  assert!(tracker.is_observed(11));
+  assert!(!tracker.is_observed(13));
+
+  (13..=255).for_each(|value| {
+    let _ = tracker.observe(value);
+  });
+  assert_eq!(tracker.highest(), Some(255));
+  assert_eq!(tracker.first_missing(), None);
+}
+
+
+/// Check that a `SeqWindow` tracks received sequence numbers and
+/// produces acknowledgment ranges.
+#[test]
+fn seq_window_acknowledgment() {
+  let mut window = SeqWindow::new(10u8, 8);
+  assert_eq!(window.advance_base(), Some(10));
+  assert_eq!(window.ack_ranges(4), Vec::new());
+  assert_eq!(window.missing_ranges(4), Vec::new());
+
+  assert!(window.observe(12));
+  assert!(!window.observe(12));
+  assert!(!window.observe(9));
+  assert!(!window.observe(18));
+  assert!(window.observe(14));
+  assert!(window.observe(17));
+  assert_eq!(window.advance_base(), Some(10));
+  assert_eq!(
+    window.ack_ranges(2),
+    vec![
+      Gap::new(Included(12), Excluded(13)),
+      Gap::new(Included(14), Excluded(15)),
+    ]
+  );
+  assert_eq!(
+    window.missing_ranges(4),
+    vec![
+      Gap::new(Included(10), Excluded(12)),
+      Gap::new(Included(13), Excluded(14)),
+      Gap::new(Included(15), Excluded(17)),
+    ]
+  );
+
+  assert!(window.observe(10));
+  assert!(window.observe(11));
+  assert_eq!(window.advance_base(), Some(13));
+  assert!(!window.observe(12));
+  assert!(window.in_window(20));
+  assert!(!window.in_window(21));
+  assert_eq!(
+    window.ack_ranges(4),
+    vec![
+      Gap::new(Included(14), Excluded(15)),
+      Gap::new(Included(17), Excluded(18)),
+    ]
+  );
+
+  let mut window = SeqWindow::new(250u8, 16);
+  (250..=255).for_each(|seq| assert!(window.observe(seq)));
+  assert_eq!(window.base(), Some(250));
+  assert_eq!(window.ack_ranges(4), Vec::new());
+  assert_eq!(window.advance_base(), None);
+  assert_eq!(window.base(), None);
+  assert_eq!(window.advance_base(), None);
+  assert_eq!(
+    window.ack_ranges(4),
+    vec![Gap::new(Included(250), Included(255))]
+  );
+  assert_eq!(window.missing_ranges(4), Vec::new());
+  assert!(!window.in_window(255));
+  assert!(!window.observe(255));
+
+  let mut window = SeqWindow::new(250u8, 10);
+  (250..=255).for_each(|seq| assert!(window.observe(seq)));
+  assert_eq!(window.advance_base(), None);
+  assert_eq!(
+    window.ack_ranges(4),
+    vec![Gap::new(Included(250), Included(255))]
+  );
+}